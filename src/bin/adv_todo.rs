@@ -0,0 +1,184 @@
+// To Do list - add, remove, edit, view, mark complete, search, save/load, undo, priorities, exit
+
+use std::collections::VecDeque;
+use std::process;
+use task::storage::{DEFAULT_FILE, load_tasks, save_tasks};
+use task::ui::{StdinUi, Ui};
+use task::{Task, TaskList};
+
+fn main() {
+    let mut ui = StdinUi;
+    let mut task_list = match load_tasks(DEFAULT_FILE) {
+        Ok(task_list) => task_list,
+        Err(error) => {
+            eprintln!("Failed to load {}: {}", DEFAULT_FILE, error);
+            process::exit(1);
+        }
+    };
+    let mut undo_stack: VecDeque<TaskList> = VecDeque::new();
+
+    loop {
+        println!("\n--- To Do List ---");
+        println!("1. Add task");
+        println!("2. Remove task");
+        println!("3. View tasks");
+        println!("4. Edit task");
+        println!("5. Mark task as complete/incomplete");
+        println!("6. Search tasks");
+        println!("7. Save tasks");
+        println!("8. Load tasks");
+        println!("9. Undo last action");
+        println!("10. Exit");
+        let Some(choice) = ui.read_line("Enter your choice: ") else {
+            break;
+        };
+
+        match choice.as_str() {
+            "1" => {
+                undo_stack.push_back(task_list.clone());
+                add_task(&mut ui, &mut task_list);
+            }
+            "2" => {
+                undo_stack.push_back(task_list.clone());
+                remove_task(&mut ui, &mut task_list);
+            }
+            "3" => view_tasks(&task_list),
+            "4" => {
+                undo_stack.push_back(task_list.clone());
+                edit_task(&mut ui, &mut task_list);
+            }
+            "5" => {
+                undo_stack.push_back(task_list.clone());
+                mark_task(&mut ui, &mut task_list);
+            }
+            "6" => search_tasks(&mut ui, &task_list),
+            "7" => match save_tasks(DEFAULT_FILE, &task_list) {
+                Ok(()) => println!("Tasks saved to file."),
+                Err(error) => println!("Failed to save tasks: {}", error),
+            },
+            "8" => match load_tasks(DEFAULT_FILE) {
+                Ok(loaded) => {
+                    task_list = loaded;
+                    println!("Tasks loaded from file.");
+                }
+                Err(error) => println!("Failed to load tasks: {}", error),
+            },
+            "9" => {
+                if let Some(prev) = undo_stack.pop_back() {
+                    task_list = prev;
+                    println!("Undo successful.");
+                } else {
+                    println!("Nothing to undo.");
+                }
+            }
+            "10" => {
+                println!("Exiting...");
+                break;
+            }
+            _ => println!("Invalid choice. Please try again."),
+        }
+    }
+}
+
+fn read_task_index(ui: &mut dyn Ui, task_list: &TaskList, prompt: &str) -> Option<usize> {
+    match task_list.resolve(&ui.ask(prompt)) {
+        Ok(index) => Some(index),
+        Err(message) => {
+            println!("{}", message);
+            None
+        }
+    }
+}
+
+fn add_task(ui: &mut dyn Ui, task_list: &mut TaskList) {
+    let description = ui.ask("Enter a description for the new task: ");
+    if description.is_empty() {
+        println!("Description cannot be empty.");
+        return;
+    }
+
+    let mut task = Task::new(description);
+    task.priority = ui
+        .ask("Enter priority (critical, high, medium, low or 1-5, optional): ")
+        .parse()
+        .unwrap_or_default();
+    match task_list.add(task) {
+        Ok(id) => println!("Task {} added.", id),
+        Err(error) => println!("{}", error),
+    }
+}
+
+fn remove_task(ui: &mut dyn Ui, task_list: &mut TaskList) {
+    if task_list.is_empty() {
+        println!("No tasks found.");
+        return;
+    }
+    view_tasks(task_list);
+    if let Some(index) = read_task_index(ui, task_list, "Enter the task id to remove: ") {
+        if ui.confirm("Are you sure you want to delete this task? (y/n): ") {
+            task_list.remove(index);
+            println!("Task removed.");
+        } else {
+            println!("Cancelled.");
+        }
+    }
+}
+
+fn edit_task(ui: &mut dyn Ui, task_list: &mut TaskList) {
+    if task_list.is_empty() {
+        println!("No tasks found.");
+        return;
+    }
+    view_tasks(task_list);
+    if let Some(index) = read_task_index(ui, task_list, "Enter the task id to edit: ") {
+        let new_task = ui.ask("Enter the updated task description: ");
+        if !new_task.is_empty() {
+            task_list.get_mut(index).unwrap().description = new_task;
+            println!("Task updated.");
+        } else {
+            println!("Description cannot be empty.");
+        }
+    }
+}
+
+fn mark_task(ui: &mut dyn Ui, task_list: &mut TaskList) {
+    if task_list.is_empty() {
+        println!("No tasks found.");
+        return;
+    }
+    view_tasks(task_list);
+    if let Some(index) = read_task_index(
+        ui,
+        task_list,
+        "Enter the task id to toggle complete/incomplete: ",
+    ) {
+        let status = if task_list.toggle(index) == Some(true) {
+            "completed"
+        } else {
+            "pending"
+        };
+        println!("Task marked as {}.", status);
+    }
+}
+
+fn search_tasks(ui: &mut dyn Ui, task_list: &TaskList) {
+    let keyword = ui.ask("Enter keyword to search: ");
+    let found = task_list.search(&keyword);
+    if found.is_empty() {
+        println!("No tasks found matching '{}'.", keyword.to_lowercase());
+    }
+    for task in found {
+        task.display();
+    }
+}
+
+fn view_tasks(task_list: &TaskList) {
+    if task_list.is_empty() {
+        println!("No tasks found.");
+    } else {
+        println!("\n--- Task List ---");
+        for task in task_list {
+            task.display();
+        }
+    }
+}
@@ -0,0 +1,8 @@
+// To Do list - add, remove, edit, view, mark complete, search, save/load, JSON, undo, sort, exit
+
+use std::{env, process};
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    process::exit(task::cli::run(&args));
+}
@@ -0,0 +1,95 @@
+// To Do list - add, remove, edit, view, exit
+
+use task::ui::{StdinUi, Ui};
+use task::{Task, TaskList};
+
+fn main() {
+    let mut ui = StdinUi;
+    let mut task_list = TaskList::new();
+
+    loop {
+        println!("\n--- To Do List ---");
+        println!("1. Add task");
+        println!("2. Remove task");
+        println!("3. View tasks");
+        println!("4. Edit task");
+        println!("5. Exit");
+        let Some(choice) = ui.read_line("Enter your choice: ") else {
+            break;
+        };
+
+        match choice.as_str() {
+            "1" => add_task(&mut ui, &mut task_list),
+            "2" => remove_task(&mut ui, &mut task_list),
+            "3" => view_tasks(&task_list),
+            "4" => edit_task(&mut ui, &mut task_list),
+            "5" => {
+                println!("Exiting...");
+                break;
+            }
+            _ => println!("Invalid choice. Please try again."),
+        }
+    }
+}
+
+fn read_task_index(ui: &mut dyn Ui, task_list: &TaskList, prompt: &str) -> Option<usize> {
+    match task_list.resolve(&ui.ask(prompt)) {
+        Ok(index) => Some(index),
+        Err(message) => {
+            println!("{}", message);
+            None
+        }
+    }
+}
+
+fn add_task(ui: &mut dyn Ui, task_list: &mut TaskList) {
+    let description = ui.ask("Enter a description for the new task: ");
+    if !description.is_empty() {
+        match task_list.add(Task::new(description)) {
+            Ok(id) => println!("Task {} added.", id),
+            Err(error) => println!("{}", error),
+        }
+    } else {
+        println!("Description cannot be empty.");
+    }
+}
+
+fn remove_task(ui: &mut dyn Ui, task_list: &mut TaskList) {
+    if task_list.is_empty() {
+        println!("No tasks found.");
+        return;
+    }
+    view_tasks(task_list);
+    if let Some(index) = read_task_index(ui, task_list, "Enter the task id to remove: ") {
+        task_list.remove(index);
+        println!("Task removed.");
+    }
+}
+
+fn edit_task(ui: &mut dyn Ui, task_list: &mut TaskList) {
+    if task_list.is_empty() {
+        println!("No tasks found.");
+        return;
+    }
+    view_tasks(task_list);
+    if let Some(index) = read_task_index(ui, task_list, "Enter the task id to edit: ") {
+        let new_task = ui.ask("Enter the updated task description: ");
+        if !new_task.is_empty() {
+            task_list.get_mut(index).unwrap().description = new_task;
+            println!("Task updated.");
+        } else {
+            println!("Description cannot be empty.");
+        }
+    }
+}
+
+fn view_tasks(task_list: &TaskList) {
+    if task_list.is_empty() {
+        println!("No tasks found.");
+    } else {
+        println!("\n--- Task List ---");
+        for task in task_list {
+            println!("{}. {}", task.id, task.description);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+// To Do list - tasks with ids and titles, priority 0-10 (10 is critical), complete/pending views, sort by priority
+
+use chrono::Local;
+use task::priority::PriorityScale;
+use task::task::Status;
+use task::ui::{StdinUi, Ui};
+use task::{Task, TaskList};
+
+fn main() {
+    let mut ui = StdinUi;
+    let mut tasks = TaskList::new();
+    loop {
+        println!("Welcome to the Todo list");
+        println!("1. Add task: ");
+        println!("2. Remove Task: ");
+        println!("3. View Task: ");
+        println!("4. View All Tasks: ");
+        println!("5. View Pending Task: ");
+        println!("6. Mark Complete: ");
+        println!("7. Viewed All Completed Tasks: ");
+        println!("8. View Pending Tasks: ");
+        println!("9. Exit: ");
+        println!("10. Sort Tasks by Priority: ");
+
+        let Some(choice) = ui.read_line("") else {
+            break;
+        };
+        let choice: i32 = match choice.parse::<i32>() {
+            Ok(num) => num,
+            Err(_) => {
+                println!("Invalid Input! Please enter a number.");
+                continue;
+            }
+        };
+        println!("Your choice is {:?}", choice);
+
+        match choice {
+            1 => add_task(&mut ui, &mut tasks),
+            2 => {
+                if let Some(index) = read_task_index(&mut ui, &tasks) {
+                    let removed = tasks.remove(index).unwrap();
+                    println!("Task removed: {:#?}", removed);
+                }
+            }
+            3 | 4 => view_tasks(&tasks),
+            5 | 8 => {
+                println!("Pending Tasks:");
+                for task in tasks.pending() {
+                    task.display();
+                }
+            }
+            6 => {
+                if let Some(index) = read_task_index(&mut ui, &tasks) {
+                    tasks
+                        .get_mut(index)
+                        .unwrap()
+                        .set_status(Status::Done, Local::now());
+                    println!("Marked Completed");
+                }
+            }
+            7 => {
+                println!("Completed Tasks:");
+                for task in tasks.completed() {
+                    task.display();
+                }
+            }
+            10 => {
+                // critical first, as the highest number was before levels
+                tasks.sort_by_priority();
+                println!("Tasks sorted by priority:");
+                view_tasks(&tasks);
+            }
+            9 => {
+                println!("Exiting the program");
+                break;
+            }
+            _ => println!("Invalid choice! Please try again."),
+        }
+    }
+}
+
+fn read_task_index(ui: &mut dyn Ui, tasks: &TaskList) -> Option<usize> {
+    match tasks.resolve(&ui.ask("Enter the TaskId\n")) {
+        Ok(index) => Some(index),
+        Err(message) => {
+            println!("{}", message);
+            None
+        }
+    }
+}
+
+fn add_task(ui: &mut dyn Ui, tasks: &mut TaskList) {
+    let title = ui.ask("Enter the task title:\n");
+    let description = ui.ask("Enter the task description:\n");
+    let scale = PriorityScale::preset("0-10").expect("0-10 is a built-in scale");
+    let priority = match scale.parse(&ui.ask("Enter the task priority (0-10):\n")) {
+        Ok(priority) => priority,
+        Err(_) => {
+            println!("Priority should be between 0 and 10, or a level such as high!");
+            return;
+        }
+    };
+
+    let mut task = Task::new(description);
+    task.title = Some(title);
+    task.priority = priority;
+    match tasks.add(task) {
+        Ok(id) => println!("Task {} added Successfully", id),
+        Err(error) => println!("{}", error),
+    }
+}
+
+fn view_tasks(tasks: &TaskList) {
+    println!("Tasks:");
+    for task in tasks {
+        task.display();
+    }
+}
@@ -0,0 +1,126 @@
+// To Do list - add task, remove task, view task, change priority, exit
+
+use task::task::Status;
+use task::ui::{StdinUi, Ui};
+use task::{Priority, Task, TaskList};
+
+fn main() {
+    let mut ui = StdinUi;
+    let mut task_list = TaskList::new();
+    loop {
+        println!("\nPlease enter your choice");
+        println!("1. Add task");
+        println!("2. Remove task");
+        println!("3. View task");
+        println!("4. Change Priority");
+        println!("5. Exit");
+        let Some(choice) = ui.read_line("") else {
+            break;
+        };
+        let choice: i32 = match choice.parse() {
+            Ok(num) => num,
+            Err(_) => {
+                println!("Invalid number");
+                continue;
+            }
+        };
+        match choice {
+            1 => add_task(&mut ui, &mut task_list),
+            2 => remove_task(&mut ui, &mut task_list),
+            3 => view_task(&task_list),
+            4 => change_priority(&mut ui, &mut task_list),
+            5 => {
+                println!("Exiting....");
+                break;
+            }
+            _ => println!("Wrong Input: Try Again"),
+        }
+    }
+}
+
+fn read_priority(ui: &mut dyn Ui, prompt: &str) -> Option<Priority> {
+    match ui.ask(prompt).parse() {
+        Ok(priority) => Some(priority),
+        Err(_) => {
+            println!("Priority must be between 1 and 5, or a level such as high.");
+            None
+        }
+    }
+}
+
+fn read_task_index(ui: &mut dyn Ui, task_list: &TaskList) -> Option<usize> {
+    match task_list.resolve(&ui.ask("")) {
+        Ok(index) => Some(index),
+        Err(message) => {
+            println!("{}", message);
+            None
+        }
+    }
+}
+
+fn add_task(ui: &mut dyn Ui, task_list: &mut TaskList) {
+    let description = ui.ask("Please enter a description for the task:\n");
+    let Some(priority) = read_priority(ui, "Please enter a priority for the task (1-5):\n") else {
+        return;
+    };
+    if !description.is_empty() {
+        let mut task = Task::new(description);
+        task.priority = priority;
+        match task_list.add(task) {
+            Ok(id) => println!("Task {} added.", id),
+            Err(error) => println!("{}", error),
+        }
+    } else {
+        println!("Description cannot be empty");
+    }
+}
+
+fn remove_task(ui: &mut dyn Ui, task_list: &mut TaskList) {
+    if task_list.is_empty() {
+        println!("No tasks are found");
+        return;
+    }
+    println!("Please enter the task id which you want to remove:");
+    view_task(task_list);
+    if let Some(index) = read_task_index(ui, task_list) {
+        task_list.remove(index);
+        println!("Task is removed");
+    }
+}
+
+fn view_task(task_list: &TaskList) {
+    if task_list.is_empty() {
+        println!("No tasks are found");
+        return;
+    }
+    println!("Task list:");
+    for task in task_list {
+        let status = match task.status {
+            Status::Pending => "Pending",
+            Status::InProgress => "In progress",
+            Status::Waiting => "Waiting",
+            Status::Done => "Completed",
+            Status::Cancelled => "Cancelled",
+        };
+        println!(
+            "{}. {} (Priority: {}, Status: {})",
+            task.id, task.description, task.priority, status
+        );
+    }
+}
+
+fn change_priority(ui: &mut dyn Ui, task_list: &mut TaskList) {
+    if task_list.is_empty() {
+        println!("No tasks are found");
+        return;
+    }
+    println!("Please enter the task id for which you want to change priority:");
+    view_task(task_list);
+    let Some(index) = read_task_index(ui, task_list) else {
+        return;
+    };
+    if let Some(priority) = read_priority(ui, "Enter new priority (1-5):\n") {
+        task_list.get_mut(index).unwrap().priority = priority;
+        println!("Priority updated.");
+    }
+}
@@ -0,0 +1,102 @@
+// To Do list - add task, remove task, view task, edit task, exit
+
+use task::ui::{StdinUi, Ui};
+use task::{Task, TaskList};
+
+fn main() {
+    let mut ui = StdinUi;
+    let mut task_list = TaskList::new();
+    loop {
+        println!("Please enter your choice");
+        println!("1. Add task");
+        println!("2. Remove task");
+        println!("3. View task");
+        println!("4. Edit task");
+        println!("5. Exit");
+        let Some(choice) = ui.read_line("") else {
+            break;
+        };
+        let choice: i32 = match choice.parse() {
+            Ok(num) => num,
+            Err(_) => {
+                println!("Invalid number");
+                continue;
+            }
+        };
+        match choice {
+            1 => add_task(&mut ui, &mut task_list),
+            2 => remove_task(&mut ui, &mut task_list),
+            3 => view_task(&task_list),
+            4 => edit_task(&mut ui, &mut task_list),
+            5 => {
+                println!("Exiting....");
+                break;
+            }
+            _ => println!("Wrong Input: Try Again"),
+        }
+    }
+}
+
+fn read_task_index(ui: &mut dyn Ui, task_list: &TaskList) -> Option<usize> {
+    match task_list.resolve(&ui.ask("")) {
+        Ok(index) => Some(index),
+        Err(message) => {
+            println!("{}", message);
+            None
+        }
+    }
+}
+
+fn add_task(ui: &mut dyn Ui, task_list: &mut TaskList) {
+    let description = ui.ask("Please enter a description for the task:\n");
+    if !description.is_empty() {
+        if let Err(error) = task_list.add(Task::new(description)) {
+            println!("{}", error);
+        }
+    } else {
+        println!("Description cannot be empty");
+    }
+}
+
+fn remove_task(ui: &mut dyn Ui, task_list: &mut TaskList) {
+    if task_list.is_empty() {
+        println!("No tasks are found");
+        return;
+    }
+    println!("Please enter the task id which you want to remove");
+    view_task(task_list);
+    if let Some(index) = read_task_index(ui, task_list) {
+        task_list.remove(index);
+        println!("Task is removed");
+    }
+}
+
+fn view_task(task_list: &TaskList) {
+    if task_list.is_empty() {
+        println!("No tasks are found");
+        return;
+    }
+    println!("Task list:");
+    for task in task_list {
+        println!("{}. {}", task.id, task.description);
+    }
+}
+
+fn edit_task(ui: &mut dyn Ui, task_list: &mut TaskList) {
+    if task_list.is_empty() {
+        println!("No tasks are found");
+        return;
+    }
+    println!("Please enter the task id which you want to edit:");
+    view_task(task_list);
+    let Some(index) = read_task_index(ui, task_list) else {
+        return;
+    };
+    let new_task = ui.ask("Type updated task:\n");
+    if !new_task.is_empty() {
+        task_list.get_mut(index).unwrap().description = new_task;
+        println!("Task is updated");
+    } else {
+        println!("Task cannot be empty");
+    }
+}
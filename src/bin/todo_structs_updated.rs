@@ -0,0 +1,221 @@
+// To Do list - add (no duplicates), remove with confirmation, views, toggle complete, change priority, sort, exit
+
+use task::ui::{StdinUi, Ui};
+use task::{Priority, Task, TaskList};
+
+fn main() {
+    let mut ui = StdinUi;
+    let mut task_list = TaskList::new();
+    loop {
+        println!("\nPlease enter your choice");
+        println!("1. Add task");
+        println!("2. Remove task");
+        println!("3. View all tasks");
+        println!("4. View Completed tasks");
+        println!("5. View Pending tasks");
+        println!("6. Toggle Complete/Incomplete");
+        println!("7. Change Priority");
+        println!("8. Sort Tasks");
+        println!("9. Exit");
+        let Some(choice) = ui.read_line("") else {
+            break;
+        };
+        let choice: i32 = match choice.parse() {
+            Ok(num) => num,
+            Err(_) => {
+                println!("Invalid number");
+                continue;
+            }
+        };
+        match choice {
+            1 => add_task(&mut ui, &mut task_list),
+            2 => remove_task(&mut ui, &mut task_list),
+            3 => view_task(&task_list),
+            4 => view_completed_task(&task_list),
+            5 => view_pending_task(&task_list),
+            6 => toggle_complete(&mut ui, &mut task_list),
+            7 => change_priority(&mut ui, &mut task_list),
+            8 => sort_tasks(&mut ui, &mut task_list),
+            9 => {
+                println!("Exiting....");
+                break;
+            }
+            _ => println!("Wrong Input: Try Again"),
+        }
+    }
+}
+
+fn is_empty(task_list: &TaskList) -> bool {
+    if task_list.is_empty() {
+        println!("No tasks are found");
+        true
+    } else {
+        false
+    }
+}
+
+fn read_priority(ui: &mut dyn Ui, prompt: &str) -> Option<Priority> {
+    match ui.ask(prompt).parse() {
+        Ok(priority) => Some(priority),
+        Err(_) => {
+            println!("Priority must be between 1 and 5, or a level such as high.");
+            None
+        }
+    }
+}
+
+fn read_task_index(ui: &mut dyn Ui, task_list: &TaskList) -> Option<usize> {
+    match task_list.resolve(&ui.ask("")) {
+        Ok(index) => Some(index),
+        Err(message) => {
+            println!("{}", message);
+            None
+        }
+    }
+}
+
+fn add_task(ui: &mut dyn Ui, task_list: &mut TaskList) {
+    let description = ui.ask("Please enter a description for the task:\n");
+    if description.is_empty() {
+        println!("Description cannot be empty");
+        return;
+    }
+    let Some(priority) = read_priority(ui, "Please enter a priority for the task (1-5):\n") else {
+        return;
+    };
+
+    // Prevent duplicate
+    if task_list
+        .iter()
+        .any(|t| t.description == description && t.priority == priority)
+    {
+        println!("Task with same description and priority already exists.");
+        return;
+    }
+
+    let mut task = Task::new(description);
+    task.priority = priority;
+    match task_list.add(task) {
+        Ok(id) => println!("Task {} added.", id),
+        Err(error) => println!("{}", error),
+    }
+}
+
+fn remove_task(ui: &mut dyn Ui, task_list: &mut TaskList) {
+    if is_empty(task_list) {
+        return;
+    }
+    println!("Please enter the task id which you want to remove:");
+    view_task(task_list);
+    if let Some(index) = read_task_index(ui, task_list) {
+        if ui.confirm("Are you sure you want to delete this task? (y/n):\n") {
+            task_list.remove(index);
+            println!("Task is removed");
+        } else {
+            println!("Task not removed");
+        }
+    }
+}
+
+fn view_task(task_list: &TaskList) {
+    if is_empty(task_list) {
+        return;
+    }
+    println!("Task list:");
+    for task in task_list {
+        let status = if task.is_done() {
+            "Completed"
+        } else {
+            "Pending"
+        };
+        println!(
+            "{}. {} (Priority: {}, Status: {})",
+            task.id, task.description, task.priority, status
+        );
+    }
+}
+
+fn print_filtered<'a>(heading: &str, empty_message: &str, tasks: impl Iterator<Item = &'a Task>) {
+    println!("{}", heading);
+    let mut found = false;
+    for task in tasks {
+        println!(
+            "{}. {} (Priority: {})",
+            task.id, task.description, task.priority
+        );
+        found = true;
+    }
+    if !found {
+        println!("{}", empty_message);
+    }
+}
+
+fn view_completed_task(task_list: &TaskList) {
+    if is_empty(task_list) {
+        return;
+    }
+    print_filtered(
+        "Completed tasks:",
+        "No completed tasks found.",
+        task_list.completed(),
+    );
+}
+
+fn view_pending_task(task_list: &TaskList) {
+    if is_empty(task_list) {
+        return;
+    }
+    print_filtered(
+        "Pending tasks:",
+        "No pending tasks found.",
+        task_list.pending(),
+    );
+}
+
+fn toggle_complete(ui: &mut dyn Ui, task_list: &mut TaskList) {
+    if is_empty(task_list) {
+        return;
+    }
+    println!("Please enter the task id to toggle complete/incomplete:");
+    view_task(task_list);
+    if let Some(index) = read_task_index(ui, task_list) {
+        let status = if task_list.toggle(index) == Some(true) {
+            "completed"
+        } else {
+            "pending"
+        };
+        println!("Task is now marked as {}.", status);
+    }
+}
+
+fn change_priority(ui: &mut dyn Ui, task_list: &mut TaskList) {
+    if is_empty(task_list) {
+        return;
+    }
+    println!("Please enter the task id for which you want to change priority:");
+    view_task(task_list);
+    let Some(index) = read_task_index(ui, task_list) else {
+        return;
+    };
+    if let Some(priority) = read_priority(ui, "Enter new priority (1-5):\n") {
+        task_list.get_mut(index).unwrap().priority = priority;
+        println!("Priority updated.");
+    }
+}
+
+fn sort_tasks(ui: &mut dyn Ui, task_list: &mut TaskList) {
+    if is_empty(task_list) {
+        return;
+    }
+    match ui.ask("Sort by: 1. Priority  2. Status\n").as_str() {
+        "1" => {
+            task_list.sort_by_priority();
+            println!("Tasks sorted by priority.");
+        }
+        "2" => {
+            task_list.sort_by_status();
+            println!("Tasks sorted by status.");
+        }
+        _ => println!("Invalid choice."),
+    }
+}
@@ -0,0 +1,1709 @@
+//! Non-interactive subcommands, e.g. `task add "desc" --priority 2`.
+
+use crate::config::{Config, DEFAULT_CONFIG};
+use crate::due::Due;
+use crate::history::{DEFAULT_HISTORY_LIMIT, History, Op};
+use crate::journal;
+use crate::menu::Menu;
+use crate::merge::merge;
+use crate::ops::{self, CompletionRules, DateField, NewTask, TaskFilter};
+use crate::priority::PriorityScale;
+use crate::query::Query;
+use crate::recur::Recurrence;
+use crate::sort::{SortKey, SortSpec};
+use crate::storage::{self, DEFAULT_FILE, DEFAULT_JSON_FILE, LoadMode, LoadReport};
+use crate::task::Status;
+use crate::ui::{StdinUi, Ui};
+use crate::urgency::Urgency;
+use crate::{Result, Task, TaskError, TaskList};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime};
+use std::io::{self, IsTerminal};
+
+/// Exit code for a command that ran but failed (bad task number, I/O error).
+pub const EXIT_FAILURE: i32 = 1;
+/// Exit code for malformed arguments.
+pub const EXIT_USAGE: i32 = 2;
+
+pub const USAGE: &str = "\
+Usage: task [OPTIONS] [COMMAND]
+
+Without a command the interactive menu starts.
+
+Options:
+  -f, --file <path>   task file to use (default: tasks.txt)
+  --lenient           load despite malformed lines, moving them to <file>.rejected
+  --backups <n>       backups to keep on save (default: $TASK_BACKUPS or 3)
+  --autosave          menu saves after every change (or set TASK_AUTOSAVE=1)
+  --no-autosave       menu saves only when asked (the default)
+  --complete-parents  completing the last open subtask completes its parent
+                      (or set TASK_COMPLETE_PARENTS=1)
+  --config <path>     settings file with named reports (default: $TASKRC or .taskrc)
+
+Commands:
+  add <description> [--priority <level>] [--due <date>] [--parent <id>]
+      [--depends <id> ...] [--recur <rule>]
+      [--scheduled <date>] [--wait <date>] [--until <date>]
+  list [--pending | --completed | --waiting] [--all] [--sort <keys>] [<query>]
+                         --all also shows waiting and expired tasks; --sort
+                         orders what is shown without reordering the list
+  search <query>         like list --all, without the tree
+  edit <id> <description | +tag | -tag | project:<name> ...>
+  done <id> [--force]     --force completes it even if it is blocked
+  undone <id>
+  start <id>             mark the task in progress
+  stop <id>              put an in-progress task back to pending
+  cancel <id>            close the task without doing it, with its open subtasks
+  status <id> <pending | in-progress | waiting | done | cancelled>
+  rm <id>...             also removes their subtasks
+  move <id> <parent-id | --top>
+  depend <id> <on-id>    <id> waits until <on-id> is done
+  undepend <id> <on-id>
+  date <id> <due | scheduled | wait | until> <date | none>
+                         wait hides the task until that day; until hides it after
+  recur <id> <rule | none>
+                         repeat the task, e.g. daily, every 3 days, weekly on mon,thu,
+                         monthly on day 15, monthly on the last fri, yearly, or an
+                         RRULE such as FREQ=WEEKLY;INTERVAL=2;BYDAY=MO
+  next                   list the open tasks that are not blocked, most urgent first
+  sort <keys>            reorder the list, e.g. sort due+,priority-,created+
+  why <id>               show what makes up the task's urgency score
+  report [<name> [<query>]]
+                         run a named report, narrowed by the query; without a name,
+                         list the reports (built in: today, overdue, next, waiting)
+  export [--json] [<query>]
+  import <file> [--scale <1-5 | 0-10>]
+                         add the tasks of a JSON export or an older task file,
+                         reading numeric priorities on the scale (default: the
+                         priority.scale setting, 1 being critical)
+  check
+  restore-backup [<n>]   list backups, or restore backup <n> (1 is the newest)
+  undo                   undo the last change, even from an earlier session
+  redo                   redo the last undone change
+  history                list the changes that can be undone and redone
+  log                    list every saved change with its time and user
+  as-of <YYYY-MM-DD>     list the tasks as they were at the end of that day
+  compact-journal [<YYYY-MM-DD>]
+                         fold the journal up to that day (default: all) into a snapshot
+  help
+
+<id> is a task id as shown by `list`, or a unique prefix of the task's uuid.
+done, undone, start, stop, cancel, status and rm take several ids, or a query
+instead, and then change every task `list <query>` shows; the query needs a
+term, an operator or a \"quoted phrase\". Removing or closing more than one
+task by query asks first, unless --yes is given.
+<query> is words to find in the title or description (\"quoted\" for a phrase)
+and terms: status:pending (or open, closed), priority<=high, priority:none,
+due.before:<date>, due.after:<date>, due:<date>, due:none (also scheduled,
+wait, until, created, modified and completed), +tag, -tag, project:<name>,
+blocked:yes and blocked:no.
+Terms side by side must all match; or, not and parentheses combine them.
+<keys> are priority, status, due, project, tags, created, modified, completed and
+urgency, separated by commas, each followed by + (ascending) or - (descending). Tasks
+without the value go last unless the key ends in :none-first; ties keep their order.
+Filtered lists and search show the most urgent tasks first unless --sort says otherwise.
+<level> is critical, high, medium, low or none, or a number on the priority.scale
+setting: 1-5 by default, where 1 is critical, 2 high, 3 medium and 4 and 5 low.
+Levels compare from critical down, so priority<=high means high or critical.
+<date> is YYYY-MM-DD, today, tomorrow, fri, next monday, in 3 days, in 2 hours, 2w, eom,
+eow or eoy. A due date may be followed by a time such as 17:00 or 5pm, and
+that by a UTC offset such as +02:00 or utc.";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ListFilter {
+    All,
+    Pending,
+    Completed,
+    /// Pending tasks that are hidden until their wait date.
+    Waiting,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Add(NewTask),
+    /// The tasks to show and, unless empty, the order to show them in.
+    List(ListFilter, TaskFilter, SortSpec),
+    Search(Query),
+    Edit(String, String),
+    /// Task, and whether to complete it even if it is blocked.
+    Done(String, bool),
+    Undone(String),
+    SetStatus(String, Status),
+    Stop(String),
+    Remove(String),
+    /// Task, and the new parent (`None` for the top level).
+    Move(String, Option<String>),
+    /// Task, and the task it waits for.
+    Depend(String, String),
+    Undepend(String, String),
+    Recur(String, Option<Recurrence>),
+    SetDate(String, DateField, Option<Due>),
+    Next,
+    /// Reorders the stored list.
+    Sort(SortSpec),
+    /// Explains a task's urgency score.
+    Why(String),
+    /// A named report narrowed by the query; `None` lists the reports.
+    Report(Option<String>, Query),
+    Export {
+        json: bool,
+        query: Query,
+    },
+    /// A file to add the tasks of, and the scale its numeric priorities
+    /// are on (`None` for the configured one).
+    Import(String, Option<PriorityScale>),
+    /// A change to every task the filter selects, and whether `--yes`
+    /// confirmed it in advance.
+    Bulk(Bulk, TaskFilter, bool),
+    /// A change to each of the listed tasks.
+    Each(Bulk, Vec<String>),
+    Check,
+    RestoreBackup(Option<usize>),
+    Undo,
+    Redo,
+    History,
+    Log,
+    AsOf(NaiveDate),
+    CompactJournal(Option<NaiveDate>),
+    Help,
+}
+
+/// The changes that can be made to many tasks at once.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bulk {
+    /// Whether to complete blocked tasks too.
+    Done {
+        force: bool,
+    },
+    Undone,
+    SetStatus(Status),
+    Stop,
+    Remove,
+}
+
+impl Bulk {
+    /// Whether the change removes or closes tasks, so that a query
+    /// matching several of them asks first.
+    pub fn is_destructive(self) -> bool {
+        matches!(
+            self,
+            Bulk::Remove | Bulk::Done { .. } | Bulk::SetStatus(Status::Done | Status::Cancelled)
+        )
+    }
+}
+
+/// Settings given before the command name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    pub file: String,
+    pub json_file: String,
+    pub load_mode: LoadMode,
+    /// Backups kept by each save.
+    pub backups: usize,
+    /// Whether the menu saves after every change.
+    pub autosave: bool,
+    /// Whether finishing every subtask completes the parent.
+    pub complete_parents: bool,
+    /// Where named reports are defined.
+    pub config: String,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            file: DEFAULT_FILE.to_string(),
+            json_file: DEFAULT_JSON_FILE.to_string(),
+            load_mode: LoadMode::Strict,
+            backups: storage::DEFAULT_BACKUPS,
+            autosave: false,
+            complete_parents: false,
+            config: DEFAULT_CONFIG.to_string(),
+        }
+    }
+}
+
+/// Splits leading options off `args`, returning them and the command words.
+pub fn parse_options(args: &[String]) -> std::result::Result<(Options, &[String]), String> {
+    let mut options = Options::default();
+    if let Ok(value) = std::env::var("TASK_BACKUPS") {
+        options.backups = parse_backups(&value)?;
+    }
+    if let Ok(value) = std::env::var("TASK_AUTOSAVE") {
+        options.autosave = parse_switch("TASK_AUTOSAVE", &value)?;
+    }
+    if let Ok(value) = std::env::var("TASK_COMPLETE_PARENTS") {
+        options.complete_parents = parse_switch("TASK_COMPLETE_PARENTS", &value)?;
+    }
+    if let Ok(value) = std::env::var("TASKRC") {
+        options.config = value;
+    }
+    let mut rest = args;
+    loop {
+        match rest {
+            [flag, tail @ ..] if flag == "--lenient" => {
+                options.load_mode = LoadMode::Lenient;
+                rest = tail;
+            }
+            [flag, path, tail @ ..] if flag == "-f" || flag == "--file" => {
+                options.file = path.clone();
+                rest = tail;
+            }
+            [flag, tail @ ..] if flag == "--autosave" || flag == "--no-autosave" => {
+                options.autosave = flag == "--autosave";
+                rest = tail;
+            }
+            [flag, tail @ ..] if flag == "--complete-parents" => {
+                options.complete_parents = true;
+                rest = tail;
+            }
+            [flag, path, tail @ ..] if flag == "--config" => {
+                options.config = path.clone();
+                rest = tail;
+            }
+            [flag, count, tail @ ..] if flag == "--backups" => {
+                options.backups = parse_backups(count)?;
+                rest = tail;
+            }
+            [flag] if flag == "-f" || flag == "--file" => {
+                return Err("--file needs a path".to_string());
+            }
+            [flag] if flag == "--backups" => return Err("--backups needs a number".to_string()),
+            [flag] if flag == "--config" => return Err("--config needs a path".to_string()),
+            _ => break,
+        }
+    }
+    Ok((options, rest))
+}
+
+fn parse_switch(name: &str, value: &str) -> std::result::Result<bool, String> {
+    match value.trim().to_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" | "" => Ok(false),
+        _ => Err(format!("{} must be 1 or 0, not '{}'.", name, value)),
+    }
+}
+
+fn parse_backups(value: &str) -> std::result::Result<usize, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid backup count '{}'.", value))
+}
+
+/// Parses the arguments that follow the program name, taking numeric
+/// priorities on the 1-5 scale.
+pub fn parse_args(args: &[String]) -> std::result::Result<Command, String> {
+    parse_args_on(args, &PriorityScale::default())
+}
+
+/// Like `parse_args`, taking numeric priorities on `scale`.
+pub fn parse_args_on(
+    args: &[String],
+    scale: &PriorityScale,
+) -> std::result::Result<Command, String> {
+    let (name, rest) = match args.split_first() {
+        Some((name, rest)) => (name.as_str(), rest),
+        None => return Ok(Command::Help),
+    };
+    match name {
+        "add" => parse_add(rest, scale),
+        "list" | "ls" => {
+            let mut filter = ListFilter::All;
+            let mut sort = SortSpec::default();
+            let mut words = Vec::new();
+            let mut args = rest.iter();
+            while let Some(arg) = args.next() {
+                match arg.as_str() {
+                    "--sort" => {
+                        let spec = args
+                            .next()
+                            .ok_or("--sort needs keys, e.g. due+,priority-")?;
+                        sort = parse_sort(spec)?;
+                    }
+                    "--pending" if filter == ListFilter::All => filter = ListFilter::Pending,
+                    "--completed" if filter == ListFilter::All => filter = ListFilter::Completed,
+                    "--waiting" if filter == ListFilter::All => filter = ListFilter::Waiting,
+                    _ => words.push(arg.as_str()),
+                }
+            }
+            let hidden = words.contains(&"--all") || filter == ListFilter::Waiting;
+            words.retain(|word| *word != "--all");
+            if let Some(flag) = words.iter().find(|word| word.starts_with("--")) {
+                return Err(format!(
+                    "list accepts one of --pending, --completed or --waiting, \
+                     --all, --sort and a query, not '{}'",
+                    flag
+                ));
+            }
+            let words: Vec<String> = words.into_iter().map(String::from).collect();
+            let select = TaskFilter {
+                query: parse_query(&words, scale)?,
+                hidden,
+            };
+            Ok(Command::List(filter, select, sort))
+        }
+        "search" => match rest {
+            [] => Err("search needs a query".to_string()),
+            words => Ok(Command::Search(parse_query(words, scale)?)),
+        },
+        "edit" => match rest {
+            [number, words @ ..] if !words.is_empty() => {
+                let description = words.join(" ");
+                if description.trim().is_empty() {
+                    return Err("Description cannot be empty.".to_string());
+                }
+                Ok(Command::Edit(
+                    number.clone(),
+                    description.trim().to_string(),
+                ))
+            }
+            _ => Err("edit needs a task id and a description".to_string()),
+        },
+        "done" => {
+            let force = rest.iter().any(|arg| arg == "--force");
+            let rest: Vec<String> = rest.iter().filter(|a| *a != "--force").cloned().collect();
+            target(
+                name,
+                &rest,
+                |reference| Command::Done(reference, force),
+                Bulk::Done { force },
+                scale,
+            )
+        }
+        "undone" => target(name, rest, Command::Undone, Bulk::Undone, scale),
+        "start" => target(
+            name,
+            rest,
+            |reference| Command::SetStatus(reference, Status::InProgress),
+            Bulk::SetStatus(Status::InProgress),
+            scale,
+        ),
+        "stop" => target(name, rest, Command::Stop, Bulk::Stop, scale),
+        "cancel" => target(
+            name,
+            rest,
+            |reference| Command::SetStatus(reference, Status::Cancelled),
+            Bulk::SetStatus(Status::Cancelled),
+            scale,
+        ),
+        "status" => match rest.iter().rposition(|arg| arg != "--yes") {
+            // the status is the last word; --yes may come after it
+            Some(last) if last > 0 => {
+                let status: Status = rest[last].parse()?;
+                let mut tasks = rest.to_vec();
+                tasks.remove(last);
+                target(
+                    name,
+                    &tasks,
+                    |reference| Command::SetStatus(reference, status),
+                    Bulk::SetStatus(status),
+                    scale,
+                )
+            }
+            _ => Err("status needs a task id (or a query) and a status".to_string()),
+        },
+        "rm" | "remove" => target(name, rest, Command::Remove, Bulk::Remove, scale),
+        "move" | "mv" => match rest {
+            [reference, top] if top == "--top" => Ok(Command::Move(reference.clone(), None)),
+            [reference, parent] => Ok(Command::Move(reference.clone(), Some(parent.clone()))),
+            _ => Err("move needs a task id and a parent id (or --top)".to_string()),
+        },
+        "depend" | "undepend" => match rest {
+            [reference, prerequisite] if name == "depend" => {
+                Ok(Command::Depend(reference.clone(), prerequisite.clone()))
+            }
+            [reference, prerequisite] => {
+                Ok(Command::Undepend(reference.clone(), prerequisite.clone()))
+            }
+            _ => Err(format!("{} needs two task ids", name)),
+        },
+        "date" => match rest {
+            [reference, field, words @ ..] if !words.is_empty() => {
+                let field = DateField::parse(field).ok_or_else(|| {
+                    format!(
+                        "Unknown date '{}' (expected due, scheduled, wait or until).",
+                        field
+                    )
+                })?;
+                let words = words.join(" ");
+                let date = if words.eq_ignore_ascii_case("none") {
+                    None
+                } else {
+                    ops::parse_due_date(&words).map_err(|e| e.to_string())?
+                };
+                Ok(Command::SetDate(reference.clone(), field, date))
+            }
+            _ => Err("date needs a task id, which date, and a date (or none)".to_string()),
+        },
+        "recur" => match rest {
+            [reference, words @ ..] if !words.is_empty() => {
+                let recur = ops::parse_recurrence(&words.join(" ")).map_err(|e| e.to_string())?;
+                Ok(Command::Recur(reference.clone(), recur))
+            }
+            _ => Err("recur needs a task id and a rule (or none)".to_string()),
+        },
+        "next" => no_arguments(name, rest, Command::Next),
+        "why" => match rest {
+            [reference] => Ok(Command::Why(reference.clone())),
+            _ => Err("why needs a task id".to_string()),
+        },
+        "sort" => match rest {
+            [] => Err("sort needs keys, e.g. due+,priority-".to_string()),
+            words => Ok(Command::Sort(parse_sort(&words.join(","))?)),
+        },
+        "report" | "reports" => match rest {
+            [] => Ok(Command::Report(None, Query::All)),
+            [report, words @ ..] => Ok(Command::Report(
+                Some(report.clone()),
+                parse_query(words, scale)?,
+            )),
+        },
+        "export" => {
+            let json = rest.iter().any(|arg| arg == "--json");
+            let words: Vec<String> = rest.iter().filter(|a| *a != "--json").cloned().collect();
+            Ok(Command::Export {
+                json,
+                query: parse_query(&words, scale)?,
+            })
+        }
+        "import" => match rest {
+            [file] => Ok(Command::Import(file.clone(), None)),
+            [file, flag, name] | [flag, name, file] if flag == "--scale" => {
+                let scale = PriorityScale::preset(name).ok_or_else(|| {
+                    format!(
+                        "Unknown priority scale '{}' (expected {}).",
+                        name,
+                        PriorityScale::PRESETS.join(" or ")
+                    )
+                })?;
+                Ok(Command::Import(file.clone(), Some(scale)))
+            }
+            _ => Err("import needs a file, optionally with --scale <1-5 | 0-10>".to_string()),
+        },
+        "check" => Ok(Command::Check),
+        "undo" => no_arguments(name, rest, Command::Undo),
+        "redo" => no_arguments(name, rest, Command::Redo),
+        "history" => no_arguments(name, rest, Command::History),
+        "log" => no_arguments(name, rest, Command::Log),
+        "as-of" => match rest {
+            [date] => Ok(Command::AsOf(parse_date(date)?)),
+            _ => Err("as-of needs a date".to_string()),
+        },
+        "compact-journal" => match rest {
+            [] => Ok(Command::CompactJournal(None)),
+            [date] => Ok(Command::CompactJournal(Some(parse_date(date)?))),
+            _ => Err("compact-journal takes at most one date".to_string()),
+        },
+        "restore-backup" => match rest {
+            [] => Ok(Command::RestoreBackup(None)),
+            [number] => match number.parse() {
+                Ok(number) if number > 0 => Ok(Command::RestoreBackup(Some(number))),
+                _ => Err(format!("Invalid backup number '{}'.", number)),
+            },
+            _ => Err("restore-backup takes at most one backup number".to_string()),
+        },
+        "help" | "--help" | "-h" => Ok(Command::Help),
+        other => Err(format!("Unknown command '{}'.", other)),
+    }
+}
+
+fn parse_add(args: &[String], scale: &PriorityScale) -> std::result::Result<Command, String> {
+    let mut description: Option<String> = None;
+    let mut new = NewTask::default();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--priority" | "-p" => {
+                let value = iter.next().ok_or("--priority needs a value")?;
+                new.priority = ops::parse_priority(value, scale).map_err(|e| e.to_string())?;
+            }
+            "--due" | "-d" => {
+                let value = iter.next().ok_or("--due needs a value")?;
+                let due = ops::parse_due_date(value).map_err(|e| e.to_string())?;
+                new.due_date = due.map(|due| due.date);
+                new.due_at = due.and_then(|due| due.at());
+            }
+            flag @ ("--scheduled" | "--wait" | "--until") => {
+                let value = iter.next().ok_or(format!("{} needs a date", flag))?;
+                let date = match ops::parse_due_date(value).map_err(|e| e.to_string())? {
+                    Some(due) if due.time.is_some() => {
+                        return Err(format!("{} takes a date without a time", flag));
+                    }
+                    due => due.map(|due| due.date),
+                };
+                match flag {
+                    "--scheduled" => new.scheduled = date,
+                    "--wait" => new.wait = date,
+                    _ => new.until = date,
+                }
+            }
+            "--parent" => {
+                let value = iter.next().ok_or("--parent needs a task id")?;
+                new.parent = Some(value.clone());
+            }
+            "--depends" => {
+                let value = iter.next().ok_or("--depends needs a task id")?;
+                new.depends.push(value.clone());
+            }
+            "--recur" => {
+                let value = iter.next().ok_or("--recur needs a rule")?;
+                new.recur = ops::parse_recurrence(value).map_err(|e| e.to_string())?;
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option '{}'.", flag)),
+            text => match &mut description {
+                Some(existing) => {
+                    existing.push(' ');
+                    existing.push_str(text);
+                }
+                None => description = Some(text.to_string()),
+            },
+        }
+    }
+    new.description = description.unwrap_or_default().trim().to_string();
+    if new.description.is_empty() {
+        return Err("Description cannot be empty.".to_string());
+    }
+    Ok(Command::Add(new))
+}
+
+fn no_arguments(
+    name: &str,
+    args: &[String],
+    command: Command,
+) -> std::result::Result<Command, String> {
+    match args {
+        [] => Ok(command),
+        _ => Err(format!("{} takes no arguments", name)),
+    }
+}
+
+fn parse_sort(spec: &str) -> std::result::Result<SortSpec, String> {
+    SortSpec::parse(spec).map_err(|e| format!("Invalid sort: {}.", e))
+}
+
+fn parse_query(words: &[String], scale: &PriorityScale) -> std::result::Result<Query, String> {
+    Query::from_args(words, scale).map_err(|e| format!("Invalid query: {}.", e))
+}
+
+/// Task ids and uuid prefixes name those tasks. Otherwise the words must
+/// be a query with a term, an operator or a quoted phrase, and the command
+/// changes every task `list` would show for it; bare words are refused so
+/// that a mistyped id never turns into a search.
+fn target(
+    name: &str,
+    args: &[String],
+    single: impl FnOnce(String) -> Command,
+    bulk: Bulk,
+    scale: &PriorityScale,
+) -> std::result::Result<Command, String> {
+    let confirmed = args.iter().any(|arg| arg == "--yes");
+    let args: Vec<String> = args.iter().filter(|a| *a != "--yes").cloned().collect();
+    match &args[..] {
+        [] => Err(format!("{} needs a task id or a query", name)),
+        [reference] if is_reference(reference) => Ok(single(reference.clone())),
+        references if references.iter().all(|word| is_reference(word)) => {
+            Ok(Command::Each(bulk, references.to_vec()))
+        }
+        words if Query::has_syntax(words) => Ok(Command::Bulk(
+            bulk,
+            TaskFilter {
+                query: parse_query(words, scale)?,
+                hidden: false,
+            },
+            confirmed,
+        )),
+        words => Err(format!(
+            "{} takes task ids or a query such as +tag, project:<name> or \"a phrase\", \
+             not '{}'",
+            name,
+            words.join(" ")
+        )),
+    }
+}
+
+/// A task id, or a uuid prefix: four or more hex digits, at least one of
+/// them a number, so that words such as `cafe` are not taken for one.
+fn is_reference(word: &str) -> bool {
+    let digits = word.chars().filter(char::is_ascii_digit).count();
+    digits == word.len() && digits > 0
+        || word.len() >= 4 && digits > 0 && word.chars().all(|c| c.is_ascii_hexdigit() || c == '-')
+}
+
+/// The change `bulk` makes to one task, or `None` when it has nothing to do.
+fn bulk_change(
+    bulk: Bulk,
+    options: &Options,
+) -> impl FnMut(&mut TaskList, &str) -> Result<Option<Op>> {
+    let rules = CompletionRules {
+        complete_parents: options.complete_parents,
+        ignore_blockers: matches!(bulk, Bulk::Done { force: true }),
+    };
+    move |list, reference| {
+        let status = list.get(list.resolve(reference)?).unwrap().status;
+        let wanted = match bulk {
+            Bulk::Remove => return ops::remove_task(list, reference).map(Some),
+            Bulk::Stop if status != Status::InProgress => return Ok(None),
+            Bulk::Undone if !status.is_closed() => return Ok(None),
+            Bulk::Stop | Bulk::Undone => Status::Pending,
+            Bulk::Done { .. } => Status::Done,
+            Bulk::SetStatus(wanted) => wanted,
+        };
+        if status == wanted {
+            return Ok(None);
+        }
+        ops::set_status(list, reference, wanted, rules).map(Some)
+    }
+}
+
+/// Asks on the terminal whether to go ahead; with no terminal to ask on
+/// (or under test), the answer is no.
+fn confirm(question: &str) -> bool {
+    if cfg!(test) || !io::stdin().is_terminal() {
+        return false;
+    }
+    let answer = StdinUi.ask(&format!("{} [y/N] ", question));
+    matches!(answer.to_lowercase().as_str(), "y" | "yes")
+}
+
+fn parse_date(value: &str) -> std::result::Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid date '{}' (expected YYYY-MM-DD).", value))
+}
+
+/// The last moment of `date` in local time.
+fn end_of_day(date: NaiveDate) -> DateTime<Local> {
+    let midnight = date.succ_opt().unwrap_or(date).and_time(NaiveTime::MIN);
+    let next_day = midnight
+        .and_local_timezone(Local)
+        .earliest()
+        .unwrap_or_else(|| midnight.and_utc().with_timezone(&Local));
+    next_day - chrono::Duration::nanoseconds(1)
+}
+
+/// Entry point for the `task` binary; returns the process exit code.
+///
+/// With no command word the interactive menu starts.
+pub fn run(args: &[String]) -> i32 {
+    let (options, rest) = match parse_options(args) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!("{}", USAGE);
+            return EXIT_USAGE;
+        }
+    };
+    if rest.is_empty() {
+        return run_menu(&options, &mut StdinUi);
+    }
+    run_command(&options, rest)
+}
+
+pub fn run_with_file(filename: &str, args: &[String]) -> i32 {
+    let options = Options {
+        file: filename.to_string(),
+        ..Options::default()
+    };
+    run_command(&options, args)
+}
+
+fn run_command(options: &Options, args: &[String]) -> i32 {
+    // a config that does not load is reported once the command runs
+    let scale =
+        Config::load(&options.config).map_or_else(|_| PriorityScale::default(), |c| c.priority);
+    let command = match parse_args_on(args, &scale) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("{}", message);
+            eprintln!("{}", USAGE);
+            return EXIT_USAGE;
+        }
+    };
+    match execute(options, command) {
+        Ok(()) => 0,
+        Err(error) => {
+            eprintln!("Error: {}", error);
+            EXIT_FAILURE
+        }
+    }
+}
+
+/// Loads the task file and opens the menu. A strict load that hits malformed
+/// lines offers to fall back to a lenient one instead of giving up.
+pub fn run_menu(options: &Options, ui: &mut dyn Ui) -> i32 {
+    let mut load_mode = options.load_mode;
+    let mut loaded = load(&options.file, load_mode);
+    if let Err(error @ TaskError::Parse { .. }) = &loaded {
+        ui.print(&format!("Failed to load {}: {}", options.file, error));
+        let question = format!(
+            "Load the remaining tasks and move the malformed lines to {}? (y/n): ",
+            storage::rejected_path(&options.file)
+        );
+        if !ui.confirm(&question) {
+            return EXIT_FAILURE;
+        }
+        load_mode = LoadMode::Lenient;
+        loaded = load(&options.file, load_mode);
+    }
+    let report = match loaded {
+        Ok(report) => report,
+        Err(error) => {
+            ui.print(&format!("Failed to load {}: {}", options.file, error));
+            return EXIT_FAILURE;
+        }
+    };
+    let history = History::load(&options.file, DEFAULT_HISTORY_LIMIT).unwrap_or_else(|error| {
+        ui.print(&format!("Starting with an empty undo history: {}", error));
+        History::default()
+    });
+    let config = Config::load(&options.config).unwrap_or_else(|error| {
+        ui.print(&format!("Using the built-in reports only: {}", error));
+        Config::default()
+    });
+    let mut menu = Menu::new(ui, report.tasks, &options.file, &options.json_file);
+    menu.set_history(history);
+    menu.set_loaded(report.stamp);
+    menu.set_load_mode(load_mode);
+    menu.set_backups(options.backups);
+    menu.set_autosave(options.autosave);
+    menu.set_complete_parents(options.complete_parents);
+    menu.set_reports(config.reports);
+    menu.set_urgency(config.urgency);
+    menu.set_priority_scale(config.priority);
+    menu.run();
+    0
+}
+
+/// Loads `filename`, warning on stderr about any quarantined lines.
+fn load(filename: &str, mode: LoadMode) -> Result<LoadReport> {
+    let report = storage::load_tasks_with(filename, mode)?;
+    if !report.rejected.is_empty() {
+        eprintln!(
+            "Moved {} malformed line(s) to {}:",
+            report.rejected.len(),
+            storage::rejected_path(filename)
+        );
+        for rejected in &report.rejected {
+            eprintln!("  {}", rejected);
+        }
+    }
+    Ok(report)
+}
+
+/// Saves `task_list`, which `op` derived from `loaded`, and runs `history`
+/// to update the undo history under the same lock. If another process
+/// saved in the meantime its changes are merged in instead of overwritten.
+/// Returns the list as written.
+fn save(
+    options: &Options,
+    loaded: &LoadReport,
+    task_list: TaskList,
+    op: Op,
+    history: &mut dyn FnMut() -> Result<()>,
+) -> Result<TaskList> {
+    let filename = options.file.as_str();
+    let stamp = Some(&loaded.stamp);
+    let changes = Some(std::slice::from_ref(&op));
+    let backups = options.backups;
+    match storage::save_tasks_then(filename, &task_list, backups, stamp, changes, history) {
+        Ok(_) => Ok(task_list),
+        Err(TaskError::ChangedOnDisk(_)) => {
+            let theirs = load(filename, options.load_mode)?;
+            let merged = merge(&loaded.tasks, &task_list, &theirs.tasks);
+            eprintln!(
+                "{} was changed by another process; merged ({} conflicting task(s)).",
+                filename, merged.conflicts
+            );
+            let stamp = Some(&theirs.stamp);
+            storage::save_tasks_then(filename, &merged.tasks, backups, stamp, None, history)?;
+            Ok(merged.tasks)
+        }
+        Err(error) => Err(error),
+    }
+}
+
+fn execute(options: &Options, command: Command) -> Result<()> {
+    let filename = options.file.as_str();
+    if command == Command::Check {
+        let rejected = storage::check_tasks(filename)?;
+        if rejected.is_empty() {
+            println!("{}: no problems found.", filename);
+            return Ok(());
+        }
+        for line in &rejected {
+            println!("{}", line);
+        }
+        return Err(TaskError::Validation(format!(
+            "{} malformed line(s) in {}.",
+            rejected.len(),
+            filename
+        )));
+    }
+    if let Command::RestoreBackup(number) = command {
+        return restore_backup(options, number);
+    }
+    if command == Command::History {
+        let history = History::load(filename, DEFAULT_HISTORY_LIMIT)?;
+        let listing = history.listing();
+        if listing.is_empty() {
+            println!("No history yet.");
+        }
+        for line in listing {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+    if command == Command::Log {
+        let events = journal::read_events(filename)?;
+        if events.is_empty() {
+            println!("No changes journaled yet.");
+        }
+        for event in events {
+            println!("{}", event);
+        }
+        return Ok(());
+    }
+    if let Command::CompactJournal(until) = command {
+        let folded = journal::compact(filename, until.map(end_of_day))?;
+        println!("Folded {} journal event(s) into a snapshot.", folded);
+        return Ok(());
+    }
+    let config = Config::load(&options.config)?;
+    if let Command::Report(name, query) = command {
+        return run_report(options, &config, name.as_deref(), &query);
+    }
+    if let Command::AsOf(date) = command {
+        let events = journal::read_events(filename)?;
+        if events.is_empty() {
+            return Err(TaskError::NotFound(format!(
+                "{} has no journal yet.",
+                filename
+            )));
+        }
+        let task_list = journal::replay(&events, Some(end_of_day(date)))?;
+        return show(
+            &task_list,
+            Command::List(ListFilter::All, TaskFilter::default(), SortSpec::default()),
+            &config.urgency,
+        );
+    }
+    let loaded = load(filename, options.load_mode)?;
+    let mut task_list = loaded.tasks.clone();
+    let bulk = matches!(command, Command::Bulk(..) | Command::Each(..));
+    let op = match command {
+        Command::Add(new) => ops::add_task(&mut task_list, new)?,
+        Command::Edit(reference, description) => {
+            ops::edit_task(&mut task_list, &reference, &description)?
+        }
+        Command::Done(ref reference, force) => {
+            let rules = CompletionRules {
+                complete_parents: options.complete_parents,
+                ignore_blockers: force,
+            };
+            ops::set_completed(&mut task_list, reference, true, rules)?
+        }
+        Command::Undone(ref reference) => {
+            let rules = CompletionRules {
+                complete_parents: options.complete_parents,
+                ignore_blockers: false,
+            };
+            ops::set_completed(&mut task_list, reference, false, rules)?
+        }
+        Command::SetStatus(ref reference, status) => {
+            let rules = CompletionRules {
+                complete_parents: options.complete_parents,
+                ignore_blockers: false,
+            };
+            ops::set_status(&mut task_list, reference, status, rules)?
+        }
+        Command::Stop(reference) => ops::stop_task(&mut task_list, &reference)?,
+        Command::Bulk(bulk, ref select, confirmed) => {
+            let matched = task_list
+                .iter()
+                .filter(|task| select.matches(task, &task_list))
+                .count();
+            if bulk.is_destructive()
+                && matched > 1
+                && !confirmed
+                && !confirm(&format!("Change {} matching tasks?", matched))
+            {
+                return Err(TaskError::Validation(format!(
+                    "{} tasks match; nothing changed (add --yes to go ahead).",
+                    matched
+                )));
+            }
+            ops::change_matching(&mut task_list, select, bulk_change(bulk, options))?
+        }
+        Command::Each(bulk, ref references) => {
+            ops::change_each(&mut task_list, references, bulk_change(bulk, options))?
+        }
+        Command::Remove(reference) => ops::remove_task(&mut task_list, &reference)?,
+        Command::Move(reference, parent) => {
+            ops::move_task(&mut task_list, &reference, parent.as_deref())?
+        }
+        Command::Depend(reference, prerequisite) => {
+            ops::add_dependency(&mut task_list, &reference, &prerequisite)?
+        }
+        Command::Undepend(reference, prerequisite) => {
+            ops::remove_dependency(&mut task_list, &reference, &prerequisite)?
+        }
+        Command::SetDate(reference, field, date) => {
+            ops::set_date(&mut task_list, &reference, field, date)?
+        }
+        Command::Recur(reference, recur) => ops::set_recurrence(&mut task_list, &reference, recur)?,
+        Command::Sort(ref spec) => ops::sort_tasks(&mut task_list, spec, &config.urgency),
+        Command::Import(file, scale) => {
+            let scale = scale.unwrap_or(config.priority);
+            let imported = storage::import_file(&file, &scale)?;
+            if imported.merged > 0 {
+                let shared: Vec<String> = scale
+                    .shared()
+                    .iter()
+                    .map(|&(number, like)| {
+                        let level = scale.level(number).unwrap_or_default();
+                        format!("{} is {} like {}", number, level, like)
+                    })
+                    .collect();
+                eprintln!(
+                    "Warning: {} imported task(s) have a priority number that shares its \
+                     level with another ({}); the difference is lost.",
+                    imported.merged,
+                    shared.join(", ")
+                );
+            }
+            let source = format!("import from {}", file);
+            ops::append_tasks(&mut task_list, imported.tasks, &source)?
+        }
+        Command::Undo | Command::Redo => {
+            return step_history(options, &loaded, command == Command::Undo);
+        }
+        command => return show(&task_list, command, &config.urgency),
+    };
+    let mut record = || {
+        let recorded = History::load(filename, DEFAULT_HISTORY_LIMIT).and_then(|mut history| {
+            history.record(op.clone());
+            history.save(filename)
+        });
+        if let Err(error) = recorded {
+            eprintln!(
+                "Warning: the change was saved but not added to the history: {}",
+                error
+            );
+        }
+        Ok(())
+    };
+    let saved = save(options, &loaded, task_list, op.clone(), &mut record)?;
+    // a bulk change counts every task it touched itself
+    let related = match &op {
+        Op::Batch { ops } if !bulk => ops.len() - 1,
+        _ => 0,
+    };
+    let mut message = match op.primary() {
+        _ if bulk => {
+            let changed = match &op {
+                Op::Batch { ops } => ops.len(),
+                _ => 1,
+            };
+            format!("{} task(s) changed.", changed)
+        }
+        Op::Add { task, .. } => {
+            // a merge may have renumbered the new task
+            let id = saved
+                .iter()
+                .find(|t| t.uuid == task.uuid)
+                .map_or(task.id, |t| t.id);
+            match task.due() {
+                Some(due) => format!("Task {} added, due {}.", id, due),
+                None => format!("Task {} added.", id),
+            }
+        }
+        Op::Edit { before, after } if before.status != after.status => {
+            ops::status_message(after.status).to_string()
+        }
+        Op::Edit { before, after } if before.parent != after.parent => "Task moved.".to_string(),
+        Op::Edit { before, after } if before.due() != after.due() => match after.due() {
+            Some(due) => format!("Task due {}.", due),
+            None => "Task no longer has a due date.".to_string(),
+        },
+        Op::Edit { before, after } if before.recur.is_some() && after.recur.is_none() => {
+            "Task no longer repeats.".to_string()
+        }
+        Op::Edit { before, after } if before.recur != after.recur => format!(
+            "Task repeats {}, next due {}.",
+            after.recur.as_ref().unwrap(),
+            after.due_date.unwrap()
+        ),
+        Op::Edit { before, after } if before.depends.len() < after.depends.len() => {
+            "Dependency added.".to_string()
+        }
+        Op::Edit { before, after } if before.depends.len() > after.depends.len() => {
+            "Dependency removed.".to_string()
+        }
+        Op::Edit { .. } => "Task updated.".to_string(),
+        Op::Sort { before, after, .. } if before == after => {
+            "The tasks were already in that order.".to_string()
+        }
+        Op::Sort { key, .. } => format!("Tasks sorted by {}.", key),
+        Op::Replace { before, after, .. } => format!(
+            "Imported {} task(s).",
+            after.tasks.len() - before.tasks.len()
+        ),
+        _ => "Task removed.".to_string(),
+    };
+    let spawned = ops::spawned_occurrence(&op);
+    let related = related - usize::from(spawned.is_some());
+    if related > 0 {
+        message.push_str(&format!(" {} related task(s) changed too.", related));
+    }
+    if let Some(next) = spawned {
+        let id = saved
+            .iter()
+            .find(|t| t.uuid == next.uuid)
+            .map_or(next.id, |t| t.id);
+        message.push_str(&format!(
+            " Next occurrence: task {}, due {}.",
+            id,
+            next.due_date.unwrap()
+        ));
+    }
+    println!("{}", message);
+    Ok(())
+}
+
+/// Undoes or redoes one step of the stored history and saves the result.
+fn step_history(options: &Options, loaded: &LoadReport, undo: bool) -> Result<()> {
+    let filename = options.file.as_str();
+    let mut history = History::load(filename, DEFAULT_HISTORY_LIMIT)?;
+    let mut task_list = loaded.tasks.clone();
+    let step = if undo {
+        history.undo(&mut task_list)?
+    } else {
+        history.redo(&mut task_list)?
+    };
+    let Some(step) = step else {
+        println!("Nothing to {}.", if undo { "undo" } else { "redo" });
+        return Ok(());
+    };
+    let description = step.op.to_string();
+    let op = if undo {
+        step.op.inverse()
+    } else {
+        step.op.clone()
+    };
+    // the tasks are written by now; failing would report an undo that
+    // happened, inviting a retry that applies it twice
+    save(options, loaded, task_list, op, &mut || {
+        if let Err(error) = history.save(filename) {
+            eprintln!(
+                "Warning: the tasks were saved but the history still lists the step as {}: {}",
+                if undo { "not undone" } else { "undone" },
+                error
+            );
+        }
+        Ok(())
+    })?;
+    println!("{}: {}", if undo { "Undid" } else { "Redid" }, description);
+    Ok(())
+}
+
+/// Runs the commands that only read the list.
+fn show(task_list: &TaskList, command: Command, urgency: &Urgency) -> Result<()> {
+    match command {
+        Command::List(ListFilter::All, select, sort)
+            if select.query == Query::All && sort.is_empty() =>
+        {
+            let lines = ops::tree_lines(task_list, select.hidden, Some(urgency));
+            if lines.is_empty() {
+                println!("No tasks found.");
+            }
+            for line in lines {
+                println!("{}", line);
+            }
+            let hidden = ops::hidden_count(task_list);
+            if !select.hidden && hidden > 0 {
+                println!(
+                    "{} waiting or expired task(s) not shown; `list --all` shows them.",
+                    hidden
+                );
+            }
+        }
+        Command::List(filter, select, sort) => {
+            let mut shown: Vec<&Task> = match filter {
+                ListFilter::All => task_list.iter().collect(),
+                ListFilter::Pending => task_list.pending().collect(),
+                ListFilter::Completed => task_list.completed().collect(),
+                ListFilter::Waiting => {
+                    let today = Local::now().date_naive();
+                    task_list.iter().filter(|t| t.is_waiting(today)).collect()
+                }
+            };
+            shown.retain(|t| select.matches(t, task_list));
+            let sort = if sort.is_empty() {
+                SortKey::Urgency.into()
+            } else {
+                sort
+            };
+            sort.sort(&mut shown, task_list, urgency);
+            if shown.is_empty() {
+                println!("No tasks found.");
+            }
+            for task in shown {
+                println!("{}", task.render_in(task_list));
+            }
+        }
+        Command::Search(query) => {
+            let mut found = ops::find_tasks(task_list, &query);
+            SortSpec::from(SortKey::Urgency).sort(&mut found, task_list, urgency);
+            if found.is_empty() {
+                return Err(TaskError::NotFound("No tasks match the query.".to_string()));
+            }
+            for task in found {
+                println!("{}", task.render_in(task_list));
+            }
+        }
+        Command::Next => {
+            let next = ops::next_actionable(task_list, urgency);
+            if next.is_empty() {
+                println!("Nothing to do right now.");
+            }
+            for task in next {
+                println!("{}", task.render_in(task_list));
+            }
+        }
+        Command::Export { json: true, query } => {
+            let tasks: Vec<Task> = ops::find_tasks(task_list, &query)
+                .into_iter()
+                .cloned()
+                .collect();
+            println!("{}", storage::to_json(&tasks)?);
+        }
+        Command::Export { json: false, query } => {
+            for task in ops::find_tasks(task_list, &query) {
+                println!("{}", storage::format_line(task));
+            }
+        }
+        Command::Why(reference) => {
+            let index = task_list.resolve(&reference)?;
+            let today = Local::now().date_naive();
+            for line in urgency.explain(task_list, index, today) {
+                println!("{}", line);
+            }
+        }
+        Command::Help => println!("{}", USAGE),
+        _ => unreachable!("changes are handled by execute"),
+    }
+    Ok(())
+}
+
+/// Prints the named report, or the list of reports when there is no name.
+fn run_report(options: &Options, config: &Config, name: Option<&str>, query: &Query) -> Result<()> {
+    let Some(name) = name else {
+        for report in &config.reports {
+            println!("{:10} {}", report.name, report.description);
+        }
+        return Ok(());
+    };
+    let report = config.report(name).ok_or_else(|| {
+        TaskError::NotFound(format!("No report named '{}'; `report` lists them.", name))
+    })?;
+    let task_list = load(&options.file, options.load_mode)?.tasks;
+    let shown = report.select(&task_list, query, &config.urgency);
+    let lines = report.render(&shown, &task_list, &config.urgency);
+    if lines.is_empty() {
+        println!("No tasks found.");
+    }
+    for line in lines {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+fn restore_backup(options: &Options, number: Option<usize>) -> Result<()> {
+    let filename = options.file.as_str();
+    let Some(number) = number else {
+        let backups = storage::list_backups(filename)?;
+        if backups.is_empty() {
+            println!("No backups of {}.", filename);
+        }
+        for backup in backups {
+            let tasks = match storage::load_tasks(&backup.path) {
+                Ok(list) => format!("{} tasks", list.len()),
+                Err(error) => format!("unreadable: {}", error),
+            };
+            println!(
+                "{}  {}  {}",
+                backup.number,
+                backup.saved_at.format("%Y-%m-%d %H:%M:%S"),
+                tasks
+            );
+        }
+        return Ok(());
+    };
+    let task_list = storage::restore_backup(filename, number, options.backups)?;
+    println!(
+        "Restored {} tasks from {}.",
+        task_list.len(),
+        storage::backup_path(filename, number)
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Priority;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    fn remove_task_files(filename: &str) {
+        std::fs::remove_file(filename).unwrap();
+        let _ = std::fs::remove_file(crate::lock::lock_path(filename));
+        let _ = std::fs::remove_file(crate::history::history_path(filename));
+        let _ = std::fs::remove_file(crate::journal::journal_path(filename));
+        for backup in storage::list_backups(filename).unwrap() {
+            std::fs::remove_file(&backup.path).unwrap();
+            let _ = std::fs::remove_file(crate::lock::lock_path(&backup.path));
+        }
+    }
+
+    #[test]
+    fn test_parse_add_with_options() {
+        let command = parse_args(&args(&[
+            "add",
+            "Write docs",
+            "--priority",
+            "2",
+            "--due",
+            "2026-11-01",
+        ]));
+        let mut expected = NewTask::new("Write docs");
+        expected.priority = Priority::High;
+        expected.due_date = chrono::NaiveDate::from_ymd_opt(2026, 11, 1);
+        assert_eq!(command, Ok(Command::Add(expected)));
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_input() {
+        assert!(parse_args(&args(&["add", "x", "--priority", "9"])).is_err());
+        assert!(parse_args(&args(&["done"])).is_err());
+        assert!(parse_args(&args(&["frobnicate"])).is_err());
+        assert!(parse_args(&args(&["move", "2"])).is_err());
+        assert!(parse_args(&args(&["depend", "2"])).is_err());
+        assert!(parse_args(&args(&["recur", "2", "fortnightly"])).is_err());
+        assert_eq!(
+            parse_args(&args(&["recur", "2", "every", "3", "days"])).unwrap(),
+            Command::Recur("2".to_string(), "every 3 days".parse().ok())
+        );
+        assert_eq!(
+            parse_args(&args(&["done", "2", "--force"])).unwrap(),
+            Command::Done("2".to_string(), true)
+        );
+        assert_eq!(
+            parse_args(&args(&["move", "2", "--top"])).unwrap(),
+            Command::Move("2".to_string(), None)
+        );
+        assert!(parse_args(&args(&["status", "2", "blocked"])).is_err());
+        assert_eq!(
+            parse_args(&args(&["status", "2", "In_Progress"])).unwrap(),
+            Command::SetStatus("2".to_string(), Status::InProgress)
+        );
+        assert_eq!(
+            parse_args(&args(&["cancel", "2"])).unwrap(),
+            Command::SetStatus("2".to_string(), Status::Cancelled)
+        );
+    }
+
+    #[test]
+    fn test_parse_list_filters() {
+        let command = parse_args(&args(&[
+            "list",
+            "+infra",
+            "--pending",
+            "--sort",
+            "due-,priority",
+            "project:work",
+        ]))
+        .unwrap();
+        let select = TaskFilter {
+            query: Query::parse("+infra project:work").unwrap(),
+            hidden: false,
+        };
+        let sort = SortSpec::parse("due-,priority+").unwrap();
+        assert_eq!(command, Command::List(ListFilter::Pending, select, sort));
+        assert!(parse_args(&args(&["list", "--sort"])).is_err());
+        assert!(parse_args(&args(&["list", "--sort", "due+,size"])).is_err());
+        assert_eq!(
+            parse_args(&args(&["sort", "due+", "created"])).unwrap(),
+            Command::Sort(SortSpec::parse("due+,created+").unwrap())
+        );
+        let Command::List(ListFilter::Waiting, select, _) =
+            parse_args(&args(&["ls", "--waiting"])).unwrap()
+        else {
+            panic!("expected a waiting list");
+        };
+        assert!(select.hidden);
+        assert!(parse_args(&args(&["date", "2", "someday", "fri"])).is_err());
+        assert!(parse_args(&args(&["add", "x", "--wait", "fri 9am"])).is_err());
+        assert!(parse_args(&args(&["list", "(infra"])).is_err());
+        assert!(parse_args(&args(&["list", "--everything"])).is_err());
+        assert!(parse_args(&args(&["list", "--pending", "--completed"])).is_err());
+        assert!(parse_args(&args(&["list", "created.after:whenever"])).is_err());
+        assert_eq!(
+            parse_args(&args(&["done", "+ops", "status:pending", "--force"])).unwrap(),
+            Command::Bulk(
+                Bulk::Done { force: true },
+                TaskFilter {
+                    query: Query::parse("+ops status:pending").unwrap(),
+                    hidden: false,
+                },
+                false
+            )
+        );
+        assert_eq!(
+            parse_args(&args(&["report", "today", "+ops"])).unwrap(),
+            Command::Report(Some("today".to_string()), Query::parse("+ops").unwrap())
+        );
+        assert_eq!(
+            parse_args(&args(&["reports"])).unwrap(),
+            Command::Report(None, Query::All)
+        );
+        assert_eq!(
+            parse_args(&args(&["rm", "3fa8"])).unwrap(),
+            Command::Remove("3fa8".to_string())
+        );
+        assert_eq!(
+            parse_args(&args(&["rm", "3", "5", "--yes"])).unwrap(),
+            Command::Each(Bulk::Remove, args(&["3", "5"]))
+        );
+        assert_eq!(
+            parse_args(&args(&["done", "3", "a1b2c3d4"])).unwrap(),
+            Command::Each(Bulk::Done { force: false }, args(&["3", "a1b2c3d4"]))
+        );
+        // hex-looking words are neither uuid prefixes nor a query
+        assert!(parse_args(&args(&["rm", "cafe"])).is_err());
+        assert!(parse_args(&args(&["rm", "add"])).is_err());
+        assert!(parse_args(&args(&["rm", "3", "fix"])).is_err());
+        assert!(matches!(
+            parse_args(&args(&["rm", "\"cafe\"", "--yes"])).unwrap(),
+            Command::Bulk(Bulk::Remove, _, true)
+        ));
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let words = args(&["--lenient", "-f", "other.txt", "list"]);
+        let (options, rest) = parse_options(&words).unwrap();
+        assert_eq!(options.file, "other.txt");
+        assert_eq!(options.load_mode, LoadMode::Lenient);
+        assert_eq!(rest, &args(&["list"])[..]);
+        assert!(parse_options(&args(&["--file"])).is_err());
+        let (options, _) = parse_options(&args(&["--config", "work.taskrc"])).unwrap();
+        assert_eq!(options.config, "work.taskrc");
+        assert!(parse_options(&args(&["--config"])).is_err());
+    }
+
+    #[test]
+    fn test_corrupt_file_needs_lenient_mode() {
+        let filename = "test_cli_corrupt.txt";
+        std::fs::write(filename, "fine|0||\nbad|maybe\n").unwrap();
+        assert_eq!(run_with_file(filename, &args(&["list"])), EXIT_FAILURE);
+        assert_eq!(run_with_file(filename, &args(&["check"])), EXIT_FAILURE);
+        let words = args(&["--lenient", "--file", filename, "done", "1"]);
+        assert_eq!(run(&words), 0);
+        let saved = std::fs::read_to_string(filename).unwrap();
+        assert!(!saved.contains("bad|maybe"));
+        let rejected = storage::rejected_path(filename);
+        assert!(
+            std::fs::read_to_string(&rejected)
+                .unwrap()
+                .contains("bad|maybe")
+        );
+        remove_task_files(filename);
+        std::fs::remove_file(rejected).unwrap();
+    }
+
+    #[test]
+    fn test_run_add_done_rm() {
+        let filename = "test_cli_tasks.txt";
+        let _ = std::fs::remove_file(filename);
+        assert_eq!(run_with_file(filename, &args(&["add", "Scripted"])), 0);
+        assert_eq!(run_with_file(filename, &args(&["done", "1"])), 0);
+        assert!(
+            storage::load_tasks(filename)
+                .unwrap()
+                .get(0)
+                .unwrap()
+                .is_done()
+        );
+        assert_eq!(run_with_file(filename, &args(&["stop", "1"])), EXIT_FAILURE);
+        assert_eq!(run_with_file(filename, &args(&["start", "1"])), 0);
+        assert_eq!(run_with_file(filename, &args(&["stop", "1"])), 0);
+        let task = storage::load_tasks(filename)
+            .unwrap()
+            .get(0)
+            .unwrap()
+            .clone();
+        assert_eq!(task.status, Status::Pending);
+        assert_eq!(task.transitions.len(), 3);
+        assert_eq!(run_with_file(filename, &args(&["add", "Second"])), 0);
+        // more than one match needs --yes
+        assert_eq!(
+            run_with_file(filename, &args(&["cancel", "status:open"])),
+            EXIT_FAILURE
+        );
+        assert!(
+            storage::load_tasks(filename)
+                .unwrap()
+                .tasks()
+                .iter()
+                .all(|task| task.status != Status::Cancelled)
+        );
+        assert_eq!(
+            run_with_file(filename, &args(&["cancel", "status:open", "--yes"])),
+            0
+        );
+        assert_eq!(
+            run_with_file(filename, &args(&["cancel", "status:open", "--yes"])),
+            EXIT_FAILURE
+        );
+        assert!(
+            storage::load_tasks(filename)
+                .unwrap()
+                .tasks()
+                .iter()
+                .all(|task| task.status == Status::Cancelled)
+        );
+        assert_eq!(run_with_file(filename, &args(&["rm", "3"])), EXIT_FAILURE);
+        assert_eq!(
+            run_with_file(filename, &args(&["rm", "status:cancelled", "--yes"])),
+            0
+        );
+        assert!(storage::load_tasks(filename).unwrap().is_empty());
+        assert_eq!(
+            run_with_file(filename, &args(&["list", "--bogus"])),
+            EXIT_USAGE
+        );
+        assert_eq!(run_with_file(filename, &args(&["check"])), 0);
+        remove_task_files(filename);
+    }
+
+    #[test]
+    fn test_run_sort() {
+        let filename = "test_cli_sort.txt";
+        let _ = std::fs::remove_file(filename);
+        for (description, priority) in [("Low", "4"), ("None", ""), ("High", "1")] {
+            let mut words = vec!["add", description];
+            if !priority.is_empty() {
+                words.extend(["--priority", priority]);
+            }
+            assert_eq!(run_with_file(filename, &args(&words)), 0);
+        }
+        let order = || -> Vec<String> {
+            let list = storage::load_tasks(filename).unwrap();
+            list.iter().map(|t| t.description.clone()).collect()
+        };
+        let words = args(&["list", "--sort", "priority-"]);
+        assert_eq!(run_with_file(filename, &words), 0);
+        assert_eq!(order(), ["Low", "None", "High"]);
+        let words = args(&["sort", "priority+:none-first"]);
+        assert_eq!(run_with_file(filename, &words), 0);
+        assert_eq!(order(), ["None", "High", "Low"]);
+        assert_eq!(run_with_file(filename, &args(&["undo"])), 0);
+        assert_eq!(order(), ["Low", "None", "High"]);
+        assert_eq!(
+            run_with_file(filename, &args(&["sort", "size"])),
+            EXIT_USAGE
+        );
+        assert_eq!(run_with_file(filename, &args(&["sort", "urgency"])), 0);
+        assert_eq!(order(), ["High", "Low", "None"]);
+        assert_eq!(run_with_file(filename, &args(&["why", "3"])), 0);
+        assert_eq!(run_with_file(filename, &args(&["why", "9"])), EXIT_FAILURE);
+        assert_eq!(run_with_file(filename, &args(&["why"])), EXIT_USAGE);
+        remove_task_files(filename);
+    }
+
+    #[test]
+    fn test_run_bulk_query() {
+        let filename = "test_cli_bulk_query.txt";
+        let _ = std::fs::remove_file(filename);
+        for words in [
+            &["add", "Rotate keys +ops"][..],
+            &["add", "Patch kernel +ops project:infra"],
+            &["add", "Write docs project:infra"],
+            &["add", "Call Bob"],
+        ] {
+            assert_eq!(run_with_file(filename, &args(words)), 0);
+        }
+        let statuses = || -> Vec<Status> {
+            let list = storage::load_tasks(filename).unwrap();
+            list.iter().map(|t| t.status).collect()
+        };
+        use Status::{Done, InProgress, Pending};
+        // starting is not destructive, so two matches need no --yes
+        assert_eq!(run_with_file(filename, &args(&["start", "+ops"])), 0);
+        assert_eq!(statuses(), [InProgress, InProgress, Pending, Pending]);
+        assert_eq!(
+            run_with_file(filename, &args(&["done", "project:infra", "or", "bob"])),
+            EXIT_FAILURE
+        );
+        assert_eq!(statuses(), [InProgress, InProgress, Pending, Pending]);
+        let words = args(&["done", "project:infra", "or", "\"call bob\"", "--yes"]);
+        assert_eq!(run_with_file(filename, &words), 0);
+        assert_eq!(statuses(), [InProgress, Done, Done, Done]);
+        // a single match goes ahead without asking
+        assert_eq!(
+            run_with_file(filename, &args(&["rm", "-ops", "\"docs\""])),
+            0
+        );
+        assert_eq!(statuses(), [InProgress, Done, Done]);
+        remove_task_files(filename);
+    }
+
+    #[test]
+    fn test_run_id_lists() {
+        let filename = "test_cli_id_lists.txt";
+        let _ = std::fs::remove_file(filename);
+        for description in ["One", "Two", "Three"] {
+            assert_eq!(run_with_file(filename, &args(&["add", description])), 0);
+        }
+        let left = || -> Vec<String> {
+            let list = storage::load_tasks(filename).unwrap();
+            list.iter().map(|t| t.description.clone()).collect()
+        };
+        // an unknown id leaves every task alone
+        assert_eq!(
+            run_with_file(filename, &args(&["rm", "1", "7"])),
+            EXIT_FAILURE
+        );
+        assert_eq!(left(), ["One", "Two", "Three"]);
+        assert_eq!(run_with_file(filename, &args(&["rm", "3", "1"])), 0);
+        assert_eq!(left(), ["Two"]);
+        assert_eq!(run_with_file(filename, &args(&["undo"])), 0);
+        assert_eq!(left(), ["One", "Two", "Three"]);
+        assert_eq!(run_with_file(filename, &args(&["rm", "Two"])), EXIT_USAGE);
+        assert_eq!(left().len(), 3);
+        remove_task_files(filename);
+    }
+
+    #[test]
+    fn test_run_reports() {
+        let filename = "test_cli_reports.txt";
+        let config = "test_cli_reports.taskrc";
+        let _ = std::fs::remove_file(filename);
+        std::fs::write(
+            config,
+            "report.mine.filter = +home\nreport.mine.limit = 1\n",
+        )
+        .unwrap();
+        let options = Options {
+            file: filename.to_string(),
+            config: config.to_string(),
+            ..Options::default()
+        };
+        assert_eq!(run_with_file(filename, &args(&["add", "Sweep +home"])), 0);
+        assert_eq!(run_command(&options, &args(&["report"])), 0);
+        assert_eq!(run_command(&options, &args(&["report", "mine"])), 0);
+        assert_eq!(run_command(&options, &args(&["report", "today", "+x"])), 0);
+        assert_eq!(
+            run_command(&options, &args(&["report", "theirs"])),
+            EXIT_FAILURE
+        );
+        std::fs::write(config, "report.mine.limit = many\n").unwrap();
+        assert_eq!(
+            run_command(&options, &args(&["report", "mine"])),
+            EXIT_FAILURE
+        );
+        std::fs::remove_file(config).unwrap();
+        remove_task_files(filename);
+    }
+
+    #[test]
+    fn test_priority_scales_and_import() {
+        let filename = "test_cli_priority.txt";
+        let config = "test_cli_priority.taskrc";
+        let legacy = "test_cli_priority_legacy.txt";
+        let _ = std::fs::remove_file(filename);
+        std::fs::write(config, "priority.scale = 0-10\n").unwrap();
+        std::fs::write(legacy, "Call Bob|0|2\nShip it|1|9|2024-01-05\n").unwrap();
+        let options = Options {
+            file: filename.to_string(),
+            config: config.to_string(),
+            ..Options::default()
+        };
+        let add = |priority: &str| args(&["add", "x", "--priority", priority]);
+        assert_eq!(run_command(&options, &add("9")), 0);
+        assert_eq!(run_command(&options, &add("Medium")), 0);
+        assert_eq!(run_command(&options, &add("11")), EXIT_USAGE);
+        assert_eq!(run_with_file(filename, &add("9")), EXIT_USAGE);
+        let levels = || -> Vec<Priority> {
+            let list = storage::load_tasks(filename).unwrap();
+            list.iter().map(|t| t.priority).collect()
+        };
+        assert_eq!(levels(), [Priority::Critical, Priority::Medium]);
+
+        assert_eq!(
+            parse_args(&args(&["import", "--scale", "1-5", legacy])).unwrap(),
+            Command::Import(legacy.to_string(), Some(PriorityScale::default()))
+        );
+        assert!(parse_args(&args(&["import", legacy, "--scale", "1-10"])).is_err());
+        assert_eq!(run_command(&options, &args(&["import", legacy])), 0);
+        assert_eq!(
+            levels(),
+            [
+                Priority::Critical,
+                Priority::Medium,
+                Priority::Low,
+                Priority::Critical
+            ]
+        );
+        let words = args(&["import", legacy, "--scale", "1-5"]);
+        assert_eq!(run_command(&options, &words), EXIT_FAILURE);
+        assert_eq!(run_with_file(filename, &args(&["undo"])), 0);
+        assert_eq!(levels().len(), 2);
+        std::fs::remove_file(config).unwrap();
+        std::fs::remove_file(legacy).unwrap();
+        remove_task_files(filename);
+    }
+
+    #[test]
+    fn test_journal_commands() {
+        let filename = "test_cli_journal.txt";
+        let _ = std::fs::remove_file(filename);
+        assert_eq!(run_with_file(filename, &args(&["add", "Journaled"])), 0);
+        assert_eq!(run_with_file(filename, &args(&["done", "1"])), 0);
+        assert_eq!(run_with_file(filename, &args(&["undo"])), 0);
+        let events = journal::read_events(filename).unwrap();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[2].change.to_string(), "mark task 1 pending");
+        let today = Local::now().format("%Y-%m-%d").to_string();
+        assert_eq!(run_with_file(filename, &args(&["as-of", &today])), 0);
+        assert_eq!(
+            run_with_file(filename, &args(&["as-of", "soon"])),
+            EXIT_USAGE
+        );
+        assert_eq!(run_with_file(filename, &args(&["compact-journal"])), 0);
+        let events = journal::read_events(filename).unwrap();
+        assert_eq!(
+            journal::replay(&events, None).unwrap(),
+            storage::load_tasks(filename).unwrap()
+        );
+        remove_task_files(filename);
+    }
+
+    #[test]
+    fn test_undo_redo_across_runs() {
+        let filename = "test_cli_history.txt";
+        let _ = std::fs::remove_file(filename);
+        assert_eq!(run_with_file(filename, &args(&["add", "First"])), 0);
+        assert_eq!(run_with_file(filename, &args(&["add", "Second"])), 0);
+        assert_eq!(run_with_file(filename, &args(&["undo"])), 0);
+        assert_eq!(storage::load_tasks(filename).unwrap().len(), 1);
+        assert_eq!(run_with_file(filename, &args(&["redo"])), 0);
+        assert_eq!(storage::load_tasks(filename).unwrap().len(), 2);
+        assert_eq!(run_with_file(filename, &args(&["history"])), 0);
+        let history = History::load(filename, DEFAULT_HISTORY_LIMIT).unwrap();
+        assert_eq!(history.listing().len(), 2);
+        assert_eq!(run_with_file(filename, &args(&["redo"])), 0);
+        remove_task_files(filename);
+    }
+}
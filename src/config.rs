@@ -0,0 +1,264 @@
+//! Settings read from a `key = value` file (`.taskrc`, or `$TASKRC`):
+//!
+//! ```text
+//! # comments start with #
+//! report.mine.filter = status:open project:home
+//! report.mine.sort = due+, priority-
+//! report.mine.columns = id, due, description
+//! report.mine.limit = 20
+//! urgency.due = 15
+//! urgency.tag.urgent = 5
+//! priority.scale = 1-5
+//! priority.5 = none
+//! ```
+
+use crate::priority::{Priority, PriorityScale};
+use crate::query::Query;
+use crate::report::{self, Column, Report};
+use crate::sort::SortSpec;
+use crate::urgency::Urgency;
+use crate::{Result, TaskError};
+
+/// Read when neither `--config` nor `$TASKRC` names another file.
+pub const DEFAULT_CONFIG: &str = ".taskrc";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    /// The built-in reports, changed or added to by the file.
+    pub reports: Vec<Report>,
+    pub urgency: Urgency,
+    /// How numbers typed as priorities map onto levels.
+    pub priority: PriorityScale,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            reports: report::builtin(),
+            urgency: Urgency::default(),
+            priority: PriorityScale::default(),
+        }
+    }
+}
+
+impl Config {
+    /// Reads `path`; a missing file leaves every setting at its default.
+    pub fn load(path: &str) -> Result<Config> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => Config::parse(&text)
+                .map_err(|error| TaskError::Validation(format!("{}: {}", path, error))),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(error) => Err(TaskError::io(path, error)),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Config> {
+        let mut config = Config::default();
+        let mut lines: Vec<(usize, &str)> = text
+            .lines()
+            .map(str::trim)
+            .enumerate()
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .collect();
+        // the priority scale first, so that report filters read numbers on it
+        lines.sort_by_key(|(_, line)| !line.starts_with("priority."));
+        for (number, line) in lines {
+            config.set(line).map_err(|message| TaskError::Parse {
+                line: number + 1,
+                message,
+            })?;
+        }
+        Ok(config)
+    }
+
+    pub fn report(&self, name: &str) -> Option<&Report> {
+        self.reports
+            .iter()
+            .find(|report| report.name.eq_ignore_ascii_case(name))
+    }
+
+    fn set(&mut self, line: &str) -> std::result::Result<(), String> {
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| format!("expected 'key = value', not '{}'", line))?;
+        let (key, value) = (key.trim(), value.trim());
+        if let Some(factor) = key.strip_prefix("urgency.") {
+            let coefficient = self
+                .urgency
+                .coefficient_mut(factor)
+                .ok_or_else(|| format!("unknown urgency factor '{}'", factor))?;
+            *coefficient = value
+                .parse::<f64>()
+                .ok()
+                .filter(|number| number.is_finite())
+                .ok_or_else(|| format!("{} must be a number, not '{}'", key, value))?;
+            return Ok(());
+        }
+        if key == "priority.scale" {
+            self.priority = PriorityScale::preset(value).ok_or_else(|| {
+                format!(
+                    "unknown priority scale '{}' (expected {})",
+                    value,
+                    PriorityScale::PRESETS.join(" or ")
+                )
+            })?;
+            return Ok(());
+        }
+        if let Some(number) = key.strip_prefix("priority.") {
+            let number = number
+                .parse()
+                .map_err(|_| format!("unknown setting '{}'", key))?;
+            let level = Priority::from_name(value)
+                .ok_or_else(|| format!("unknown priority level '{}'", value))?;
+            self.priority.set(number, level);
+            return Ok(());
+        }
+        let Some(("report", rest)) = key.split_once('.') else {
+            return Err(format!("unknown setting '{}'", key));
+        };
+        let Some((name, field)) = rest.rsplit_once('.') else {
+            return Err(format!("'{}' needs a report name and a field", key));
+        };
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!("invalid report name '{}'", name));
+        }
+        let report = match self
+            .reports
+            .iter()
+            .position(|r| r.name.eq_ignore_ascii_case(name))
+        {
+            Some(index) => &mut self.reports[index],
+            None => {
+                self.reports.push(Report::new(name));
+                self.reports.last_mut().unwrap()
+            }
+        };
+        match field {
+            "description" => report.description = value.to_string(),
+            "filter" => {
+                report.filter.query = Query::parse_on(value, &self.priority)
+                    .map_err(|e| format!("invalid filter: {}", e))?;
+            }
+            "all" => {
+                report.filter.hidden = match value.to_lowercase().as_str() {
+                    "1" | "true" | "yes" | "on" => true,
+                    "0" | "false" | "no" | "off" => false,
+                    _ => return Err(format!("{} must be yes or no, not '{}'", key, value)),
+                }
+            }
+            "sort" => report.sort = SortSpec::parse(value)?,
+            "columns" => {
+                report.columns = parse_list(value, Column::parse, "column")?;
+                if report.columns.is_empty() {
+                    return Err(format!("{} needs at least one column", key));
+                }
+            }
+            "limit" => {
+                report.limit = match value {
+                    "" | "none" => None,
+                    _ => match value.parse() {
+                        Ok(limit) if limit > 0 => Some(limit),
+                        _ => return Err(format!("invalid limit '{}'", value)),
+                    },
+                }
+            }
+            _ => return Err(format!("unknown report field '{}'", field)),
+        }
+        Ok(())
+    }
+}
+
+/// Splits a comma-separated list, naming the first entry `parse` rejects.
+fn parse_list<T>(
+    value: &str,
+    parse: impl Fn(&str) -> Option<T>,
+    what: &str,
+) -> std::result::Result<Vec<T>, String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(|item| parse(item).ok_or_else(|| format!("unknown {} '{}'", what, item)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config() {
+        let config = Config::parse(
+            "# my reports\n\
+             report.mine.description = home chores\n\
+             report.mine.filter = status:open project:home\n\
+             report.mine.sort = due+, priority-\n\
+             report.mine.columns = id,due,description\n\
+             report.mine.limit = 5\n\
+             \n\
+             report.overdue.limit = 3\n\
+             report.hot.filter = priority>=7\n\
+             urgency.blocking = 2.5\n\
+             urgency.tag.Urgent = -1\n\
+             priority.scale = 0-10\n\
+             priority.11 = critical\n",
+        )
+        .unwrap();
+        let mine = config.report("Mine").unwrap();
+        assert_eq!(mine.description, "home chores");
+        assert_eq!(
+            mine.filter.query,
+            Query::parse("status:open project:home").unwrap()
+        );
+        assert_eq!(mine.sort.to_string(), "due+,priority-");
+        assert_eq!(mine.columns, [Column::Id, Column::Due, Column::Description]);
+        assert_eq!(mine.limit, Some(5));
+        let overdue = config.report("overdue").unwrap();
+        assert_eq!(overdue.limit, Some(3));
+        assert_eq!(overdue.sort.to_string(), "due+,priority+");
+        // read on the 0-10 scale set further down
+        assert_eq!(
+            config.report("hot").unwrap().filter.query,
+            Query::parse("priority>=high").unwrap()
+        );
+        assert_eq!(config.reports.len(), report::builtin().len() + 2);
+        assert_eq!(config.urgency.blocking, 2.5);
+        assert_eq!(config.urgency.tag["urgent"], -1.0);
+        assert_eq!(config.urgency.due, Urgency::default().due);
+        assert_eq!(config.priority.parse("9"), Ok(Priority::Critical));
+        assert_eq!(config.priority.range(), "0-11");
+
+        for (text, message) in [
+            ("colour = on", "line 1: unknown setting 'colour'"),
+            (
+                "report.x.sort = due, size",
+                "line 1: unknown sort key 'size'",
+            ),
+            ("\nreport.x.limit = 0", "line 2: invalid limit '0'"),
+            ("report.x.filter = (", "line 1: invalid filter: missing ')'"),
+            ("report.x", "line 1: expected 'key = value', not 'report.x'"),
+            ("report.x.width = 3", "line 1: unknown report field 'width'"),
+            ("urgency.size = 3", "line 1: unknown urgency factor 'size'"),
+            (
+                "priority.scale = 1-3",
+                "line 1: unknown priority scale '1-3' (expected 1-5 or 0-10)",
+            ),
+            (
+                "priority.6 = urgent",
+                "line 1: unknown priority level 'urgent'",
+            ),
+            (
+                "urgency.due = lots",
+                "line 1: urgency.due must be a number, not 'lots'",
+            ),
+        ] {
+            let error = Config::parse(text).unwrap_err().to_string();
+            assert!(error.starts_with(message), "{}: {}", text, error);
+        }
+        assert_eq!(Config::load("no_such_taskrc").unwrap(), Config::default());
+    }
+}
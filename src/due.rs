@@ -0,0 +1,348 @@
+//! Dates typed the way people say them: `today`, `fri`, `next monday`,
+//! `in 3 days`, `eom`, `2w`, optionally followed by a time of day and a UTC
+//! offset, and the relative wording (`in 3h`, `overdue by 2d`) they are
+//! shown with.
+
+use crate::recur::weekday;
+use chrono::{
+    DateTime, Datelike, Days, FixedOffset, Local, Months, NaiveDate, NaiveDateTime, NaiveTime,
+    Offset, TimeDelta, TimeZone,
+};
+use std::fmt;
+
+/// A date with an optional time of day, which is local time unless an
+/// offset was given.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Due {
+    pub date: NaiveDate,
+    pub time: Option<NaiveTime>,
+    pub offset: Option<FixedOffset>,
+}
+
+impl Due {
+    /// The exact moment, if a time was given.
+    pub fn at(&self) -> Option<DateTime<FixedOffset>> {
+        let time = self.date.and_time(self.time?);
+        match self.offset {
+            Some(offset) => offset.from_local_datetime(&time).single(),
+            None => Local
+                .from_local_datetime(&time)
+                .earliest()
+                .map(|at| at.fixed_offset()),
+        }
+    }
+}
+
+impl fmt::Display for Due {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.date.format("%a %Y-%m-%d"))?;
+        if let Some(time) = self.time {
+            write!(f, " {}", time.format("%H:%M"))?;
+        }
+        if let Some(offset) = self.offset {
+            write!(f, " {}", offset)?;
+        }
+        Ok(())
+    }
+}
+
+/// `at` as `YYYY-MM-DD HH:MM`, followed by its UTC offset when that is not
+/// the local one.
+pub fn format_at(at: DateTime<FixedOffset>) -> String {
+    let local = Local.offset_from_utc_datetime(&at.naive_utc()).fix();
+    if *at.offset() == local {
+        at.format("%Y-%m-%d %H:%M").to_string()
+    } else {
+        at.format("%Y-%m-%d %H:%M %:z").to_string()
+    }
+}
+
+/// How far `date` (or the exact moment `at` on it) is from `now`:
+/// `today`, `in 3h`, `in 2d` or `overdue by 2d`.
+pub fn relative(
+    date: NaiveDate,
+    at: Option<DateTime<FixedOffset>>,
+    now: DateTime<Local>,
+) -> String {
+    let left = match at {
+        Some(at) => at.with_timezone(&Local) - now,
+        None if date == now.date_naive() => return "today".to_string(),
+        None => date - now.date_naive(),
+    };
+    if left < TimeDelta::zero() {
+        format!("overdue by {}", span(-left))
+    } else {
+        format!("in {}", span(left))
+    }
+}
+
+/// The span in its largest whole unit: minutes, hours (under two days) or days.
+fn span(delta: TimeDelta) -> String {
+    if delta < TimeDelta::hours(1) {
+        format!("{}m", delta.num_minutes())
+    } else if delta < TimeDelta::days(2) {
+        format!("{}h", delta.num_hours())
+    } else {
+        format!("{}d", delta.num_days())
+    }
+}
+
+/// Resolves `input` against `now`. A bare weekday is the next such day
+/// after today; `next <weekday>` is that day in the following week
+/// (weeks start on Monday). `eow`, `eom` and `eoy` are the last day of the
+/// week, month and year; `3d`, `2w`, `1m` and `1y` count from today, and
+/// `in 3 hours` or `90min` from now. A time
+/// may be followed by `utc`, `z` or an offset such as `+02:00`.
+pub fn parse(input: &str, now: NaiveDateTime) -> Result<Due, String> {
+    let lower = input.trim().to_lowercase();
+    let mut words: Vec<&str> = lower.split_whitespace().collect();
+    let unknown = || {
+        format!(
+            "Cannot understand the due date '{}' (try YYYY-MM-DD, today, fri, next monday, \
+             in 3 days, in 2 hours, eom or 2w, optionally followed by a time such as 17:00 or 5pm).",
+            input.trim()
+        )
+    };
+    let mut zone = None;
+    if let [.., time, last] = words.as_slice()
+        && parse_time(time).is_some()
+        && let Some(parsed) = parse_offset(last)
+    {
+        zone = Some(parsed);
+        words.pop();
+    }
+    let mut time = None;
+    if let Some(last) = words.last()
+        && let Some(parsed) = parse_time(last)
+    {
+        time = Some(parsed);
+        words.pop();
+        if words.last() == Some(&"at") {
+            words.pop();
+        }
+    }
+    let today = now.date();
+    // `in 3 hours` and `90min` are exact moments rather than days
+    let count_and_unit = match words.as_slice() {
+        ["in", count, unit] => Some((*count, *unit)),
+        [word] => word
+            .find(|c: char| !c.is_ascii_digit())
+            .map(|split| word.split_at(split)),
+        _ => None,
+    };
+    if time.is_none()
+        && zone.is_none()
+        && let Some((count, unit)) = count_and_unit
+        && let Ok(count) = count.parse::<i64>()
+        && let Some(at) = match unit {
+            "h" | "hour" | "hours" => TimeDelta::try_hours(count),
+            "min" | "mins" | "minute" | "minutes" => TimeDelta::try_minutes(count),
+            _ => None,
+        }
+        .and_then(|delta| now.checked_add_signed(delta))
+    {
+        if !storable(at.date()) {
+            return Err(unknown());
+        }
+        return Ok(Due {
+            date: at.date(),
+            time: Some(at.time()),
+            offset: None,
+        });
+    }
+    let date = match words.as_slice() {
+        // a time alone means today
+        [] if time.is_some() => Some(today),
+        [word] => parse_word(word, today),
+        ["next", day] => weekday(day).map(|day| {
+            let monday = today - Days::new(today.weekday().num_days_from_monday().into());
+            monday + Days::new(7 + u64::from(day.num_days_from_monday()))
+        }),
+        ["in", count, unit] => count.parse().ok().and_then(|n| offset(today, n, unit)),
+        _ => None,
+    };
+    date.filter(|date| storable(*date))
+        .map(|date| Due {
+            date,
+            time,
+            offset: zone,
+        })
+        .ok_or_else(unknown)
+}
+
+/// Whether the year has four digits, as the task file and RFC 3339 need.
+fn storable(date: NaiveDate) -> bool {
+    (0..=9999).contains(&date.year())
+}
+
+/// `utc`, `gmt`, `z`, or `+HH:MM`, `+HHMM` or `+HH` (or with `-`).
+fn parse_offset(word: &str) -> Option<FixedOffset> {
+    if matches!(word, "utc" | "gmt" | "z") {
+        return FixedOffset::east_opt(0);
+    }
+    let sign = match word.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    let digits = word[1..].replace(':', "");
+    if !digits.chars().all(|c| c.is_ascii_digit()) || !matches!(digits.len(), 2 | 4) {
+        return None;
+    }
+    let hours: i32 = digits[..2].parse().ok()?;
+    let minutes: i32 = digits
+        .get(2..)
+        .filter(|m| !m.is_empty())
+        .map_or(Some(0), |m| m.parse().ok())?;
+    if hours > 14 || minutes > 59 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+fn parse_word(word: &str, today: NaiveDate) -> Option<NaiveDate> {
+    if let Ok(date) = NaiveDate::parse_from_str(word, "%Y-%m-%d") {
+        return Some(date);
+    }
+    match word {
+        "today" | "tod" => return Some(today),
+        "tomorrow" | "tom" => return today.succ_opt(),
+        "yesterday" => return today.pred_opt(),
+        "eow" => {
+            let left = 6 - today.weekday().num_days_from_monday();
+            return Some(today + Days::new(left.into()));
+        }
+        "eom" => {
+            let first = today.with_day(1)?;
+            return (first + Months::new(1)).pred_opt();
+        }
+        "eoy" => return NaiveDate::from_ymd_opt(today.year(), 12, 31),
+        _ => {}
+    }
+    if let Some(day) = weekday(word) {
+        let ahead = (7 + day.num_days_from_monday() - today.weekday().num_days_from_monday()) % 7;
+        let ahead = if ahead == 0 { 7 } else { ahead };
+        return Some(today + Days::new(ahead.into()));
+    }
+    let split = word.find(|c: char| !c.is_ascii_digit())?;
+    let (count, unit) = word.split_at(split);
+    offset(today, count.parse().ok()?, unit)
+}
+
+/// `count` days, weeks, months or years after `today`.
+fn offset(today: NaiveDate, count: u32, unit: &str) -> Option<NaiveDate> {
+    match unit {
+        "d" | "day" | "days" => today.checked_add_days(Days::new(count.into())),
+        "w" | "week" | "weeks" => today.checked_add_days(Days::new(7 * u64::from(count))),
+        "m" | "month" | "months" => today.checked_add_months(Months::new(count)),
+        "y" | "year" | "years" => today.checked_add_months(Months::new(count.checked_mul(12)?)),
+        _ => None,
+    }
+}
+
+/// `17:00`, `9:30`, `5pm` or `9:30am`.
+fn parse_time(word: &str) -> Option<NaiveTime> {
+    let (clock, pm) = if let Some(clock) = word.strip_suffix("am") {
+        (clock, Some(false))
+    } else if let Some(clock) = word.strip_suffix("pm") {
+        (clock, Some(true))
+    } else if word.contains(':') {
+        (word, None)
+    } else {
+        return None;
+    };
+    let (hour, minute) = clock.split_once(':').unwrap_or((clock, "0"));
+    let (hour, minute): (u32, u32) = (hour.parse().ok()?, minute.parse().ok()?);
+    let hour = match pm {
+        None => hour,
+        Some(_) if !(1..=12).contains(&hour) => return None,
+        Some(pm) => hour % 12 + if pm { 12 } else { 0 },
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_relative_dates() {
+        // a Wednesday
+        let now = NaiveDate::from_ymd_opt(2026, 10, 21)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        let date = |input: &str| parse(input, now).unwrap().to_string();
+        assert_eq!(date("2026-11-01"), "Sun 2026-11-01");
+        assert_eq!(date("today"), "Wed 2026-10-21");
+        assert_eq!(date("Tomorrow"), "Thu 2026-10-22");
+        assert_eq!(date("fri"), "Fri 2026-10-23");
+        assert_eq!(date("wed"), "Wed 2026-10-28");
+        assert_eq!(date("next monday"), "Mon 2026-10-26");
+        assert_eq!(date("next fri"), "Fri 2026-10-30");
+        assert_eq!(date("in 3 days"), "Sat 2026-10-24");
+        assert_eq!(date("2w"), "Wed 2026-11-04");
+        assert_eq!(date("1m"), "Sat 2026-11-21");
+        assert_eq!(date("eom"), "Sat 2026-10-31");
+        assert_eq!(date("eow"), "Sun 2026-10-25");
+        assert_eq!(date("fri 17:30"), "Fri 2026-10-23 17:30");
+        assert_eq!(date("tomorrow at 9am"), "Thu 2026-10-22 09:00");
+        assert_eq!(date("12pm"), "Wed 2026-10-21 12:00");
+        assert_eq!(date("12am"), "Wed 2026-10-21 00:00");
+        assert_eq!(date("in 3 hours"), "Wed 2026-10-21 12:00");
+        assert_eq!(date("90min"), "Wed 2026-10-21 10:30");
+        assert_eq!(date("fri 9:00 utc"), "Fri 2026-10-23 09:00 +00:00");
+        assert_eq!(date("today 17:00 -05:30"), "Wed 2026-10-21 17:00 -05:30");
+        let handoff = parse("fri 17:00 +0200", now).unwrap().at().unwrap();
+        assert_eq!(handoff.to_rfc3339(), "2026-10-23T17:00:00+02:00");
+        for bad in [
+            "",
+            "soon",
+            "next week",
+            "in three days",
+            "fri 25:00",
+            "13pm",
+            "fri utc",
+            "fri 17:00 +25",
+        ] {
+            assert!(parse(bad, now).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_far_future_dates() {
+        let now = NaiveDate::from_ymd_opt(2026, 10, 21)
+            .unwrap()
+            .and_hms_opt(9, 0, 0)
+            .unwrap();
+        // far but four-digit years survive the trip through RFC 3339
+        let at = parse("in 60000000 hours", now).unwrap().at().unwrap();
+        assert_eq!(at.year(), 8871);
+        assert_eq!(DateTime::parse_from_rfc3339(&at.to_rfc3339()), Ok(at));
+        assert_eq!(parse("in 7000 years", now).unwrap().date.year(), 9026);
+        for bad in [
+            "in 9999999999999999 hours",
+            "in 99999999999 minutes",
+            "in 8000 years",
+            "in 999999999 years",
+            "9999999d",
+        ] {
+            assert!(parse(bad, now).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_relative_wording() {
+        let now = Local.with_ymd_and_hms(2026, 10, 21, 9, 0, 0).unwrap();
+        let today = now.date_naive();
+        let at = |hours: i64| Some((now + TimeDelta::hours(hours)).fixed_offset());
+        assert_eq!(relative(today, None, now), "today");
+        assert_eq!(relative(today, at(3), now), "in 3h");
+        assert_eq!(relative(today, at(-50), now), "overdue by 2d");
+        assert_eq!(relative(today + Days::new(3), None, now), "in 3d");
+        assert_eq!(relative(today - Days::new(2), None, now), "overdue by 2d");
+        let zoned = (now + TimeDelta::minutes(90))
+            .with_timezone(&FixedOffset::east_opt(13 * 3600).unwrap());
+        assert_eq!(relative(today, Some(zoned), now), "in 1h");
+    }
+}
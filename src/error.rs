@@ -0,0 +1,59 @@
+//! `TaskError`, the error type returned by every library operation.
+
+use std::fmt;
+use std::io;
+
+#[derive(Debug)]
+pub enum TaskError {
+    /// Reading or writing `path` failed.
+    Io { path: String, source: io::Error },
+    /// A stored file could not be understood; `line` is 1-based.
+    Parse { line: usize, message: String },
+    /// Input was rejected before touching any data.
+    Validation(String),
+    /// No task matched the given reference.
+    NotFound(String),
+    /// The operation clashes with existing data (e.g. an ambiguous uuid prefix).
+    Conflict(String),
+    /// The file at this path was written by someone else since it was loaded.
+    ChangedOnDisk(String),
+}
+
+pub type Result<T> = std::result::Result<T, TaskError>;
+
+impl TaskError {
+    pub fn io(path: &str, source: io::Error) -> Self {
+        TaskError::Io {
+            path: path.to_string(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for TaskError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskError::Io { path, source } => write!(f, "{}: {}", path, source),
+            TaskError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            TaskError::Validation(message)
+            | TaskError::NotFound(message)
+            | TaskError::Conflict(message) => write!(f, "{}", message),
+            TaskError::ChangedOnDisk(path) => {
+                write!(
+                    f,
+                    "{} was changed by another process since it was loaded",
+                    path
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for TaskError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TaskError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,530 @@
+//! Undo/redo built from reversible operations, persisted next to the task file.
+
+use crate::error::{Result, TaskError};
+use crate::storage::write_atomic;
+use crate::task::Status;
+use crate::{Task, TaskList};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use std::io;
+use uuid::Uuid;
+
+/// Steps kept for undo unless configured otherwise.
+pub const DEFAULT_HISTORY_LIMIT: usize = 100;
+
+/// Where the history of `filename` is stored.
+pub fn history_path(filename: &str) -> String {
+    format!("{}.history", filename)
+}
+
+/// The whole list as it was before or after a bulk change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub tasks: Vec<Task>,
+    pub next_id: u32,
+}
+
+impl From<&TaskList> for Snapshot {
+    fn from(list: &TaskList) -> Self {
+        Self {
+            tasks: list.tasks().to_vec(),
+            next_id: list.next_id(),
+        }
+    }
+}
+
+/// One change to a task list, holding enough to apply it again or reverse it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum Op {
+    Add {
+        index: usize,
+        task: Task,
+    },
+    Remove {
+        index: usize,
+        task: Task,
+    },
+    /// Any change to a single task's fields.
+    Edit {
+        before: Box<Task>,
+        after: Box<Task>,
+    },
+    /// Written before tasks had a status; status changes are now edits.
+    SetCompleted {
+        id: u32,
+        uuid: Uuid,
+        before: bool,
+        after: bool,
+    },
+    Sort {
+        key: String,
+        before: Vec<Uuid>,
+        after: Vec<Uuid>,
+    },
+    /// The whole list was swapped out (import, load, merge); `source` says why.
+    Replace {
+        source: String,
+        before: Snapshot,
+        after: Snapshot,
+    },
+    /// Steps that belong together, e.g. completing a task with its subtasks.
+    /// The first is the one that was asked for.
+    Batch {
+        ops: Vec<Op>,
+    },
+}
+
+impl Op {
+    /// The operation that undoes this one.
+    pub fn inverse(&self) -> Op {
+        match self.clone() {
+            Op::Add { index, task } => Op::Remove { index, task },
+            Op::Remove { index, task } => Op::Add { index, task },
+            Op::Edit { before, after } => Op::Edit {
+                before: after,
+                after: before,
+            },
+            Op::SetCompleted {
+                id,
+                uuid,
+                before,
+                after,
+            } => Op::SetCompleted {
+                id,
+                uuid,
+                before: after,
+                after: before,
+            },
+            Op::Sort { key, before, after } => Op::Sort {
+                key,
+                before: after,
+                after: before,
+            },
+            Op::Replace {
+                source,
+                before,
+                after,
+            } => Op::Replace {
+                source,
+                before: after,
+                after: before,
+            },
+            Op::Batch { ops } => Op::Batch {
+                ops: ops.iter().rev().map(Op::inverse).collect(),
+            },
+        }
+    }
+
+    pub fn edit(before: Task, after: Task) -> Op {
+        Op::Edit {
+            before: Box::new(before),
+            after: Box::new(after),
+        }
+    }
+
+    /// Several steps as one; a single step stays as it is.
+    pub fn batch(mut ops: Vec<Op>) -> Op {
+        if ops.len() == 1 {
+            ops.pop().unwrap()
+        } else {
+            Op::Batch { ops }
+        }
+    }
+
+    /// The step a batch was made for; any other operation itself.
+    pub fn primary(&self) -> &Op {
+        match self {
+            Op::Batch { ops } => ops.first().map_or(self, Op::primary),
+            op => op,
+        }
+    }
+
+    /// Whether applying this would leave the list as it was.
+    pub fn is_noop(&self) -> bool {
+        match self {
+            Op::Add { .. } | Op::Remove { .. } => false,
+            Op::Edit { before, after } => before == after,
+            Op::SetCompleted { before, after, .. } => before == after,
+            Op::Sort { before, after, .. } => before == after,
+            Op::Replace { before, after, .. } => before == after,
+            Op::Batch { ops } => ops.iter().all(Op::is_noop),
+        }
+    }
+
+    /// Performs the operation on `list`. Fails without changing anything if
+    /// the list no longer looks the way the operation expects, e.g. because
+    /// the file was edited by someone else.
+    pub fn apply(&self, list: &mut TaskList) -> Result<()> {
+        let find = |list: &TaskList, uuid: Uuid, id: u32| {
+            list.position_by_uuid(uuid)
+                .ok_or_else(|| TaskError::Conflict(format!("Task {} no longer exists.", id)))
+        };
+        match self {
+            Op::Add { index, task } => list.insert(*index, task.clone()),
+            Op::Remove { task, .. } => {
+                let index = find(list, task.uuid, task.id)?;
+                list.remove(index);
+                Ok(())
+            }
+            Op::Edit { before, after } => {
+                let index = find(list, before.uuid, before.id)?;
+                let task = list.get(index).unwrap();
+                if task != before.as_ref() {
+                    return Err(TaskError::Conflict(format!(
+                        "Task {} was changed since.",
+                        task.id
+                    )));
+                }
+                *list.get_mut(index).unwrap() = after.as_ref().clone();
+                Ok(())
+            }
+            Op::SetCompleted {
+                id,
+                uuid,
+                before,
+                after,
+            } => {
+                let index = find(list, *uuid, *id)?;
+                if list.get(index).unwrap().is_done() != *before {
+                    return Err(TaskError::Conflict(format!(
+                        "Task {} was changed since.",
+                        id
+                    )));
+                }
+                let status = if *after {
+                    Status::Done
+                } else {
+                    Status::Pending
+                };
+                list.get_mut(index).unwrap().status = status;
+                Ok(())
+            }
+            Op::Sort { after, .. } => {
+                list.reorder(after);
+                Ok(())
+            }
+            Op::Replace { after, .. } => {
+                *list = TaskList::with_next_id(after.tasks.clone(), after.next_id)?;
+                Ok(())
+            }
+            Op::Batch { ops } => {
+                // all or nothing
+                let mut updated = list.clone();
+                for op in ops {
+                    op.apply(&mut updated)?;
+                }
+                *list = updated;
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Op::Add { task, .. } => write!(f, "add task {} '{}'", task.id, summary(task)),
+            Op::Remove { task, .. } => write!(f, "remove task {} '{}'", task.id, summary(task)),
+            Op::Edit { before, after } if before.status != after.status => {
+                write!(f, "mark task {} {}", after.id, after.status)
+            }
+            Op::Edit { after, .. } => write!(f, "edit task {} '{}'", after.id, summary(after)),
+            Op::SetCompleted { id, after, .. } => {
+                let status = if *after { "completed" } else { "pending" };
+                write!(f, "mark task {} {}", id, status)
+            }
+            Op::Sort { key, .. } => write!(f, "sort by {}", key),
+            Op::Replace { source, after, .. } => {
+                write!(f, "{} ({} tasks)", source, after.tasks.len())
+            }
+            Op::Batch { ops } => match ops.split_first() {
+                Some((first, [])) => write!(f, "{}", first),
+                Some((first, rest)) => write!(f, "{} (+{} more)", first, rest.len()),
+                None => write!(f, "nothing"),
+            },
+        }
+    }
+}
+
+/// First line of the description, shortened for one-line listings.
+fn summary(task: &Task) -> String {
+    let line = task.description.lines().next().unwrap_or_default();
+    match line.char_indices().nth(30) {
+        Some((end, _)) => format!("{}...", &line[..end]),
+        None => line.to_string(),
+    }
+}
+
+/// A recorded operation and when it happened.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Step {
+    pub at: DateTime<Local>,
+    pub op: Op,
+}
+
+/// Something done to an in-memory history, kept so it can be replayed on
+/// the stored copy, which other processes may have added to meanwhile.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    Record(Op),
+    Undo(Step),
+    Redo(Step),
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Stored {
+    done: VecDeque<Step>,
+    undone: Vec<Step>,
+}
+
+/// Steps that can be undone (oldest first) and steps that can be redone.
+#[derive(Debug)]
+pub struct History {
+    done: VecDeque<Step>,
+    undone: Vec<Step>,
+    limit: usize,
+}
+
+impl Default for History {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_LIMIT)
+    }
+}
+
+impl History {
+    /// Keeps at most `limit` steps; the oldest are forgotten first.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            done: VecDeque::new(),
+            undone: Vec::new(),
+            limit,
+        }
+    }
+
+    /// Remembers an operation that was just applied. Anything undone before
+    /// can no longer be redone.
+    pub fn record(&mut self, op: Op) {
+        if op.is_noop() {
+            return;
+        }
+        self.undone.clear();
+        self.done.push_back(Step {
+            at: Local::now(),
+            op,
+        });
+        while self.done.len() > self.limit {
+            self.done.pop_front();
+        }
+    }
+
+    /// Reverses the latest step; `Ok(None)` when there is nothing to undo.
+    pub fn undo(&mut self, list: &mut TaskList) -> Result<Option<&Step>> {
+        let Some(step) = self.done.pop_back() else {
+            return Ok(None);
+        };
+        if let Err(error) = step.op.inverse().apply(list) {
+            self.done.push_back(step);
+            return Err(error);
+        }
+        self.undone.push(step);
+        Ok(self.undone.last())
+    }
+
+    /// Applies the most recently undone step again.
+    pub fn redo(&mut self, list: &mut TaskList) -> Result<Option<&Step>> {
+        let Some(step) = self.undone.pop() else {
+            return Ok(None);
+        };
+        if let Err(error) = step.op.apply(list) {
+            self.undone.push(step);
+            return Err(error);
+        }
+        self.done.push_back(step);
+        Ok(self.done.back())
+    }
+
+    /// Applies changes made to another copy of this history. An undo or
+    /// redo whose step is no longer at the top here (another process
+    /// recorded something since) is kept as a new step instead, so it can
+    /// still be reversed.
+    pub fn replay(&mut self, changes: &[Change]) {
+        for change in changes {
+            match change {
+                Change::Record(op) => self.record(op.clone()),
+                Change::Undo(step) if self.done.back() == Some(step) => {
+                    self.undone.extend(self.done.pop_back());
+                }
+                Change::Undo(step) => self.record(step.op.inverse()),
+                Change::Redo(step) if self.undone.last() == Some(step) => {
+                    self.done.extend(self.undone.pop());
+                }
+                Change::Redo(step) => self.record(step.op.clone()),
+            }
+        }
+    }
+
+    /// Steps that can be undone, oldest first.
+    pub fn done(&self) -> impl Iterator<Item = &Step> {
+        self.done.iter()
+    }
+
+    /// Steps that can be redone, next redo first.
+    pub fn undone(&self) -> impl Iterator<Item = &Step> {
+        self.undone.iter().rev()
+    }
+
+    /// One line per step: numbered undoable steps, oldest first, then the
+    /// steps that can be redone.
+    pub fn listing(&self) -> Vec<String> {
+        let time = |step: &Step| step.at.format("%Y-%m-%d %H:%M").to_string();
+        let mut lines: Vec<String> = self
+            .done()
+            .enumerate()
+            .map(|(n, step)| format!("{:>3}. {}  {}", n + 1, time(step), step.op))
+            .collect();
+        lines.extend(
+            self.undone()
+                .map(|step| format!("     {}  {} (undone)", time(step), step.op)),
+        );
+        lines
+    }
+
+    /// Reads the history stored for `filename`; none yet is an empty history.
+    pub fn load(filename: &str, limit: usize) -> Result<Self> {
+        let path = history_path(filename);
+        let data = match std::fs::read_to_string(&path) {
+            Ok(data) => data,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::new(limit)),
+            Err(e) => return Err(TaskError::io(&path, e)),
+        };
+        let stored: Stored = serde_json::from_str(&data).map_err(|e| TaskError::Parse {
+            line: e.line(),
+            message: format!("{}: {}", path, e),
+        })?;
+        let mut history = Self {
+            done: stored.done,
+            undone: stored.undone,
+            limit,
+        };
+        while history.done.len() > limit {
+            history.done.pop_front();
+        }
+        Ok(history)
+    }
+
+    /// Stores the history next to `filename`.
+    pub fn save(&self, filename: &str) -> Result<()> {
+        let stored = Stored {
+            done: self.done.clone(),
+            undone: self.undone.clone(),
+        };
+        let json =
+            serde_json::to_string(&stored).map_err(|e| TaskError::Validation(e.to_string()))?;
+        write_atomic(&history_path(filename), &json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Priority;
+    use crate::ops::{self, NewTask};
+    use crate::sort::SortKey;
+    use crate::urgency::Urgency;
+
+    #[test]
+    fn test_undo_redo_each_operation() {
+        let mut list = TaskList::new();
+        let mut history = History::default();
+        history.record(ops::add_task(&mut list, NewTask::new("b")).unwrap());
+        let mut new = NewTask::new("a");
+        new.priority = Priority::Critical;
+        history.record(ops::add_task(&mut list, new).unwrap());
+        history.record(ops::edit_task(&mut list, "1", "b, edited").unwrap());
+        history.record(ops::mark_task(&mut list, "2", Default::default()).unwrap());
+        history.record(ops::sort_tasks(
+            &mut list,
+            &SortKey::Priority.into(),
+            &Urgency::default(),
+        ));
+        history.record(ops::remove_task(&mut list, "1").unwrap());
+        let imported = TaskList::try_from(vec![Task::new("imported")]).unwrap();
+        history.record(ops::replace_tasks(
+            &mut list,
+            imported,
+            "import from x.json",
+        ));
+        let end = list.clone();
+        assert_eq!(history.done().count(), 7);
+
+        let mut states = Vec::new();
+        while let Some(step) = history.undo(&mut list).unwrap() {
+            states.push(step.op.to_string());
+        }
+        assert!(list.is_empty());
+        assert_eq!(states[0], "import from x.json (1 tasks)");
+        assert_eq!(states[6], "add task 1 'b'");
+        while history.redo(&mut list).unwrap().is_some() {}
+        assert_eq!(list, end);
+    }
+
+    #[test]
+    fn test_history_is_bounded_and_persisted() {
+        let filename = "test_history_tasks.txt";
+        let mut list = TaskList::new();
+        let mut history = History::new(2);
+        for name in ["one", "two", "three"] {
+            history.record(ops::add_task(&mut list, NewTask::new(name)).unwrap());
+        }
+        assert_eq!(history.done().count(), 2);
+        history.undo(&mut list).unwrap();
+        history.save(filename).unwrap();
+
+        let mut loaded = History::load(filename, 2).unwrap();
+        assert_eq!(loaded.done().count(), 1);
+        assert_eq!(loaded.undone().count(), 1);
+        loaded.redo(&mut list).unwrap();
+        assert_eq!(list.len(), 3);
+        // a step that no longer fits the list is refused and kept
+        list.remove(2);
+        assert!(loaded.undo(&mut list).is_err());
+        assert_eq!(loaded.done().count(), 2);
+        std::fs::remove_file(history_path(filename)).unwrap();
+    }
+
+    #[test]
+    fn test_replay_keeps_steps_recorded_elsewhere() {
+        let mut list = TaskList::new();
+        let mut stored = History::default();
+        stored.replay(&[Change::Record(
+            ops::add_task(&mut list, NewTask::new("one")).unwrap(),
+        )]);
+        let first = stored.done().next().unwrap().clone();
+
+        // unchanged elsewhere: the undo moves the same step
+        let mut same = History::default();
+        same.replay(&[Change::Record(first.op.clone())]);
+        let top = same.done().next().unwrap().clone();
+        same.replay(&[Change::Undo(top.clone())]);
+        assert_eq!(same.done().count(), 0);
+        assert_eq!(same.undone().next(), Some(&top));
+        same.replay(&[Change::Redo(top.clone())]);
+        assert_eq!(same.done().next(), Some(&top));
+
+        // another process recorded a step since: the undo becomes a step
+        let theirs = ops::add_task(&mut list, NewTask::new("two")).unwrap();
+        stored.record(theirs);
+        let later = ops::add_task(&mut list, NewTask::new("three")).unwrap();
+        stored.replay(&[Change::Undo(first.clone()), Change::Record(later)]);
+        let steps: Vec<String> = stored.done().map(|step| step.op.to_string()).collect();
+        assert_eq!(steps.len(), 4);
+        assert_eq!(steps[0], "add task 1 'one'");
+        assert_eq!(steps[1], "add task 2 'two'");
+        assert_eq!(steps[2], first.op.inverse().to_string());
+        assert_eq!(steps[3], "add task 3 'three'");
+        assert_eq!(stored.undone().count(), 0);
+    }
+}
@@ -0,0 +1,295 @@
+//! Append-only journal of every saved change, for auditing and for rebuilding
+//! the list as it was at any past moment.
+//!
+//! The journal is written only while the task file's exclusive lock is held,
+//! so its events are in the same order as the saves they describe.
+
+use crate::TaskList;
+use crate::error::{Result, TaskError};
+use crate::history::{Op, Snapshot};
+use crate::lock::FileLock;
+use crate::storage::write_atomic;
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+
+/// After this many events without a snapshot, a save appends one so that
+/// replaying never has to start from the very beginning.
+pub const SNAPSHOT_EVERY: usize = 500;
+
+/// Where the journal of `filename` is stored.
+pub fn journal_path(filename: &str) -> String {
+    format!("{}.journal", filename)
+}
+
+/// Who is making changes: `$TASK_USER`, else the login name.
+pub fn current_user() -> String {
+    ["TASK_USER", "USER", "USERNAME"]
+        .iter()
+        .find_map(|name| std::env::var(name).ok().filter(|user| !user.is_empty()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Change {
+    /// The whole list, used where the individual steps are unknown (or were
+    /// compacted away).
+    Snapshot(Snapshot),
+    Op(Box<Op>),
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Change::Snapshot(snapshot) => write!(f, "snapshot of {} tasks", snapshot.tasks.len()),
+            Change::Op(op) => write!(f, "{}", op),
+        }
+    }
+}
+
+/// One journal line: what changed, when and by whom.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    pub at: DateTime<Local>,
+    pub user: String,
+    pub change: Change,
+}
+
+impl Event {
+    /// A change made now by the current user.
+    pub fn new(change: Change) -> Self {
+        Self {
+            at: Local::now(),
+            user: current_user(),
+            change,
+        }
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}  {:<10}  {}",
+            self.at.format("%Y-%m-%d %H:%M"),
+            self.user,
+            self.change
+        )
+    }
+}
+
+/// Reads every event of `filename`'s journal, oldest first; none if it has
+/// no journal.
+pub fn read_events(filename: &str) -> Result<Vec<Event>> {
+    let path = journal_path(filename);
+    let data = match fs::read_to_string(&path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(TaskError::io(&path, e)),
+    };
+    data.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|e| TaskError::Parse {
+                line: index + 1,
+                message: format!("{}: {}", path, e),
+            })
+        })
+        .collect()
+}
+
+/// Rebuilds the list from `events`, stopping before the first one after
+/// `until`. Fails if the events do not fit together or if they start with a
+/// snapshot taken after `until`, since nothing older is known.
+pub fn replay(events: &[Event], until: Option<DateTime<Local>>) -> Result<TaskList> {
+    let end = until.map_or(events.len(), |until| {
+        events.partition_point(|event| event.at <= until)
+    });
+    if let (Some(until), Some(first)) = (until, events.first())
+        && end == 0
+        && matches!(first.change, Change::Snapshot(_))
+    {
+        return Err(TaskError::NotFound(format!(
+            "The journal only goes back to {}, not {}.",
+            first.at.format("%Y-%m-%d %H:%M"),
+            until.format("%Y-%m-%d %H:%M")
+        )));
+    }
+    // the latest snapshot holds everything before it
+    let start = events[..end]
+        .iter()
+        .rposition(|event| matches!(event.change, Change::Snapshot(_)))
+        .unwrap_or(0);
+    let mut task_list = TaskList::new();
+    for (index, event) in events.iter().enumerate().take(end).skip(start) {
+        let applied = match &event.change {
+            Change::Snapshot(snapshot) => {
+                TaskList::with_next_id(snapshot.tasks.clone(), snapshot.next_id)
+                    .map(|list| task_list = list)
+            }
+            Change::Op(op) => op.apply(&mut task_list),
+        };
+        applied.map_err(|e| TaskError::Parse {
+            line: index + 1,
+            message: format!("cannot replay '{}': {}", event.change, e),
+        })?;
+    }
+    task_list.mark_saved();
+    Ok(task_list)
+}
+
+/// Records a save of `task_list` to `filename`. `changes` are the operations
+/// that led to it; `None` means they are unknown and the whole list is
+/// recorded instead, but only if a journal already exists. `base` starts a
+/// new journal with what the file held before. The caller holds the file's
+/// exclusive lock.
+pub(crate) fn record(
+    filename: &str,
+    base: Option<Event>,
+    task_list: &TaskList,
+    changes: Option<&[Op]>,
+) -> Result<()> {
+    let path = journal_path(filename);
+    let exists = fs::exists(&path).map_err(|e| TaskError::io(&path, e))?;
+    let snapshot = || Event::new(Change::Snapshot(Snapshot::from(task_list)));
+    let mut events: Vec<Event> = base.into_iter().collect();
+    match changes {
+        Some(ops) => events.extend(
+            ops.iter()
+                .filter(|op| !op.is_noop())
+                .map(|op| Event::new(Change::Op(Box::new(op.clone())))),
+        ),
+        None if exists => events.push(snapshot()),
+        None => {}
+    }
+    if events.is_empty() {
+        return Ok(());
+    }
+    let since_snapshot = read_events(filename)?
+        .iter()
+        .rev()
+        .take_while(|event| !matches!(event.change, Change::Snapshot(_)))
+        .count();
+    if since_snapshot + events.len() >= SNAPSHOT_EVERY
+        && !matches!(events.last().unwrap().change, Change::Snapshot(_))
+    {
+        events.push(snapshot());
+    }
+    append(&path, &events)
+}
+
+/// One JSON object per line.
+fn to_lines(events: &[Event]) -> Result<String> {
+    let mut lines = String::new();
+    for event in events {
+        let line =
+            serde_json::to_string(event).map_err(|e| TaskError::Validation(e.to_string()))?;
+        lines.push_str(&line);
+        lines.push('\n');
+    }
+    Ok(lines)
+}
+
+fn append(path: &str, events: &[Event]) -> Result<()> {
+    let lines = to_lines(events)?;
+    let write = || -> io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        file.write_all(lines.as_bytes())?;
+        file.sync_all()
+    };
+    write().map_err(|e| TaskError::io(path, e))
+}
+
+/// Folds every event up to `until` (all of them by default) into a single
+/// snapshot, giving up the detail of those steps. Returns how many events
+/// were folded.
+pub fn compact(filename: &str, until: Option<DateTime<Local>>) -> Result<usize> {
+    let _lock = FileLock::exclusive(filename)?;
+    let events = read_events(filename)?;
+    let end = until.map_or(events.len(), |until| {
+        events.partition_point(|event| event.at <= until)
+    });
+    if end == 0 || (end == 1 && matches!(events[0].change, Change::Snapshot(_))) {
+        return Ok(0);
+    }
+    let folded = replay(&events[..end], None)?;
+    let mut kept = vec![Event {
+        at: events[end - 1].at,
+        user: current_user(),
+        change: Change::Snapshot(Snapshot::from(&folded)),
+    }];
+    kept.extend_from_slice(&events[end..]);
+    write_atomic(&journal_path(filename), &to_lines(&kept)?)?;
+    Ok(end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ops::{self, NewTask};
+    use crate::storage::{load_tasks, save_tasks, save_tasks_checked};
+    use chrono::Duration;
+
+    fn remove_journaled_files(filename: &str) {
+        for path in [
+            filename.to_string(),
+            journal_path(filename),
+            crate::lock::lock_path(filename),
+        ] {
+            let _ = fs::remove_file(path);
+        }
+        for backup in crate::storage::list_backups(filename).unwrap() {
+            fs::remove_file(&backup.path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_replay_matches_saved_file() {
+        let filename = "test_journal_tasks.txt";
+        remove_journaled_files(filename);
+        // a file that predates the journal becomes its first snapshot
+        let mut list = TaskList::new();
+        ops::add_task(&mut list, NewTask::new("old")).unwrap();
+        save_tasks(filename, &list).unwrap();
+        assert!(read_events(filename).unwrap().is_empty());
+
+        let mut ops = vec![ops::add_task(&mut list, NewTask::new("new")).unwrap()];
+        ops.push(ops::mark_task(&mut list, "1", Default::default()).unwrap());
+        save_tasks_checked(filename, &list, 0, None, Some(&ops)).unwrap();
+        ops = vec![ops::remove_task(&mut list, "2").unwrap()];
+        save_tasks_checked(filename, &list, 0, None, Some(&ops)).unwrap();
+        // a save without known steps is journaled as a snapshot
+        ops::edit_task(&mut list, "1", "old, edited").unwrap();
+        save_tasks(filename, &list).unwrap();
+
+        let events = read_events(filename).unwrap();
+        assert_eq!(events.len(), 5);
+        assert!(events[1].to_string().ends_with("  add task 2 'new'"));
+        assert_eq!(
+            replay(&events, None).unwrap(),
+            load_tasks(filename).unwrap()
+        );
+        let before_edit = replay(&events, Some(events[3].at)).unwrap();
+        assert_eq!(before_edit.len(), 1);
+        assert_eq!(before_edit.get(0).unwrap().description, "old");
+        assert!(before_edit.get(0).unwrap().is_done());
+
+        assert_eq!(compact(filename, Some(events[2].at)).unwrap(), 3);
+        let compacted = read_events(filename).unwrap();
+        assert_eq!(compacted.len(), 3);
+        assert_eq!(
+            replay(&compacted, None).unwrap(),
+            load_tasks(filename).unwrap()
+        );
+        let too_early = events[0].at - Duration::days(1);
+        assert!(matches!(
+            replay(&compacted, Some(too_early)),
+            Err(TaskError::NotFound(_))
+        ));
+        remove_journaled_files(filename);
+    }
+}
@@ -0,0 +1,27 @@
+//! Shared task model and operations used by every to-do binary.
+
+pub mod cli;
+pub mod config;
+pub mod due;
+pub mod error;
+pub mod history;
+pub mod journal;
+pub mod list;
+pub mod lock;
+pub mod menu;
+pub mod merge;
+pub mod ops;
+pub mod priority;
+pub mod query;
+pub mod recur;
+pub mod report;
+pub mod sort;
+pub mod storage;
+pub mod task;
+pub mod ui;
+pub mod urgency;
+
+pub use error::{Result, TaskError};
+pub use list::TaskList;
+pub use priority::Priority;
+pub use task::Task;
@@ -0,0 +1,565 @@
+//! `TaskList`, the ordered collection every binary operates on.
+
+use crate::error::{Result, TaskError};
+use crate::sort::{SortKey, SortSpec};
+use crate::task::{Status, Task};
+use crate::urgency::Urgency;
+use chrono::Local;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Shortest all-digit string treated as a uuid prefix rather than an id.
+const MIN_NUMERIC_UUID_PREFIX: usize = 8;
+
+#[derive(Debug, Clone)]
+pub struct TaskList {
+    tasks: Vec<Task>,
+    next_id: u32,
+    /// Set by every change, cleared once the list is known to match its file.
+    dirty: bool,
+}
+
+/// Lists are equal when their tasks and id counter are; the dirty flag is
+/// bookkeeping and does not count.
+impl PartialEq for TaskList {
+    fn eq(&self, other: &Self) -> bool {
+        self.tasks == other.tasks && self.next_id == other.next_id
+    }
+}
+
+impl Default for TaskList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TaskList {
+    pub fn new() -> Self {
+        Self {
+            tasks: Vec::new(),
+            next_id: 1,
+            dirty: false,
+        }
+    }
+
+    /// Builds a list from stored tasks, keeping their ids where they are unique.
+    ///
+    /// Tasks without an id or uuid (or with one already taken) get fresh ones.
+    /// `next_id` is the persisted counter; it is raised past the largest id so
+    /// ids of removed tasks are never handed out again. The result counts as
+    /// unsaved until `mark_saved` is called. Fails if an id is `u32::MAX`,
+    /// which would leave no id to hand out after it.
+    pub fn with_next_id(tasks: Vec<Task>, next_id: u32) -> Result<Self> {
+        let max_id = tasks.iter().map(|t| t.id).max().unwrap_or(0);
+        let mut list = Self {
+            tasks: Vec::with_capacity(tasks.len()),
+            next_id: next_id.max(after(max_id)?),
+            dirty: true,
+        };
+        let mut ids = HashSet::new();
+        let mut uuids = HashSet::new();
+        for mut task in tasks {
+            if task.id == 0 || !ids.insert(task.id) {
+                task.id = list.take_id()?;
+                ids.insert(task.id);
+            }
+            if task.uuid.is_nil() || !uuids.insert(task.uuid) {
+                task.uuid = Uuid::new_v4();
+                uuids.insert(task.uuid);
+            }
+            list.tasks.push(task);
+        }
+        Ok(list)
+    }
+
+    /// The id the next added task will receive.
+    pub fn next_id(&self) -> u32 {
+        self.next_id
+    }
+
+    /// Whether the list changed since it was last loaded or saved.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Records that the list now matches what is stored on disk.
+    pub fn mark_saved(&mut self) {
+        self.dirty = false;
+    }
+
+    /// Records a change made behind the list's back, e.g. restoring a snapshot.
+    pub fn mark_modified(&mut self) {
+        self.dirty = true;
+    }
+
+    fn take_id(&mut self) -> Result<u32> {
+        let id = self.next_id;
+        self.next_id = after(id)?;
+        Ok(id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    pub fn tasks(&self) -> &[Task] {
+        &self.tasks
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Task> {
+        self.tasks.iter()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Task> {
+        self.tasks.get(index)
+    }
+
+    /// Counts as a change, since the caller may edit the task.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut Task> {
+        let task = self.tasks.get_mut(index)?;
+        self.dirty = true;
+        Some(task)
+    }
+
+    /// Appends a task under a freshly assigned id and returns that id.
+    pub fn add(&mut self, mut task: Task) -> Result<u32> {
+        if task.description.trim().is_empty() {
+            return Err(TaskError::Validation(
+                "Description cannot be empty.".to_string(),
+            ));
+        }
+        let id = self.take_id()?;
+        task.id = id;
+        let now = Local::now();
+        task.created_at.get_or_insert(now);
+        task.modified_at.get_or_insert(now);
+        if task.uuid.is_nil() || self.tasks.iter().any(|t| t.uuid == task.uuid) {
+            task.uuid = Uuid::new_v4();
+        }
+        self.tasks.push(task);
+        self.dirty = true;
+        Ok(id)
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<Task> {
+        if index < self.tasks.len() {
+            self.dirty = true;
+            Some(self.tasks.remove(index))
+        } else {
+            None
+        }
+    }
+
+    /// Marks an open task done, or a closed one pending again, and returns
+    /// whether it is now done.
+    pub fn toggle(&mut self, index: usize) -> Option<bool> {
+        let task = self.get_mut(index)?;
+        let status = if task.is_closed() {
+            Status::Pending
+        } else {
+            Status::Done
+        };
+        task.set_status(status, Local::now());
+        Some(task.is_done())
+    }
+
+    pub fn position_by_id(&self, id: u32) -> Option<usize> {
+        self.tasks.iter().position(|t| t.id == id)
+    }
+
+    pub fn position_by_uuid(&self, uuid: Uuid) -> Option<usize> {
+        self.tasks.iter().position(|t| t.uuid == uuid)
+    }
+
+    /// Puts back a task that already has its id, e.g. when undoing a removal.
+    /// Fails if its id or uuid is in use.
+    pub fn insert(&mut self, index: usize, task: Task) -> Result<()> {
+        if self
+            .tasks
+            .iter()
+            .any(|t| t.id == task.id || t.uuid == task.uuid)
+        {
+            return Err(TaskError::Conflict(format!(
+                "Task {} is already in the list.",
+                task.id
+            )));
+        }
+        self.next_id = self.next_id.max(after(task.id)?);
+        let index = index.min(self.tasks.len());
+        self.tasks.insert(index, task);
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// The uuids in list order.
+    pub fn order(&self) -> Vec<Uuid> {
+        self.tasks.iter().map(|t| t.uuid).collect()
+    }
+
+    /// Rearranges tasks to follow `order`; tasks it does not mention keep
+    /// their relative order after the rest.
+    pub fn reorder(&mut self, order: &[Uuid]) {
+        let rank = |task: &Task| {
+            order
+                .iter()
+                .position(|u| *u == task.uuid)
+                .unwrap_or(order.len())
+        };
+        self.sort_by(|a, b| rank(a).cmp(&rank(b)));
+    }
+
+    /// The tasks directly under `parent`, in list order.
+    pub fn children(&self, parent: Uuid) -> impl Iterator<Item = &Task> {
+        self.tasks.iter().filter(move |t| t.parent == Some(parent))
+    }
+
+    /// Done and total direct subtasks, not counting cancelled ones; `None`
+    /// for a task without any.
+    pub fn progress(&self, parent: Uuid) -> Option<(usize, usize)> {
+        let (done, total) = self
+            .children(parent)
+            .filter(|t| t.status != Status::Cancelled)
+            .fold((0, 0), |(done, total), t| {
+                (done + t.is_done() as usize, total + 1)
+            });
+        (total > 0).then_some((done, total))
+    }
+
+    /// Indices of every task below `parent`, depth first.
+    pub fn descendants(&self, parent: Uuid) -> Vec<usize> {
+        let mut seen = vec![false; self.tasks.len()];
+        let mut found = Vec::new();
+        let mut pending = vec![parent];
+        while let Some(uuid) = pending.pop() {
+            for (index, task) in self.tasks.iter().enumerate() {
+                // a parent cycle in a hand-edited file must not loop forever
+                if task.parent == Some(uuid) && !seen[index] && task.uuid != parent {
+                    seen[index] = true;
+                    found.push(index);
+                    pending.push(task.uuid);
+                }
+            }
+        }
+        found
+    }
+
+    /// Indices of the tasks above the one at `index`, nearest first.
+    pub fn ancestors(&self, index: usize) -> Vec<usize> {
+        let mut found = Vec::new();
+        let mut current = index;
+        while let Some(parent) = self.tasks[current]
+            .parent
+            .and_then(|uuid| self.position_by_uuid(uuid))
+        {
+            if parent == index || found.contains(&parent) {
+                break;
+            }
+            found.push(parent);
+            current = parent;
+        }
+        found
+    }
+
+    /// The open tasks that the one at `index` depends on.
+    pub fn blockers(&self, index: usize) -> Vec<&Task> {
+        self.tasks[index]
+            .depends
+            .iter()
+            .filter_map(|uuid| self.position_by_uuid(*uuid))
+            .map(|i| &self.tasks[i])
+            .filter(|t| !t.is_closed())
+            .collect()
+    }
+
+    /// Whether `from` depends on `to`, directly or through other tasks.
+    pub fn depends_on(&self, from: Uuid, to: Uuid) -> bool {
+        let mut seen = HashSet::new();
+        let mut pending = vec![from];
+        while let Some(uuid) = pending.pop() {
+            if !seen.insert(uuid) {
+                continue;
+            }
+            let Some(index) = self.position_by_uuid(uuid) else {
+                continue;
+            };
+            for dependency in &self.tasks[index].depends {
+                if *dependency == to {
+                    return true;
+                }
+                pending.push(*dependency);
+            }
+        }
+        false
+    }
+
+    /// Every task with its depth, each followed by its subtasks. Tasks whose
+    /// parent is missing, or that sit in a parent cycle, are shown at the top.
+    pub fn tree(&self) -> Vec<(usize, &Task)> {
+        let mut shown = vec![false; self.tasks.len()];
+        let mut lines = Vec::with_capacity(self.tasks.len());
+        let roots = self.tasks.iter().enumerate().filter(|(_, t)| {
+            t.parent
+                .is_none_or(|parent| self.position_by_uuid(parent).is_none())
+        });
+        for (index, _) in roots {
+            self.walk(index, 0, &mut shown, &mut lines);
+        }
+        for index in 0..self.tasks.len() {
+            self.walk(index, 0, &mut shown, &mut lines);
+        }
+        lines
+    }
+
+    fn walk<'a>(
+        &'a self,
+        index: usize,
+        depth: usize,
+        shown: &mut [bool],
+        lines: &mut Vec<(usize, &'a Task)>,
+    ) {
+        if shown[index] {
+            return;
+        }
+        shown[index] = true;
+        let task = &self.tasks[index];
+        lines.push((depth, task));
+        for (child, t) in self.tasks.iter().enumerate() {
+            if t.parent == Some(task.uuid) {
+                self.walk(child, depth + 1, shown, lines);
+            }
+        }
+    }
+
+    /// Finds the list index for a task reference typed by the user.
+    ///
+    /// A short number is always a task id. Anything else must be a prefix of
+    /// exactly one task's uuid; all-digit prefixes need at least
+    /// `MIN_NUMERIC_UUID_PREFIX` digits so they are never mistaken for ids.
+    pub fn resolve(&self, reference: &str) -> Result<usize> {
+        let reference = reference.trim();
+        if reference.is_empty() {
+            return Err(TaskError::Validation("No task id given.".to_string()));
+        }
+        if reference.len() < MIN_NUMERIC_UUID_PREFIX
+            && reference.bytes().all(|b| b.is_ascii_digit())
+        {
+            return reference
+                .parse::<u32>()
+                .ok()
+                .and_then(|id| self.position_by_id(id))
+                .ok_or_else(|| TaskError::NotFound(format!("No task with id {}.", reference)));
+        }
+        let prefix = reference.to_lowercase();
+        let mut matches = self
+            .tasks
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| t.uuid.to_string().starts_with(&prefix));
+        match (matches.next(), matches.next()) {
+            (Some((index, _)), None) => Ok(index),
+            (Some(_), Some(_)) => Err(TaskError::Conflict(format!(
+                "'{}' matches more than one task; use a longer uuid prefix.",
+                reference
+            ))),
+            (None, _) => Err(TaskError::NotFound(format!(
+                "No task with id '{}'.",
+                reference
+            ))),
+        }
+    }
+
+    /// Case-insensitive substring match on the description.
+    pub fn search(&self, keyword: &str) -> Vec<&Task> {
+        let keyword = keyword.to_lowercase();
+        self.tasks
+            .iter()
+            .filter(|t| t.description.to_lowercase().contains(&keyword))
+            .collect()
+    }
+
+    /// Open tasks: pending, in progress or waiting on someone.
+    pub fn pending(&self) -> impl Iterator<Item = &Task> {
+        self.tasks.iter().filter(|t| !t.is_closed())
+    }
+
+    /// Closed tasks: done or cancelled.
+    pub fn completed(&self) -> impl Iterator<Item = &Task> {
+        self.tasks.iter().filter(|t| t.is_closed())
+    }
+
+    /// Most important (1) first; tasks without a priority go last.
+    pub fn sort_by_priority(&mut self) {
+        self.sort_by_spec(&SortKey::Priority.into());
+    }
+
+    /// Pending, in progress, waiting, done, then cancelled.
+    pub fn sort_by_status(&mut self) {
+        self.sort_by_spec(&SortKey::Status.into());
+    }
+
+    fn sort_by_spec(&mut self, spec: &SortSpec) {
+        let scores = spec.scores(self, &Urgency::default());
+        self.sort_by(|a, b| spec.compare(a, b, &scores));
+    }
+
+    /// Stable sort; only marks the list dirty if the order actually changed.
+    pub fn sort_by<F>(&mut self, mut compare: F)
+    where
+        F: FnMut(&Task, &Task) -> Ordering,
+    {
+        if self
+            .tasks
+            .is_sorted_by(|a, b| compare(a, b) != Ordering::Greater)
+        {
+            return;
+        }
+        self.tasks.sort_by(compare);
+        self.dirty = true;
+    }
+
+    pub fn into_tasks(self) -> Vec<Task> {
+        self.tasks
+    }
+}
+
+impl TryFrom<Vec<Task>> for TaskList {
+    type Error = TaskError;
+
+    fn try_from(tasks: Vec<Task>) -> Result<Self> {
+        Self::with_next_id(tasks, 1)
+    }
+}
+
+/// The id after `id`; `u32::MAX` has none, so it cannot be used.
+fn after(id: u32) -> Result<u32> {
+    id.checked_add(1).ok_or_else(|| {
+        TaskError::Validation(format!(
+            "Task id {} is out of range (the largest is {}).",
+            id,
+            u32::MAX - 1
+        ))
+    })
+}
+
+impl<'a> IntoIterator for &'a TaskList {
+    type Item = &'a Task;
+    type IntoIter = std::slice::Iter<'a, Task>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.tasks.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Priority;
+
+    #[test]
+    fn test_toggle_and_remove() {
+        let mut list = TaskList::new();
+        list.add(Task::new("First")).unwrap();
+        list.add(Task::new("Second")).unwrap();
+        assert_eq!(list.toggle(0), Some(true));
+        assert_eq!(list.completed().count(), 1);
+        assert_eq!(list.remove(0).unwrap().description, "First");
+        assert_eq!(list.len(), 1);
+        assert!(list.remove(5).is_none());
+    }
+
+    #[test]
+    fn test_ids_survive_removal_and_sorting() {
+        let mut list = TaskList::new();
+        let a = list.add(Task::new("a")).unwrap();
+        let b = list.add(Task::new("b")).unwrap();
+        list.remove(list.resolve(&a.to_string()).unwrap());
+        let c = list.add(Task::new("c")).unwrap();
+        assert_eq!((a, b, c), (1, 2, 3));
+        list.sort_by(|x, y| y.id.cmp(&x.id));
+        assert_eq!(
+            list.get(list.resolve("2").unwrap()).unwrap().description,
+            "b"
+        );
+    }
+
+    #[test]
+    fn test_resolve_by_uuid_prefix() {
+        let mut list = TaskList::new();
+        list.add(Task::new("a")).unwrap();
+        list.add(Task::new("b")).unwrap();
+        let uuid = list.get(1).unwrap().uuid.to_string();
+        assert_eq!(list.resolve(&uuid[..8]).unwrap(), 1);
+        assert!(matches!(list.resolve("zzz"), Err(TaskError::NotFound(_))));
+        assert!(matches!(
+            list.add(Task::new("  ")),
+            Err(TaskError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_with_next_id_fixes_duplicates() {
+        let mut first = Task::new("first");
+        first.id = 4;
+        let mut second = first.clone();
+        second.description = "second".to_string();
+        let list = TaskList::with_next_id(vec![first, second], 2).unwrap();
+        assert_eq!(list.get(0).unwrap().id, 4);
+        assert_eq!(list.get(1).unwrap().id, 5);
+        assert_ne!(list.get(0).unwrap().uuid, list.get(1).unwrap().uuid);
+        assert_eq!(list.next_id(), 6);
+    }
+
+    #[test]
+    fn test_ids_stop_before_u32_max() {
+        let mut last = Task::new("last");
+        last.id = u32::MAX;
+        assert!(matches!(
+            TaskList::with_next_id(vec![last.clone()], 1),
+            Err(TaskError::Validation(_))
+        ));
+        let mut list = TaskList::new();
+        assert!(list.insert(0, last.clone()).is_err());
+        last.id = u32::MAX - 1;
+        let mut list = TaskList::with_next_id(vec![last], 1).unwrap();
+        assert_eq!(list.next_id(), u32::MAX);
+        let error = list.add(Task::new("one more")).unwrap_err();
+        assert!(error.to_string().contains("4294967295"), "{}", error);
+        assert_eq!(list.len(), 1);
+    }
+
+    #[test]
+    fn test_dirty_tracking() {
+        let mut list = TaskList::new();
+        assert!(!list.is_dirty());
+        list.add(Task::new("b")).unwrap();
+        list.add(Task::new("a")).unwrap();
+        assert!(list.is_dirty());
+        list.mark_saved();
+        list.sort_by_status();
+        assert!(!list.is_dirty(), "already in order");
+        list.sort_by(|x, y| x.description.cmp(&y.description));
+        assert!(list.is_dirty());
+        list.mark_saved();
+        list.toggle(0);
+        assert!(list.is_dirty());
+        list.mark_saved();
+        assert!(list.remove(5).is_none());
+        assert!(!list.is_dirty());
+    }
+
+    #[test]
+    fn test_sort_by_priority_puts_unset_last() {
+        let mut list = TaskList::new();
+        list.add(Task::new("none")).unwrap();
+        let mut high = Task::new("high");
+        high.priority = Priority::Critical;
+        list.add(high).unwrap();
+        list.sort_by_priority();
+        assert_eq!(list.get(0).unwrap().description, "high");
+    }
+}
@@ -0,0 +1,186 @@
+//! Advisory locks and change detection for task files shared between processes.
+
+use crate::error::{Result, TaskError};
+use std::collections::hash_map::DefaultHasher;
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+/// How long to wait for another process to release a task file.
+pub const LOCK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The lock is taken on this companion file because saves replace the task
+/// file itself. It is never deleted; removing it would let two processes
+/// hold "the" lock on different files.
+pub fn lock_path(filename: &str) -> String {
+    format!("{}.lock", filename)
+}
+
+/// A held lock on a task file, released on drop.
+#[derive(Debug)]
+pub struct FileLock {
+    file: File,
+}
+
+impl FileLock {
+    /// Lock for reading; other readers may hold it at the same time.
+    pub fn shared(filename: &str) -> Result<Self> {
+        Self::acquire(filename, false)
+    }
+
+    /// Lock for writing; waits until every other holder is gone.
+    pub fn exclusive(filename: &str) -> Result<Self> {
+        Self::acquire(filename, true)
+    }
+
+    fn acquire(filename: &str, exclusive: bool) -> Result<Self> {
+        let path = lock_path(filename);
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)
+            .map_err(|e| TaskError::io(&path, e))?;
+        let started = Instant::now();
+        loop {
+            let attempt = if exclusive {
+                file.try_lock()
+            } else {
+                file.try_lock_shared()
+            };
+            match attempt {
+                Ok(()) => return Ok(Self { file }),
+                Err(TryLockError::WouldBlock) if started.elapsed() < LOCK_TIMEOUT => {
+                    thread::sleep(Duration::from_millis(50));
+                }
+                Err(TryLockError::WouldBlock) => {
+                    return Err(TaskError::Conflict(format!(
+                        "{} is locked by another process.",
+                        filename
+                    )));
+                }
+                Err(TryLockError::Error(e)) => return Err(TaskError::io(&path, e)),
+            }
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
+
+/// What a task file looked like when it was read, so a later save can tell
+/// whether someone else has written it since.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileStamp {
+    /// `None` when the file did not exist.
+    seen: Option<Seen>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Seen {
+    modified: SystemTime,
+    len: u64,
+    hash: u64,
+}
+
+impl FileStamp {
+    /// The stamp of a file that does not exist.
+    pub fn missing() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn new(modified: SystemTime, contents: &[u8]) -> Self {
+        Self {
+            seen: Some(Seen {
+                modified,
+                len: contents.len() as u64,
+                hash: hash(contents),
+            }),
+        }
+    }
+
+    /// Stamps `filename` as it is now.
+    pub fn read(filename: &str) -> Result<Self> {
+        let read = || -> io::Result<Self> {
+            let modified = fs::metadata(filename)?.modified()?;
+            Ok(Self::new(modified, &fs::read(filename)?))
+        };
+        match read() {
+            Ok(stamp) => Ok(stamp),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::missing()),
+            Err(e) => Err(TaskError::io(filename, e)),
+        }
+    }
+
+    /// Whether `filename` still holds what this stamp saw. An unchanged
+    /// mtime and size is trusted; otherwise the contents are hashed, so a
+    /// file that was merely touched still matches.
+    pub fn matches(&self, filename: &str) -> Result<bool> {
+        let metadata = match fs::metadata(filename) {
+            Ok(metadata) => metadata,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(self.seen.is_none()),
+            Err(e) => return Err(TaskError::io(filename, e)),
+        };
+        let Some(seen) = &self.seen else {
+            return Ok(false);
+        };
+        let modified = metadata
+            .modified()
+            .map_err(|e| TaskError::io(filename, e))?;
+        if modified == seen.modified && metadata.len() == seen.len {
+            return Ok(true);
+        }
+        let contents = fs::read(filename).map_err(|e| TaskError::io(filename, e))?;
+        Ok(hash(&contents) == seen.hash)
+    }
+}
+
+fn hash(contents: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exclusive_lock_excludes_others() {
+        let filename = "test_lock_tasks.txt";
+        let held = FileLock::exclusive(filename).unwrap();
+        let path = lock_path(filename);
+        // a second handle in the same process contends like another process would
+        let other = OpenOptions::new().write(true).open(&path).unwrap();
+        assert!(matches!(other.try_lock(), Err(TryLockError::WouldBlock)));
+        drop(held);
+        assert!(other.try_lock().is_ok());
+        drop(other);
+        let shared = FileLock::shared(filename).unwrap();
+        let _second = FileLock::shared(filename).unwrap();
+        drop(shared);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_stamp_notices_other_writes() {
+        let filename = "test_stamp_tasks.txt";
+        assert!(FileStamp::missing().matches(filename).unwrap());
+        std::fs::write(filename, "one").unwrap();
+        let stamp = FileStamp::read(filename).unwrap();
+        assert!(stamp.matches(filename).unwrap());
+        assert!(!FileStamp::missing().matches(filename).unwrap());
+        std::fs::write(filename, "two, longer").unwrap();
+        assert!(!stamp.matches(filename).unwrap());
+        // same contents with a new mtime still match
+        std::fs::write(filename, "one").unwrap();
+        assert!(stamp.matches(filename).unwrap());
+        std::fs::remove_file(filename).unwrap();
+    }
+}
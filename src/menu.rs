@@ -0,0 +1,997 @@
+//! The numbered interactive menu that `task` opens when run without arguments.
+
+use crate::due::Due;
+use crate::history::{Change, DEFAULT_HISTORY_LIMIT, History, Op};
+use crate::lock::FileStamp;
+use crate::merge::merge;
+use crate::ops::{self, CompletionRules, DateField, NewTask};
+use crate::priority::{Priority, PriorityScale};
+use crate::query::Query;
+use crate::report::{self, Report};
+use crate::sort::{SortKey, SortSpec};
+use crate::storage::{
+    DEFAULT_BACKUPS, LoadMode, export_json, import_json, load_tasks_with, save_tasks_then,
+};
+use crate::task::Status;
+use crate::ui::Ui;
+use crate::urgency::Urgency;
+use crate::{Result, Task, TaskError, TaskList};
+use chrono::{Local, NaiveDate};
+
+pub struct Menu<'a> {
+    ui: &'a mut dyn Ui,
+    task_list: TaskList,
+    history: History,
+    file: String,
+    json_file: String,
+    load_mode: LoadMode,
+    backups: usize,
+    autosave: bool,
+    complete_parents: bool,
+    reports: Vec<Report>,
+    urgency: Urgency,
+    priority_scale: PriorityScale,
+    /// What `file` held when last loaded or saved, and the matching list.
+    disk: FileStamp,
+    base: TaskList,
+    /// The operations that turned `base` into the list, for the journal;
+    /// `None` when they are not known (e.g. after a merge).
+    unsaved: Option<Vec<Op>>,
+    /// What was done to `history` since it was last stored, replayed on
+    /// the stored copy when the tasks are saved.
+    history_changes: Vec<Change>,
+}
+
+impl<'a> Menu<'a> {
+    /// `file` backs Save/Load (text) and `json_file` backs Export/Import.
+    pub fn new(ui: &'a mut dyn Ui, task_list: TaskList, file: &str, json_file: &str) -> Self {
+        let unsaved = (!task_list.is_dirty()).then(Vec::new);
+        Self {
+            ui,
+            task_list,
+            history: History::new(DEFAULT_HISTORY_LIMIT),
+            file: file.to_string(),
+            json_file: json_file.to_string(),
+            load_mode: LoadMode::Strict,
+            backups: DEFAULT_BACKUPS,
+            autosave: false,
+            complete_parents: false,
+            reports: report::builtin(),
+            urgency: Urgency::default(),
+            priority_scale: PriorityScale::default(),
+            disk: FileStamp::missing(),
+            base: TaskList::new(),
+            unsaved,
+            history_changes: Vec::new(),
+        }
+    }
+
+    /// Records that the current list is what `file` held at `stamp`, so a
+    /// save can notice changes made by other processes since.
+    pub fn set_loaded(&mut self, stamp: FileStamp) {
+        self.disk = stamp;
+        self.base = self.task_list.clone();
+        self.unsaved = Some(Vec::new());
+    }
+
+    /// Undo history to continue from, e.g. one loaded from an earlier
+    /// session. It is stored next to `file` whenever the tasks are saved.
+    pub fn set_history(&mut self, history: History) {
+        self.history = history;
+        self.history_changes.clear();
+    }
+
+    /// How "Load tasks" treats malformed lines (strict by default).
+    pub fn set_load_mode(&mut self, load_mode: LoadMode) {
+        self.load_mode = load_mode;
+    }
+
+    /// How many backups "Save tasks" keeps.
+    pub fn set_backups(&mut self, backups: usize) {
+        self.backups = backups;
+    }
+
+    /// Save after every command that changed the list (off by default).
+    pub fn set_autosave(&mut self, autosave: bool) {
+        self.autosave = autosave;
+    }
+
+    /// Complete a parent once its last open subtask is done (off by default).
+    pub fn set_complete_parents(&mut self, complete_parents: bool) {
+        self.complete_parents = complete_parents;
+    }
+
+    /// The reports item 24 offers (the built-in ones by default).
+    pub fn set_reports(&mut self, reports: Vec<Report>) {
+        self.reports = reports;
+    }
+
+    /// How the tree view, item 18 and reports score urgency.
+    pub fn set_urgency(&mut self, urgency: Urgency) {
+        self.urgency = urgency;
+    }
+
+    /// What a number typed as a priority stands for (1-5 by default).
+    pub fn set_priority_scale(&mut self, scale: PriorityScale) {
+        self.priority_scale = scale;
+    }
+
+    pub fn task_list(&self) -> &TaskList {
+        &self.task_list
+    }
+
+    /// Shows the menu until the user exits or input runs out.
+    pub fn run(&mut self) {
+        loop {
+            self.ui.print("\n--- To Do List ---");
+            for item in [
+                "1. Add task",
+                "2. Remove task",
+                "3. View tasks",
+                "4. Edit task",
+                "5. Mark task as complete/incomplete",
+                "6. Search tasks",
+                "7. Save tasks (text)",
+                "8. Load tasks (text)",
+                "9. Export tasks (JSON)",
+                "10. Import tasks (JSON)",
+                "11. Undo last action",
+                "12. Sort tasks",
+                "13. Exit",
+                "14. Redo last undone action",
+                "15. Show history",
+                "16. Add subtask",
+                "17. Move task under another",
+                "18. Show next actionable tasks",
+                "19. Add/remove a dependency",
+                "20. Set recurrence",
+                "21. View all tasks, including waiting ones",
+                "22. Set due, scheduled, wait or until date",
+                "23. Start, stop, cancel or otherwise change a task's status",
+                "24. Run a saved report",
+                "25. View tasks in another order (the list keeps its order)",
+                "26. Explain a task's urgency",
+            ] {
+                self.ui.print(item);
+            }
+            let Some(choice) = self.ui.read_line("Enter your choice: ") else {
+                if self.task_list.is_dirty() {
+                    self.ui
+                        .print("Input ended; unsaved changes were discarded.");
+                }
+                self.ui.print("Exiting...");
+                break;
+            };
+
+            match choice.as_str() {
+                "1" => self.add_task(None),
+                "2" => self.remove_task(),
+                "3" => self.view_tasks(),
+                "4" => self.edit_task(),
+                "5" => self.mark_task(),
+                "6" => self.search_tasks(),
+                "7" => self.save_tasks(),
+                "8" => {
+                    self.load_tasks();
+                }
+                "9" => match export_json(&self.json_file, self.task_list.tasks()) {
+                    Ok(()) => self.ui.print("Tasks exported to JSON."),
+                    Err(error) => self.ui.print(&format!("Failed to export tasks: {}", error)),
+                },
+                "10" => {
+                    let source = format!("import from {}", self.json_file);
+                    let imported = import_json(&self.json_file)
+                        .and_then(|tasks| ops::import_tasks(&mut self.task_list, tasks, &source));
+                    match imported {
+                        Ok(op) => {
+                            self.record(op);
+                            self.ui.print("Tasks imported from JSON.");
+                        }
+                        Err(error) => self.ui.print(&format!("Failed to import tasks: {}", error)),
+                    }
+                }
+                "11" => self.undo(),
+                "12" => self.sort_tasks(),
+                "14" => self.redo(),
+                "15" => self.show_history(),
+                "16" => self.add_subtask(),
+                "17" => self.move_task(),
+                "18" => self.show_next(),
+                "19" => self.toggle_dependency(),
+                "20" => self.set_recurrence(),
+                "21" => self.view_all_tasks(),
+                "22" => self.set_date(),
+                "23" => self.change_status(),
+                "24" => self.run_report(),
+                "25" => self.view_sorted(),
+                "26" => self.explain_urgency(),
+                "13" => {
+                    if self.confirm_exit() {
+                        self.ui.print("Exiting...");
+                        break;
+                    }
+                    continue;
+                }
+                _ => self.ui.print("Invalid choice. Please try again."),
+            }
+            if self.autosave && self.task_list.is_dirty() {
+                self.save_tasks();
+            }
+        }
+    }
+
+    /// Offers to save unsaved changes; false means stay in the menu.
+    fn confirm_exit(&mut self) -> bool {
+        if !self.task_list.is_dirty() {
+            return true;
+        }
+        let choice = self
+            .ui
+            .choose("You have unsaved changes", &["Save", "Discard", "Cancel"]);
+        match choice {
+            Some(0) => {
+                self.save_tasks();
+                !self.task_list.is_dirty()
+            }
+            Some(1) => true,
+            _ => false,
+        }
+    }
+
+    fn save_tasks(&mut self) {
+        match self.write_tasks(true) {
+            Ok(()) => {}
+            Err(TaskError::ChangedOnDisk(_)) => self.resolve_changed_file(),
+            Err(error) => self.ui.print(&format!("Failed to save tasks: {}", error)),
+        }
+    }
+
+    /// Writes the list, and while `file` is still locked brings the stored
+    /// undo history up to date. `checked` refuses to overwrite changes
+    /// other processes saved since the list was loaded.
+    fn write_tasks(&mut self, checked: bool) -> Result<()> {
+        let disk = checked.then_some(&self.disk);
+        let changes = if checked {
+            self.unsaved.as_deref()
+        } else {
+            None
+        };
+        let mut synced = None;
+        let mut sync = || {
+            synced = Some(History::load(&self.file, DEFAULT_HISTORY_LIMIT).and_then(
+                |mut history| {
+                    history.replay(&self.history_changes);
+                    history.save(&self.file).map(|()| history)
+                },
+            ));
+            Ok(())
+        };
+        let stamp = save_tasks_then(
+            &self.file,
+            &self.task_list,
+            self.backups,
+            disk,
+            changes,
+            &mut sync,
+        )?;
+        self.task_list.mark_saved();
+        self.set_loaded(stamp);
+        self.ui.print("Tasks saved to file.");
+        match synced {
+            Some(Ok(history)) => {
+                self.history = history;
+                self.history_changes.clear();
+            }
+            Some(Err(error)) => self
+                .ui
+                .print(&format!("Failed to save the undo history: {}", error)),
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// Another process saved `file` after we loaded it; ask before clobbering.
+    fn resolve_changed_file(&mut self) {
+        self.ui.print(&format!(
+            "{} was changed by another process since it was loaded.",
+            self.file
+        ));
+        let choice = self.ui.choose(
+            "Keep which changes",
+            &["Reload (discard mine)", "Merge", "Overwrite theirs"],
+        );
+        match choice {
+            Some(0) => {
+                self.load_tasks();
+            }
+            Some(1) => {
+                let theirs = match load_tasks_with(&self.file, self.load_mode) {
+                    Ok(report) => report,
+                    Err(error) => {
+                        self.ui.print(&format!("Failed to load tasks: {}", error));
+                        return;
+                    }
+                };
+                let merged = merge(&self.base, &self.task_list, &theirs.tasks);
+                let source = format!("merge changes to {}", self.file);
+                let op = ops::replace_tasks(&mut self.task_list, merged.tasks, &source);
+                self.record(op);
+                self.disk = theirs.stamp;
+                self.base = theirs.tasks;
+                self.unsaved = None;
+                self.ui.print(&format!(
+                    "Merged; {} task(s) were changed on both sides.",
+                    merged.conflicts
+                ));
+                self.save_tasks();
+            }
+            Some(2) => {
+                if let Err(error) = self.write_tasks(false) {
+                    self.ui.print(&format!("Failed to save tasks: {}", error));
+                }
+            }
+            _ => self.ui.print("Save cancelled."),
+        }
+    }
+
+    /// Replaces the list with the file's contents; false if that failed.
+    fn load_tasks(&mut self) -> bool {
+        match load_tasks_with(&self.file, self.load_mode) {
+            Ok(report) => {
+                let source = format!("load {}", self.file);
+                let clean = !report.tasks.is_dirty();
+                let op = ops::replace_tasks(&mut self.task_list, report.tasks, &source);
+                self.record(op);
+                if clean {
+                    self.task_list.mark_saved();
+                }
+                self.set_loaded(report.stamp);
+                if !clean {
+                    self.unsaved = None;
+                }
+                self.ui.print("Tasks loaded from file.");
+                for rejected in &report.rejected {
+                    self.ui.print(&format!("Moved malformed {}", rejected));
+                }
+                true
+            }
+            Err(error) => {
+                self.ui.print(&format!("Failed to load tasks: {}", error));
+                false
+            }
+        }
+    }
+
+    fn add_task(&mut self, parent: Option<String>) {
+        self.ui
+            .print("Enter a description for the new task (end with a single '.' on a new line):");
+        let description = self.ui.read_multiline();
+        if description.is_empty() {
+            self.ui.print("Description cannot be empty.");
+            return;
+        }
+
+        let prompt = format!(
+            "Enter priority (critical, high, medium, low or {}, optional): ",
+            self.priority_scale.range()
+        );
+        let input = self.ui.ask(&prompt);
+        let priority = match ops::parse_priority(&input, &self.priority_scale) {
+            Ok(priority) => priority,
+            Err(error) => {
+                self.ui.print(&format!("{} Leaving it unset.", error));
+                Priority::None
+            }
+        };
+        let due = self.ask_due(DateField::Due);
+
+        let new = NewTask {
+            description,
+            priority,
+            due_date: due.map(|due| due.date),
+            due_at: due.and_then(|due| due.at()),
+            parent,
+            ..NewTask::default()
+        };
+        match ops::add_task(&mut self.task_list, new) {
+            Ok(op) => {
+                if let Op::Add { task, .. } = op.primary() {
+                    self.ui.print(&format!("Task {} added.", task.id));
+                }
+                self.record(op);
+            }
+            Err(error) => self.ui.print(&error.to_string()),
+        }
+    }
+
+    fn add_subtask(&mut self) {
+        if self.task_list.is_empty() {
+            self.ui.print("No tasks found.");
+            return;
+        }
+        self.view_tasks();
+        let parent = self
+            .ui
+            .ask("Enter the id of the task to add a subtask to: ");
+        if let Err(error) = self.task_list.resolve(&parent) {
+            self.ui.print(&error.to_string());
+            return;
+        }
+        self.add_task(Some(parent));
+    }
+
+    fn move_task(&mut self) {
+        if self.task_list.is_empty() {
+            self.ui.print("No tasks found.");
+            return;
+        }
+        self.view_tasks();
+        let reference = self.ui.ask("Enter the task id to move: ");
+        let parent = self
+            .ui
+            .ask("Enter the id of its new parent (leave empty for the top level): ");
+        let parent = Some(parent.trim()).filter(|p| !p.is_empty());
+        match ops::move_task(&mut self.task_list, &reference, parent) {
+            Ok(op) => {
+                self.record(op);
+                self.ui.print("Task moved.");
+            }
+            Err(error) => self.ui.print(&error.to_string()),
+        }
+    }
+
+    /// Asks until the date is understood, or left empty. Anything but a
+    /// plain `YYYY-MM-DD` date is echoed back to be confirmed.
+    fn ask_due(&mut self, field: DateField) -> Option<Due> {
+        let prompt = format!(
+            "Enter {} date (e.g. 2026-11-01, fri, next monday 9am, in 3 days, eom; optional): ",
+            field.name()
+        );
+        loop {
+            let input = self.ui.ask(&prompt);
+            let due = match ops::parse_due_date(&input) {
+                Ok(due) => due?,
+                Err(error) => {
+                    self.ui.print(&error.to_string());
+                    continue;
+                }
+            };
+            if NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d").is_ok()
+                || self.ui.confirm(&format!(
+                    "The {} date will be {}. Is that right? (y/n): ",
+                    field.name(),
+                    due
+                ))
+            {
+                return Some(due);
+            }
+        }
+    }
+
+    fn remove_task(&mut self) {
+        if self.task_list.is_empty() {
+            self.ui.print("No tasks found.");
+            return;
+        }
+        self.view_tasks();
+        let reference = self.ui.ask("Enter the task id to remove: ");
+        if let Err(error) = self.task_list.resolve(&reference) {
+            self.ui.print(&error.to_string());
+            return;
+        }
+        let index = self.task_list.resolve(&reference).unwrap();
+        let subtasks = self
+            .task_list
+            .descendants(self.task_list.get(index).unwrap().uuid)
+            .len();
+        let question = if subtasks > 0 {
+            format!(
+                "Are you sure you want to delete this task and its {} subtask(s)? (y/n): ",
+                subtasks
+            )
+        } else {
+            "Are you sure you want to delete this task? (y/n): ".to_string()
+        };
+        if self.ui.confirm(&question) {
+            match ops::remove_task(&mut self.task_list, &reference) {
+                Ok(op) => {
+                    self.record(op);
+                    self.ui.print("Task removed.");
+                }
+                Err(error) => self.ui.print(&error.to_string()),
+            }
+        } else {
+            self.ui.print("Cancelled.");
+        }
+    }
+
+    fn edit_task(&mut self) {
+        if self.task_list.is_empty() {
+            self.ui.print("No tasks found.");
+            return;
+        }
+        self.view_tasks();
+        let reference = self.ui.ask("Enter the task id to edit: ");
+        if let Err(error) = self.task_list.resolve(&reference) {
+            self.ui.print(&error.to_string());
+            return;
+        }
+        self.ui
+            .print("Enter the updated task description (end with a single '.' on a new line):");
+        let description = self.ui.read_multiline();
+        match ops::edit_task(&mut self.task_list, &reference, &description) {
+            Ok(op) => {
+                self.record(op);
+                self.ui.print("Task updated.");
+            }
+            Err(error) => self.ui.print(&error.to_string()),
+        }
+    }
+
+    fn mark_task(&mut self) {
+        if self.task_list.is_empty() {
+            self.ui.print("No tasks found.");
+            return;
+        }
+        self.view_tasks();
+        let reference = self
+            .ui
+            .ask("Enter the task id to toggle complete/incomplete: ");
+        let index = match self.task_list.resolve(&reference) {
+            Ok(index) => index,
+            Err(error) => {
+                self.ui.print(&error.to_string());
+                return;
+            }
+        };
+        let mut rules = CompletionRules {
+            complete_parents: self.complete_parents,
+            ignore_blockers: false,
+        };
+        let mut marked = ops::mark_task(&mut self.task_list, &reference, rules);
+        if let Err(TaskError::Conflict(message)) = &marked
+            && self.is_blocked(index)
+        {
+            self.ui.print(message);
+            if !self.ui.confirm("Complete it anyway? (y/n): ") {
+                self.ui.print("Cancelled.");
+                return;
+            }
+            rules.ignore_blockers = true;
+            marked = ops::mark_task(&mut self.task_list, &reference, rules);
+        }
+        match marked {
+            Ok(op) => self.record_status_change(op),
+            Err(error) => self.ui.print(&error.to_string()),
+        }
+    }
+
+    fn run_report(&mut self) {
+        let names: Vec<String> = self
+            .reports
+            .iter()
+            .map(|report| match report.description.as_str() {
+                "" => report.name.clone(),
+                description => format!("{} ({})", report.name, description),
+            })
+            .collect();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let Some(choice) = self.ui.choose("Report", &names) else {
+            self.ui.print("Invalid choice.");
+            return;
+        };
+        let report = &self.reports[choice];
+        let shown = report.select(&self.task_list, &Query::All, &self.urgency);
+        let lines = report.render(&shown, &self.task_list, &self.urgency);
+        if lines.is_empty() {
+            self.ui.print("No tasks found.");
+        }
+        for line in lines {
+            self.ui.print(&line);
+        }
+    }
+
+    fn explain_urgency(&mut self) {
+        if self.task_list.is_empty() {
+            self.ui.print("No tasks found.");
+            return;
+        }
+        self.view_tasks();
+        let reference = self.ui.ask("Enter the id of the task: ");
+        let index = match self.task_list.resolve(&reference) {
+            Ok(index) => index,
+            Err(error) => {
+                self.ui.print(&error.to_string());
+                return;
+            }
+        };
+        let today = Local::now().date_naive();
+        for line in self.urgency.explain(&self.task_list, index, today) {
+            self.ui.print(&line);
+        }
+    }
+
+    fn change_status(&mut self) {
+        if self.task_list.is_empty() {
+            self.ui.print("No tasks found.");
+            return;
+        }
+        self.view_tasks();
+        let reference = self.ui.ask("Enter the task id: ");
+        let index = match self.task_list.resolve(&reference) {
+            Ok(index) => index,
+            Err(error) => {
+                self.ui.print(&error.to_string());
+                return;
+            }
+        };
+        let names: Vec<&str> = Status::ALL.iter().map(|s| s.name()).collect();
+        let Some(choice) = self.ui.choose("New status", &names) else {
+            self.ui.print("Invalid choice.");
+            return;
+        };
+        let status = Status::ALL[choice];
+        let mut rules = CompletionRules {
+            complete_parents: self.complete_parents,
+            ignore_blockers: false,
+        };
+        let mut changed = ops::set_status(&mut self.task_list, &reference, status, rules);
+        if let Err(TaskError::Conflict(message)) = &changed
+            && self.is_blocked(index)
+        {
+            self.ui.print(message);
+            if !self.ui.confirm("Complete it anyway? (y/n): ") {
+                self.ui.print("Cancelled.");
+                return;
+            }
+            rules.ignore_blockers = true;
+            changed = ops::set_status(&mut self.task_list, &reference, status, rules);
+        }
+        match changed {
+            Ok(op) => self.record_status_change(op),
+            Err(error) => self.ui.print(&error.to_string()),
+        }
+    }
+
+    /// Whether the task waits on open prerequisites, the one conflict that
+    /// completing it anyway can get past.
+    fn is_blocked(&self, index: usize) -> bool {
+        !self.task_list.blockers(index).is_empty()
+    }
+
+    /// Reports a status change, with the tasks it carried along, and records it.
+    fn record_status_change(&mut self, op: Op) {
+        if let Op::Edit { after, .. } = op.primary() {
+            self.ui.print(ops::status_message(after.status));
+        }
+        let spawned = ops::spawned_occurrence(&op);
+        let related = match &op {
+            Op::Batch { ops } => ops.len() - 1 - usize::from(spawned.is_some()),
+            _ => 0,
+        };
+        if related > 0 {
+            self.ui
+                .print(&format!("{} related task(s) changed too.", related));
+        }
+        if let Some(next) = spawned {
+            self.ui.print(&format!(
+                "Next occurrence: task {}, due {}.",
+                next.id,
+                next.due_date.unwrap()
+            ));
+        }
+        self.record(op);
+    }
+
+    fn set_date(&mut self) {
+        if self.task_list.is_empty() {
+            self.ui.print("No tasks found.");
+            return;
+        }
+        self.view_all_tasks();
+        let reference = self.ui.ask("Enter the task id: ");
+        if let Err(error) = self.task_list.resolve(&reference) {
+            self.ui.print(&error.to_string());
+            return;
+        }
+        let names: Vec<&str> = DateField::ALL.iter().map(|f| f.name()).collect();
+        let Some(choice) = self.ui.choose("Which date", &names) else {
+            self.ui.print("Invalid choice.");
+            return;
+        };
+        let field = DateField::ALL[choice];
+        self.ui.print("Leave the date empty to clear it.");
+        let date = self.ask_due(field);
+        match ops::set_date(&mut self.task_list, &reference, field, date) {
+            Ok(op) => {
+                self.record(op);
+                match date {
+                    Some(date) => {
+                        self.ui
+                            .print(&format!("The {} date is now {}.", field.name(), date))
+                    }
+                    None => self
+                        .ui
+                        .print(&format!("The {} date is cleared.", field.name())),
+                }
+            }
+            Err(error) => self.ui.print(&error.to_string()),
+        }
+    }
+
+    fn set_recurrence(&mut self) {
+        if self.task_list.is_empty() {
+            self.ui.print("No tasks found.");
+            return;
+        }
+        self.view_tasks();
+        let reference = self.ui.ask("Enter the task id to repeat: ");
+        if let Err(error) = self.task_list.resolve(&reference) {
+            self.ui.print(&error.to_string());
+            return;
+        }
+        let rule = self.ui.ask(
+            "Enter how often it repeats (e.g. daily, every 2 weeks on mon,thu, \
+             monthly on the last fri; empty to stop): ",
+        );
+        let changed = ops::parse_recurrence(&rule)
+            .and_then(|recur| ops::set_recurrence(&mut self.task_list, &reference, recur));
+        match changed {
+            Ok(op) => {
+                if let Op::Edit { after, .. } = &op {
+                    match &after.recur {
+                        Some(recur) => self.ui.print(&format!(
+                            "Task repeats {}, next due {}.",
+                            recur,
+                            after.due_date.unwrap()
+                        )),
+                        None => self.ui.print("Task no longer repeats."),
+                    }
+                }
+                self.record(op);
+            }
+            Err(error) => self.ui.print(&error.to_string()),
+        }
+    }
+
+    fn show_next(&mut self) {
+        let next: Vec<String> = ops::next_actionable(&self.task_list, &self.urgency)
+            .into_iter()
+            .map(|task| task.render_in(&self.task_list))
+            .collect();
+        if next.is_empty() {
+            self.ui.print("Nothing to do right now.");
+        }
+        for line in next {
+            self.ui.print(&line);
+        }
+    }
+
+    /// Adds the dependency, or removes it if it already exists.
+    fn toggle_dependency(&mut self) {
+        if self.task_list.len() < 2 {
+            self.ui.print("Dependencies need at least two tasks.");
+            return;
+        }
+        self.view_tasks();
+        let reference = self.ui.ask("Enter the id of the task that has to wait: ");
+        let prerequisite = self.ui.ask("Enter the id of the task it waits for: ");
+        let existing = match (
+            self.task_list.resolve(&reference),
+            self.task_list.resolve(&prerequisite),
+        ) {
+            (Ok(index), Ok(required)) => {
+                let uuid = self.task_list.get(required).unwrap().uuid;
+                self.task_list.get(index).unwrap().depends.contains(&uuid)
+            }
+            (Err(error), _) | (_, Err(error)) => {
+                self.ui.print(&error.to_string());
+                return;
+            }
+        };
+        let changed = if existing {
+            ops::remove_dependency(&mut self.task_list, &reference, &prerequisite)
+        } else {
+            ops::add_dependency(&mut self.task_list, &reference, &prerequisite)
+        };
+        match changed {
+            Ok(op) => {
+                self.record(op);
+                self.ui.print(if existing {
+                    "Dependency removed."
+                } else {
+                    "Dependency added."
+                });
+            }
+            Err(error) => self.ui.print(&error.to_string()),
+        }
+    }
+
+    fn search_tasks(&mut self) {
+        let keyword = self.ui.ask(
+            "Enter words or a query to search (e.g. +tag project:name status:pending \
+             priority<=high due.before:eow, combined with and/or/not): ",
+        );
+        let found: Vec<String> =
+            match ops::search_tasks(&self.task_list, &keyword, &self.priority_scale) {
+                Ok(found) => found
+                    .into_iter()
+                    .map(|task| task.render_in(&self.task_list))
+                    .collect(),
+                Err(error) => {
+                    self.ui.print(&error.to_string());
+                    return;
+                }
+            };
+        if found.is_empty() {
+            self.ui.print(&format!(
+                "No tasks found matching '{}'.",
+                keyword.to_lowercase()
+            ));
+        }
+        for line in found {
+            self.ui.print(&line);
+        }
+    }
+
+    /// Shows the tasks as a tree, leaving out waiting and expired ones.
+    fn view_tasks(&mut self) {
+        if self.task_list.is_empty() {
+            self.ui.print("No tasks found.");
+            return;
+        }
+        self.ui.print("\n--- Task List ---");
+        for line in ops::tree_lines(&self.task_list, false, Some(&self.urgency)) {
+            self.ui.print(&line);
+        }
+        let hidden = ops::hidden_count(&self.task_list);
+        if hidden > 0 {
+            self.ui.print(&format!(
+                "({} waiting or expired task(s) not shown; choose 21 to see them.)",
+                hidden
+            ));
+        }
+    }
+
+    fn view_all_tasks(&mut self) {
+        if self.task_list.is_empty() {
+            self.ui.print("No tasks found.");
+            return;
+        }
+        self.ui.print("\n--- All Tasks ---");
+        for line in ops::tree_lines(&self.task_list, true, Some(&self.urgency)) {
+            self.ui.print(&line);
+        }
+    }
+
+    fn undo(&mut self) {
+        if self
+            .ui
+            .confirm("Are you sure you want to undo the last action? (y/n): ")
+        {
+            match self.history.undo(&mut self.task_list) {
+                Ok(Some(step)) => {
+                    let message = format!("Undo successful. Reverted: {}.", step.op);
+                    let inverse = step.op.inverse();
+                    self.history_changes.push(Change::Undo(step.clone()));
+                    self.task_list.mark_modified();
+                    if let Some(unsaved) = &mut self.unsaved {
+                        unsaved.push(inverse);
+                    }
+                    self.ui.print(&message);
+                }
+                Ok(None) => self.ui.print("Nothing to undo."),
+                Err(error) => self.ui.print(&format!("Cannot undo: {}", error)),
+            }
+        } else {
+            self.ui.print("Undo cancelled.");
+        }
+    }
+
+    fn sort_tasks(&mut self) {
+        let (spec, name) = match self.ui.choose(
+            "Sort by",
+            &[
+                "Priority",
+                "Status",
+                "Due Date",
+                "Project",
+                "Tags",
+                "Created",
+                "Modified",
+                "Completed",
+                "Urgency",
+                "Several keys",
+            ],
+        ) {
+            Some(9) => match self.ask_sort_spec() {
+                Some(spec) => {
+                    let name = spec.to_string();
+                    (spec, name)
+                }
+                None => return,
+            },
+            Some(choice) => {
+                let key = SortKey::ALL[choice];
+                (SortSpec::from(key), key.name().to_string())
+            }
+            None => {
+                self.ui.print("Invalid choice.");
+                return;
+            }
+        };
+        let op = ops::sort_tasks(&mut self.task_list, &spec, &self.urgency);
+        self.record(op);
+        self.ui.print(&format!("Tasks sorted by {}.", name));
+    }
+
+    /// Shows every task in the order asked for, leaving the list as it is.
+    fn view_sorted(&mut self) {
+        let Some(spec) = self.ask_sort_spec() else {
+            return;
+        };
+        let mut tasks: Vec<&Task> = self.task_list.iter().collect();
+        spec.sort(&mut tasks, &self.task_list, &self.urgency);
+        let lines: Vec<String> = tasks
+            .into_iter()
+            .map(|task| task.render_in(&self.task_list))
+            .collect();
+        if lines.is_empty() {
+            self.ui.print("No tasks found.");
+        }
+        for line in lines {
+            self.ui.print(&line);
+        }
+    }
+
+    fn ask_sort_spec(&mut self) -> Option<SortSpec> {
+        let input = self.ui.ask(
+            "Enter the keys in order, each + (ascending) or - (descending), \
+             e.g. due+,priority-,created+ (add :none-first to put tasks without \
+             the value first): ",
+        );
+        match SortSpec::parse(&input) {
+            Ok(spec) => Some(spec),
+            Err(error) => {
+                self.ui.print(&format!("Invalid sort: {}.", error));
+                None
+            }
+        }
+    }
+
+    /// Remembers an operation just applied to the list.
+    fn record(&mut self, op: Op) {
+        if let Some(unsaved) = &mut self.unsaved {
+            unsaved.push(op.clone());
+        }
+        self.history_changes.push(Change::Record(op.clone()));
+        self.history.record(op);
+    }
+
+    fn redo(&mut self) {
+        match self.history.redo(&mut self.task_list) {
+            Ok(Some(step)) => {
+                let message = format!("Redo successful. Reapplied: {}.", step.op);
+                let op = step.op.clone();
+                self.history_changes.push(Change::Redo(step.clone()));
+                self.task_list.mark_modified();
+                if let Some(unsaved) = &mut self.unsaved {
+                    unsaved.push(op);
+                }
+                self.ui.print(&message);
+            }
+            Ok(None) => self.ui.print("Nothing to redo."),
+            Err(error) => self.ui.print(&format!("Cannot redo: {}", error)),
+        }
+    }
+
+    fn show_history(&mut self) {
+        let listing = self.history.listing();
+        if listing.is_empty() {
+            self.ui.print("No history yet.");
+        }
+        for line in listing {
+            self.ui.print(&line);
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! Three-way merge of task lists, for saving over a file someone else changed.
+
+use crate::list::TaskList;
+use crate::task::Task;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+#[derive(Debug)]
+pub struct Merged {
+    pub tasks: TaskList,
+    /// Tasks changed on both sides, or changed on one and removed on the
+    /// other. An edit beats a removal; when both edited, ours is kept.
+    pub conflicts: usize,
+}
+
+/// Combines `ours` and `theirs`, both descended from `base`, matching tasks
+/// by uuid. Their order is kept and our new tasks go at the end; a new task
+/// whose id they already used gets a fresh one.
+pub fn merge(base: &TaskList, ours: &TaskList, theirs: &TaskList) -> Merged {
+    let by_uuid = |list: &TaskList| -> HashMap<Uuid, Task> {
+        list.iter().map(|t| (t.uuid, t.clone())).collect()
+    };
+    let base_tasks = by_uuid(base);
+    let our_tasks = by_uuid(ours);
+    let their_tasks = by_uuid(theirs);
+    let mut tasks = Vec::new();
+    let mut conflicts = 0;
+
+    for their in theirs {
+        match (base_tasks.get(&their.uuid), our_tasks.get(&their.uuid)) {
+            (Some(original), Some(our)) if our == original => tasks.push(their.clone()),
+            (Some(original), Some(our)) => {
+                if their != original {
+                    conflicts += 1;
+                }
+                tasks.push(our.clone());
+            }
+            // we removed it; keep it only if they changed it meanwhile
+            (Some(original), None) => {
+                if their != original {
+                    conflicts += 1;
+                    tasks.push(their.clone());
+                }
+            }
+            (None, Some(our)) => tasks.push(our.clone()),
+            (None, None) => tasks.push(their.clone()),
+        }
+    }
+    for our in ours {
+        if their_tasks.contains_key(&our.uuid) {
+            continue;
+        }
+        match base_tasks.get(&our.uuid) {
+            Some(original) if our == original => {}
+            Some(_) => {
+                conflicts += 1;
+                tasks.push(our.clone());
+            }
+            None => tasks.push(our.clone()),
+        }
+    }
+
+    let next_id = ours.next_id().max(theirs.next_id());
+    Merged {
+        // every id is below the next id of the list it came from
+        tasks: TaskList::with_next_id(tasks, next_id).expect("merged ids are in range"),
+        conflicts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Priority;
+    use crate::task::Status;
+
+    #[test]
+    fn test_merge_keeps_both_sides() {
+        let mut base = TaskList::new();
+        for name in ["keep", "we edit", "they edit", "we remove", "both edit"] {
+            base.add(Task::new(name)).unwrap();
+        }
+        let mut ours = base.clone();
+        let mut theirs = base.clone();
+
+        ours.get_mut(1).unwrap().status = Status::Done;
+        theirs.get_mut(2).unwrap().priority = Priority::Critical;
+        ours.remove(3);
+        ours.get_mut(3).unwrap().description = "both edit (ours)".to_string();
+        theirs.get_mut(4).unwrap().description = "both edit (theirs)".to_string();
+        let our_new = ours.add(Task::new("our new")).unwrap();
+        let their_new = theirs.add(Task::new("their new")).unwrap();
+        assert_eq!(our_new, their_new);
+
+        let merged = merge(&base, &ours, &theirs);
+        let descriptions: Vec<&str> = merged
+            .tasks
+            .iter()
+            .map(|t| t.description.as_str())
+            .collect();
+        assert_eq!(
+            descriptions,
+            [
+                "keep",
+                "we edit",
+                "they edit",
+                "both edit (ours)",
+                "their new",
+                "our new"
+            ]
+        );
+        assert!(merged.tasks.get(1).unwrap().is_done());
+        assert_eq!(merged.tasks.get(2).unwrap().priority, Priority::Critical);
+        assert_eq!(merged.conflicts, 1);
+        assert_eq!(merged.tasks.get(4).unwrap().id, their_new);
+        assert_eq!(merged.tasks.get(5).unwrap().id, 7);
+    }
+}
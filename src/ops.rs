@@ -0,0 +1,1293 @@
+//! Pure task operations shared by the menu and the command line.
+//!
+//! Nothing here prompts or prints; tasks are addressed by the references
+//! `TaskList::resolve` accepts (id or uuid prefix).
+
+use crate::due::{self, Due};
+use crate::error::{Result, TaskError};
+use crate::history::{Op, Snapshot};
+use crate::priority::{Priority, PriorityScale};
+use crate::query::Query;
+use crate::recur::Recurrence;
+use crate::report;
+use crate::sort::{SortKey, SortSpec};
+use crate::task::{Status, is_valid_name, is_valid_project};
+use crate::urgency::Urgency;
+use crate::{Task, TaskList};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, TimeZone};
+use std::cmp::Ordering;
+use uuid::Uuid;
+
+/// Everything needed to create a task; the list assigns the id.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NewTask {
+    pub description: String,
+    pub title: Option<String>,
+    pub priority: Priority,
+    pub due_date: Option<NaiveDate>,
+    /// The exact moment on `due_date`, if it has a time.
+    pub due_at: Option<DateTime<FixedOffset>>,
+    pub scheduled: Option<NaiveDate>,
+    pub wait: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+    /// Reference of the task to add this one under.
+    pub parent: Option<String>,
+    /// References of the tasks that must be completed first.
+    pub depends: Vec<String>,
+    pub recur: Option<Recurrence>,
+}
+
+/// How completing a task treats the tasks around it.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CompletionRules {
+    /// Complete a parent once its last open subtask is done.
+    pub complete_parents: bool,
+    /// Complete a task even though tasks it depends on are still open.
+    pub ignore_blockers: bool,
+}
+
+impl NewTask {
+    pub fn new(description: impl Into<String>) -> Self {
+        Self {
+            description: description.into(),
+            ..Self::default()
+        }
+    }
+}
+
+/// The dates a task can carry besides its due date.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DateField {
+    Due,
+    Scheduled,
+    Wait,
+    Until,
+}
+
+impl DateField {
+    pub const ALL: [DateField; 4] = [
+        DateField::Due,
+        DateField::Scheduled,
+        DateField::Wait,
+        DateField::Until,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DateField::Due => "due",
+            DateField::Scheduled => "scheduled",
+            DateField::Wait => "wait",
+            DateField::Until => "until",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<DateField> {
+        Self::ALL
+            .into_iter()
+            .find(|field| field.name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// A description with its `+tag`, `-tag` and `project:<path>` words taken out.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Markup {
+    pub text: String,
+    pub tags: Vec<String>,
+    /// `-tag` words, recognised only when editing.
+    pub removed_tags: Vec<String>,
+    /// `Some(None)` for a bare `project:`, which clears the project.
+    pub project: Option<Option<String>>,
+}
+
+impl Markup {
+    /// Whitespace inside each line is collapsed; line breaks are kept.
+    /// Words that merely look similar (`+1`, `project:a..b`) stay text.
+    pub fn parse(input: &str, removals: bool) -> Self {
+        let mut markup = Markup::default();
+        let lines: Vec<String> = input
+            .lines()
+            .map(|line| {
+                let words: Vec<&str> = line
+                    .split_whitespace()
+                    .filter(|word| !markup.take(word, removals))
+                    .collect();
+                words.join(" ")
+            })
+            .collect();
+        markup.text = lines.join("\n").trim().to_string();
+        markup
+    }
+
+    /// Records `word` if it is markup.
+    fn take(&mut self, word: &str, removals: bool) -> bool {
+        if let Some(tag) = word.strip_prefix('+')
+            && is_valid_name(tag)
+        {
+            self.tags.push(tag.to_lowercase());
+        } else if let Some(tag) = word.strip_prefix('-')
+            && removals
+            && is_valid_name(tag)
+        {
+            self.removed_tags.push(tag.to_lowercase());
+        } else if let Some(project) = word.strip_prefix("project:")
+            && (project.is_empty() || is_valid_project(project))
+        {
+            self.project = Some(Some(project.to_lowercase()).filter(|p| !p.is_empty()));
+        } else {
+            return false;
+        }
+        true
+    }
+
+    fn apply(&self, task: &mut Task) {
+        for tag in &self.tags {
+            task.add_tag(tag);
+        }
+        for tag in &self.removed_tags {
+            task.remove_tag(tag);
+        }
+        if let Some(project) = &self.project {
+            task.project = project.clone();
+        }
+    }
+}
+
+/// Which tasks a listing or bulk change covers.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TaskFilter {
+    pub query: Query,
+    /// Also include waiting and expired tasks and the subtasks under them.
+    pub hidden: bool,
+}
+
+impl TaskFilter {
+    pub fn matches(&self, task: &Task, task_list: &TaskList) -> bool {
+        (self.hidden || !is_hidden_within(task, task_list, Local::now().date_naive()))
+            && self.query.matches(task, task_list)
+    }
+}
+
+/// Whether the task, or a task above it, is waiting or expired.
+fn is_hidden_within(task: &Task, task_list: &TaskList, today: NaiveDate) -> bool {
+    task.is_hidden(today)
+        || task_list.position_by_uuid(task.uuid).is_some_and(|index| {
+            task_list
+                .ancestors(index)
+                .into_iter()
+                .any(|above| task_list.get(above).unwrap().is_hidden(today))
+        })
+}
+
+/// Each mutating operation returns the `Op` it performed, for the caller to
+/// record in its `History`. Tags and a project written in the description
+/// are moved to their own fields.
+pub fn add_task(task_list: &mut TaskList, new: NewTask) -> Result<Op> {
+    let markup = Markup::parse(&new.description, false);
+    let mut task = Task::new(markup.text.as_str());
+    markup.apply(&mut task);
+    task.title = new.title;
+    task.priority = new.priority;
+    task.due_date = new.due_date;
+    task.due_at = new.due_at;
+    task.scheduled = new.scheduled;
+    task.wait = new.wait;
+    task.until = new.until;
+    if let Some(recur) = new.recur {
+        set_recur(&mut task, Some(recur));
+    }
+    if let Some(parent) = &new.parent {
+        let parent = task_list.resolve(parent)?;
+        task.parent = Some(task_list.get(parent).unwrap().uuid);
+    }
+    for dependency in &new.depends {
+        let dependency = task_list.resolve(dependency)?;
+        let uuid = task_list.get(dependency).unwrap().uuid;
+        if !task.depends.contains(&uuid) {
+            task.depends.push(uuid);
+        }
+    }
+    let id = task_list.add(task)?;
+    let index = task_list.position_by_id(id).unwrap();
+    let mut ops = vec![Op::Add {
+        index,
+        task: task_list.get(index).unwrap().clone(),
+    }];
+    reopen_ancestors(task_list, index, &mut ops);
+    Ok(Op::batch(ops))
+}
+
+/// Removes the task together with all of its subtasks.
+pub fn remove_task(task_list: &mut TaskList, reference: &str) -> Result<Op> {
+    let index = task_list.resolve(reference)?;
+    let uuid = task_list.get(index).unwrap().uuid;
+    let mut doomed: Vec<Uuid> = vec![uuid];
+    doomed.extend(
+        task_list
+            .descendants(uuid)
+            .into_iter()
+            .map(|i| task_list.get(i).unwrap().uuid),
+    );
+    let ops = doomed
+        .into_iter()
+        .map(|uuid| {
+            let index = task_list.position_by_uuid(uuid).unwrap();
+            let task = task_list.remove(index).unwrap();
+            Op::Remove { index, task }
+        })
+        .collect();
+    Ok(Op::batch(ops))
+}
+
+/// The task that follows a just completed instance of a recurring series,
+/// due on the next occurrence that is not already past. None if the task
+/// does not recur or the series already has a pending instance.
+fn next_occurrence(task_list: &TaskList, index: usize) -> Option<Task> {
+    let task = task_list.get(index).unwrap();
+    let recur = task.recur.as_ref()?;
+    let series = task.series.unwrap_or(task.uuid);
+    if task_list
+        .pending()
+        .any(|t| t.series == Some(series) || t.uuid == series)
+    {
+        return None;
+    }
+    let today = Local::now().date_naive();
+    let mut due = recur.next_after(task.due_date.unwrap_or(today));
+    while due < today {
+        due = recur.next_after(due);
+    }
+    let mut next = Task::new(task.description.as_str());
+    next.title = task.title.clone();
+    next.priority = task.priority;
+    next.due_date = Some(due);
+    next.due_at = task.due_at.and_then(|at| {
+        at.offset()
+            .from_local_datetime(&due.and_time(at.time()))
+            .single()
+    });
+    next.tags = task.tags.clone();
+    next.project = task.project.clone();
+    next.parent = task.parent;
+    next.recur = Some(recur.clone());
+    next.series = Some(series);
+    Some(next)
+}
+
+/// The next occurrence that completing a recurring task added, if any.
+pub fn spawned_occurrence(op: &Op) -> Option<&Task> {
+    match op {
+        Op::Batch { ops } => ops.iter().skip(1).find_map(|op| match op {
+            Op::Add { task, .. } => Some(task),
+            _ => None,
+        }),
+        _ => None,
+    }
+}
+
+/// Sets or clears the recurrence, giving a recurring task without a due
+/// date its first occurrence from today.
+fn set_recur(task: &mut Task, recur: Option<Recurrence>) {
+    task.recur = recur.map(|recur| {
+        let due = *task.due_date.get_or_insert_with(|| {
+            let yesterday = Local::now().date_naive() - chrono::Days::new(1);
+            recur.next_after(yesterday)
+        });
+        recur.anchored(due)
+    });
+}
+
+/// Sets or clears one of the task's dates; only the due date can have a
+/// time.
+pub fn set_date(
+    task_list: &mut TaskList,
+    reference: &str,
+    field: DateField,
+    date: Option<Due>,
+) -> Result<Op> {
+    let index = task_list.resolve(reference)?;
+    if field != DateField::Due && date.is_some_and(|date| date.time.is_some()) {
+        return Err(TaskError::Validation(format!(
+            "The {} date cannot have a time.",
+            field.name()
+        )));
+    }
+    let task = task_list.get_mut(index).unwrap();
+    let before = task.clone();
+    let day = date.map(|date| date.date);
+    match field {
+        DateField::Due => task.set_due(date),
+        DateField::Scheduled => task.scheduled = day,
+        DateField::Wait => task.wait = day,
+        DateField::Until => task.until = day,
+    }
+    Ok(edited(before, task))
+}
+
+pub fn set_recurrence(
+    task_list: &mut TaskList,
+    reference: &str,
+    recur: Option<Recurrence>,
+) -> Result<Op> {
+    let index = task_list.resolve(reference)?;
+    let task = task_list.get_mut(index).unwrap();
+    let before = task.clone();
+    set_recur(task, recur);
+    Ok(edited(before, task))
+}
+
+/// Parses a recurrence rule; empty input or `none` means none.
+pub fn parse_recurrence(input: &str) -> Result<Option<Recurrence>> {
+    let input = input.trim();
+    if input.is_empty() || input.eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+    input
+        .parse()
+        .map(Some)
+        .map_err(|e| TaskError::Validation(format!("Invalid recurrence: {}.", e)))
+}
+
+/// Puts the task under `parent`, or at the top level for `None`.
+pub fn move_task(task_list: &mut TaskList, reference: &str, parent: Option<&str>) -> Result<Op> {
+    let index = task_list.resolve(reference)?;
+    let task = task_list.get(index).unwrap();
+    let parent = match parent {
+        Some(parent) => {
+            let parent = task_list.resolve(parent)?;
+            if parent == index || task_list.ancestors(parent).contains(&index) {
+                return Err(TaskError::Validation(format!(
+                    "Task {} cannot be moved under itself or one of its subtasks.",
+                    task.id
+                )));
+            }
+            Some(task_list.get(parent).unwrap().uuid)
+        }
+        None => None,
+    };
+    let before = task.clone();
+    let task = task_list.get_mut(index).unwrap();
+    task.parent = parent;
+    let mut ops = vec![edited(before, task)];
+    if !task_list.get(index).unwrap().is_closed() {
+        reopen_ancestors(task_list, index, &mut ops);
+    }
+    Ok(Op::batch(ops))
+}
+
+/// Replaces the description. `+tag`, `-tag` and `project:` words change the
+/// tags and project instead; if nothing else is given the description stays.
+pub fn edit_task(task_list: &mut TaskList, reference: &str, description: &str) -> Result<Op> {
+    let markup = Markup::parse(description, true);
+    if markup == Markup::default() {
+        return Err(TaskError::Validation(
+            "Description cannot be empty.".to_string(),
+        ));
+    }
+    let index = task_list.resolve(reference)?;
+    let task = task_list.get_mut(index).unwrap();
+    let before = task.clone();
+    if !markup.text.is_empty() {
+        task.description = markup.text.clone();
+    }
+    markup.apply(task);
+    Ok(edited(before, task))
+}
+
+/// Marks an open task done and a closed one pending; see `set_status`.
+pub fn mark_task(task_list: &mut TaskList, reference: &str, rules: CompletionRules) -> Result<Op> {
+    let index = task_list.resolve(reference)?;
+    let status = if task_list.get(index).unwrap().is_closed() {
+        Status::Pending
+    } else {
+        Status::Done
+    };
+    change_status(task_list, index, status, rules)
+}
+
+/// Marks the task done, or pending again; see `set_status`.
+pub fn set_completed(
+    task_list: &mut TaskList,
+    reference: &str,
+    completed: bool,
+    rules: CompletionRules,
+) -> Result<Op> {
+    let status = if completed {
+        Status::Done
+    } else {
+        Status::Pending
+    };
+    set_status(task_list, reference, status, rules)
+}
+
+/// Moves the task to `status`. A closed task never has open subtasks:
+/// closing one (done or cancelled) closes its open subtasks the same way,
+/// and reopening one reopens the closed tasks above it. A task whose
+/// dependencies are still open cannot be done unless the rules say otherwise.
+pub fn set_status(
+    task_list: &mut TaskList,
+    reference: &str,
+    status: Status,
+    rules: CompletionRules,
+) -> Result<Op> {
+    let index = task_list.resolve(reference)?;
+    change_status(task_list, index, status, rules)
+}
+
+/// Puts an in-progress task back to pending.
+pub fn stop_task(task_list: &mut TaskList, reference: &str) -> Result<Op> {
+    let index = task_list.resolve(reference)?;
+    let task = task_list.get(index).unwrap();
+    if task.status != Status::InProgress {
+        return Err(TaskError::Validation(format!(
+            "Task {} is not in progress.",
+            task.id
+        )));
+    }
+    change_status(
+        task_list,
+        index,
+        Status::Pending,
+        CompletionRules::default(),
+    )
+}
+
+/// What changing a task to `status` reports, e.g. "Task started."
+pub fn status_message(status: Status) -> &'static str {
+    match status {
+        Status::Pending => "Task marked as pending.",
+        Status::InProgress => "Task started.",
+        Status::Waiting => "Task marked as waiting on someone else.",
+        Status::Done => "Task marked as completed.",
+        Status::Cancelled => "Task cancelled.",
+    }
+}
+
+fn change_status(
+    task_list: &mut TaskList,
+    index: usize,
+    status: Status,
+    rules: CompletionRules,
+) -> Result<Op> {
+    let task = task_list.get(index).unwrap();
+    if task.status == status {
+        return Err(TaskError::Validation(format!(
+            "Task {} is already {}.",
+            task.id, status
+        )));
+    }
+    let blockers: Vec<String> = task_list
+        .blockers(index)
+        .iter()
+        .map(|t| t.id.to_string())
+        .collect();
+    if status == Status::Done && !rules.ignore_blockers && !blockers.is_empty() {
+        return Err(TaskError::Conflict(format!(
+            "Task {} is blocked by task(s) {}.",
+            task_list.get(index).unwrap().id,
+            blockers.join(", ")
+        )));
+    }
+    // a cancelled occurrence is skipped, so the series goes on either way
+    let spawns = status.is_closed() && !task_list.get(index).unwrap().is_closed();
+    let mut ops = vec![status_edit(task_list, index, status)];
+    if !status.is_closed() {
+        reopen_ancestors(task_list, index, &mut ops);
+        return Ok(Op::batch(ops));
+    }
+    let uuid = task_list.get(index).unwrap().uuid;
+    for below in task_list.descendants(uuid) {
+        if !task_list.get(below).unwrap().is_closed() {
+            ops.push(status_edit(task_list, below, status));
+        }
+    }
+    if spawns && let Some(next) = next_occurrence(task_list, index) {
+        let id = task_list.add(next)?;
+        let index = task_list.position_by_id(id).unwrap();
+        let task = task_list.get(index).unwrap().clone();
+        ops.push(Op::Add { index, task });
+    }
+    if status == Status::Done && rules.complete_parents {
+        for above in task_list.ancestors(index) {
+            let parent = task_list.get(above).unwrap();
+            if parent.is_closed()
+                || !task_list.children(parent.uuid).all(Task::is_closed)
+                || !task_list.blockers(above).is_empty()
+            {
+                break;
+            }
+            ops.push(status_edit(task_list, above, Status::Done));
+        }
+    }
+    Ok(Op::batch(ops))
+}
+
+/// Makes the task wait for `prerequisite`, refusing edges that would form
+/// a cycle.
+pub fn add_dependency(task_list: &mut TaskList, reference: &str, prerequisite: &str) -> Result<Op> {
+    let index = task_list.resolve(reference)?;
+    let required = task_list.resolve(prerequisite)?;
+    let task = task_list.get(index).unwrap();
+    let other = task_list.get(required).unwrap();
+    if index == required {
+        return Err(TaskError::Validation(
+            "A task cannot depend on itself.".to_string(),
+        ));
+    }
+    if task.depends.contains(&other.uuid) {
+        return Err(TaskError::Validation(format!(
+            "Task {} already depends on task {}.",
+            task.id, other.id
+        )));
+    }
+    if task_list.depends_on(other.uuid, task.uuid) {
+        return Err(TaskError::Validation(format!(
+            "Task {} already depends on task {}, so this would be a cycle.",
+            other.id, task.id
+        )));
+    }
+    let uuid = other.uuid;
+    let before = task.clone();
+    let task = task_list.get_mut(index).unwrap();
+    task.depends.push(uuid);
+    Ok(edited(before, task))
+}
+
+pub fn remove_dependency(
+    task_list: &mut TaskList,
+    reference: &str,
+    prerequisite: &str,
+) -> Result<Op> {
+    let index = task_list.resolve(reference)?;
+    let required = task_list.resolve(prerequisite)?;
+    let uuid = task_list.get(required).unwrap().uuid;
+    let task = task_list.get(index).unwrap();
+    if !task.depends.contains(&uuid) {
+        return Err(TaskError::NotFound(format!(
+            "Task {} does not depend on task {}.",
+            task.id,
+            task_list.get(required).unwrap().id
+        )));
+    }
+    let before = task.clone();
+    let task = task_list.get_mut(index).unwrap();
+    task.depends.retain(|u| *u != uuid);
+    Ok(edited(before, task))
+}
+
+/// Pending and in-progress tasks that can be worked on now (not blocked,
+/// scheduled for later, or waiting or expired themselves or through a
+/// parent), most urgent first; equal scores go by priority, then by due
+/// date. These are what the built-in `next` report shows, without its
+/// limit.
+pub fn next_actionable<'a>(task_list: &'a TaskList, urgency: &Urgency) -> Vec<&'a Task> {
+    let mut next = report::next();
+    next.limit = None;
+    next.select(task_list, &Query::All, urgency)
+}
+
+fn reopen_ancestors(task_list: &mut TaskList, index: usize, ops: &mut Vec<Op>) {
+    for above in task_list.ancestors(index) {
+        if task_list.get(above).unwrap().is_closed() {
+            ops.push(status_edit(task_list, above, Status::Pending));
+        }
+    }
+}
+
+fn status_edit(task_list: &mut TaskList, index: usize, status: Status) -> Op {
+    let task = task_list.get_mut(index).unwrap();
+    let before = task.clone();
+    task.set_status(status, Local::now());
+    edited(before, task)
+}
+
+/// The edit that turned `before` into `task`, which is stamped as modified
+/// unless nothing changed.
+fn edited(before: Task, task: &mut Task) -> Op {
+    if *task != before {
+        task.modified_at = Some(Local::now());
+    }
+    Op::edit(before, task.clone())
+}
+
+/// The list as an indented tree, parents showing how many subtasks are done.
+/// Unless `hidden` is set, waiting and expired tasks are left out together
+/// with their subtasks. With `urgency`, each line starts with the open
+/// task's score and the tasks under each parent (and at the top) are
+/// listed most urgent first, closed ones last; otherwise in stored order.
+pub fn tree_lines(task_list: &TaskList, hidden: bool, urgency: Option<&Urgency>) -> Vec<String> {
+    let today = Local::now().date_naive();
+    let scores = urgency.map(|urgency| urgency.scores(task_list));
+    let mut tree = task_list.tree();
+    if let Some(scores) = &scores {
+        let spec = SortSpec::from(SortKey::Urgency);
+        let mut ordered = Vec::with_capacity(tree.len());
+        order_siblings(&tree, &mut ordered, &|a, b| {
+            a.is_closed()
+                .cmp(&b.is_closed())
+                .then_with(|| spec.compare(a, b, scores))
+        });
+        tree = ordered;
+    }
+    let mut skip_below = None;
+    let mut lines = Vec::new();
+    for (depth, task) in tree {
+        if skip_below.is_some_and(|hidden_depth| depth > hidden_depth) {
+            continue;
+        }
+        skip_below = None;
+        if !hidden && task.is_hidden(today) {
+            skip_below = Some(depth);
+            continue;
+        }
+        let score = match &scores {
+            Some(_) if task.is_closed() => format!("{:5} ", ""),
+            Some(scores) => format!("{:5.1} ", scores[&task.uuid]),
+            None => String::new(),
+        };
+        lines.push(format!(
+            "{}{}{}",
+            score,
+            "    ".repeat(depth),
+            task.render_in(task_list)
+        ));
+    }
+    lines
+}
+
+/// Copies `tree` (tasks each followed by their subtasks, as
+/// `TaskList::tree` gives them) with every set of siblings stably sorted
+/// by `compare`; subtasks stay under their parent.
+fn order_siblings<'a>(
+    tree: &[(usize, &'a Task)],
+    ordered: &mut Vec<(usize, &'a Task)>,
+    compare: &dyn Fn(&Task, &Task) -> Ordering,
+) {
+    let mut branches = Vec::new();
+    let mut start = 0;
+    while start < tree.len() {
+        let depth = tree[start].0;
+        let end = tree[start + 1..]
+            .iter()
+            .position(|(below, _)| *below <= depth)
+            .map_or(tree.len(), |n| start + 1 + n);
+        branches.push(&tree[start..end]);
+        start = end;
+    }
+    branches.sort_by(|a, b| compare(a[0].1, b[0].1));
+    for branch in branches {
+        ordered.push(branch[0]);
+        order_siblings(&branch[1..], ordered, compare);
+    }
+}
+
+/// How many tasks the usual views leave out because they are waiting or
+/// expired.
+pub fn hidden_count(task_list: &TaskList) -> usize {
+    let today = Local::now().date_naive();
+    task_list.iter().filter(|t| t.is_hidden(today)).count()
+}
+
+/// Every task `query` matches, waiting and expired ones included.
+pub fn find_tasks<'a>(task_list: &'a TaskList, query: &Query) -> Vec<&'a Task> {
+    task_list
+        .iter()
+        .filter(|task| query.matches(task, task_list))
+        .collect()
+}
+
+/// Parses `input` as a query, reading priority numbers on `scale`, and
+/// finds the tasks it matches.
+pub fn search_tasks<'a>(
+    task_list: &'a TaskList,
+    input: &str,
+    scale: &PriorityScale,
+) -> Result<Vec<&'a Task>> {
+    let query = Query::parse_on(input, scale)
+        .map_err(|e| TaskError::Validation(format!("Invalid query: {}.", e)))?;
+    Ok(find_tasks(task_list, &query))
+}
+
+/// Applies `change` to every task `select` matches, as one step. `change`
+/// gets a reference to each task that is still there and returns `None` to
+/// leave it be, e.g. because closing its parent already closed it.
+pub fn change_matching(
+    task_list: &mut TaskList,
+    select: &TaskFilter,
+    change: impl FnMut(&mut TaskList, &str) -> Result<Option<Op>>,
+) -> Result<Op> {
+    let matched: Vec<Uuid> = task_list
+        .iter()
+        .filter(|task| select.matches(task, task_list))
+        .map(|task| task.uuid)
+        .collect();
+    if matched.is_empty() {
+        return Err(TaskError::NotFound("No tasks match the query.".to_string()));
+    }
+    change_all(task_list, matched, change)
+}
+
+/// Like `change_matching`, for the tasks `references` name. Nothing is
+/// changed unless every reference resolves.
+pub fn change_each(
+    task_list: &mut TaskList,
+    references: &[String],
+    change: impl FnMut(&mut TaskList, &str) -> Result<Option<Op>>,
+) -> Result<Op> {
+    let mut uuids = Vec::new();
+    for reference in references {
+        let uuid = task_list.get(task_list.resolve(reference)?).unwrap().uuid;
+        if !uuids.contains(&uuid) {
+            uuids.push(uuid);
+        }
+    }
+    change_all(task_list, uuids, change)
+}
+
+fn change_all(
+    task_list: &mut TaskList,
+    uuids: Vec<Uuid>,
+    mut change: impl FnMut(&mut TaskList, &str) -> Result<Option<Op>>,
+) -> Result<Op> {
+    let mut ops = Vec::new();
+    for uuid in uuids {
+        if task_list.position_by_uuid(uuid).is_some()
+            && let Some(op) = change(task_list, &uuid.to_string())?
+        {
+            ops.push(op);
+        }
+    }
+    if ops.is_empty() {
+        return Err(TaskError::Validation(
+            "The tasks need no change.".to_string(),
+        ));
+    }
+    Ok(Op::Batch { ops })
+}
+
+/// Reorders the stored list; tasks that tie on every key keep their order.
+pub fn sort_tasks(task_list: &mut TaskList, spec: &SortSpec, urgency: &Urgency) -> Op {
+    let before = task_list.order();
+    let scores = spec.scores(task_list, urgency);
+    task_list.sort_by(|a, b| spec.compare(a, b, &scores));
+    Op::Sort {
+        key: spec.to_string(),
+        before,
+        after: task_list.order(),
+    }
+}
+
+/// Swaps in tasks read from an export. Tasks that do not say when they were
+/// created or changed are stamped with the time of the import, and closed
+/// ones without a completion time get the time they were last closed.
+pub fn import_tasks(task_list: &mut TaskList, mut tasks: Vec<Task>, source: &str) -> Result<Op> {
+    stamp_imported(&mut tasks);
+    let replacement = TaskList::try_from(tasks)?;
+    Ok(replace_tasks(task_list, replacement, source))
+}
+
+/// Adds tasks read from another file after the ones already listed, with
+/// new ids. They are stamped as `import_tasks` stamps them.
+pub fn append_tasks(task_list: &mut TaskList, mut tasks: Vec<Task>, source: &str) -> Result<Op> {
+    stamp_imported(&mut tasks);
+    let before = Snapshot::from(&*task_list);
+    for task in tasks {
+        task_list.add(task)?;
+    }
+    Ok(Op::Replace {
+        source: source.to_string(),
+        before,
+        after: Snapshot::from(&*task_list),
+    })
+}
+
+fn stamp_imported(tasks: &mut [Task]) {
+    let now = Local::now();
+    for task in tasks {
+        task.created_at.get_or_insert(now);
+        task.modified_at.get_or_insert(now);
+        if task.is_closed() && task.completed_at.is_none() {
+            task.completed_at = Some(task.since(task.status).unwrap_or(now));
+        }
+    }
+}
+
+/// Swaps in a whole new list (import, load, merge). Ids already handed out
+/// by either list stay retired.
+pub fn replace_tasks(task_list: &mut TaskList, replacement: TaskList, source: &str) -> Op {
+    let before = Snapshot::from(&*task_list);
+    let next_id = task_list.next_id().max(replacement.next_id());
+    // the replacement's ids are all below its next id
+    *task_list = TaskList::with_next_id(replacement.into_tasks(), next_id)
+        .expect("a list's ids are in range");
+    Op::Replace {
+        source: source.to_string(),
+        before,
+        after: Snapshot::from(&*task_list),
+    }
+}
+
+/// Parses an optional priority, a level's name or a number on `scale`;
+/// empty input means none.
+pub fn parse_priority(input: &str, scale: &PriorityScale) -> Result<Priority> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(Priority::None);
+    }
+    scale.parse(input).map_err(|_| {
+        TaskError::Validation(format!(
+            "Invalid priority '{}' (expected critical, high, medium, low or {}).",
+            input,
+            scale.range()
+        ))
+    })
+}
+
+/// Parses an optional due date such as `2026-11-01`, `fri 17:00` or
+/// `in 3 days`, resolved against the local clock; empty input means none.
+pub fn parse_due_date(input: &str) -> Result<Option<Due>> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(None);
+    }
+    due::parse(input, Local::now().naive_local())
+        .map(Some)
+        .map_err(TaskError::Validation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_edit_mark_remove() {
+        let mut list = TaskList::new();
+        let mut new = NewTask::new("  Write report ");
+        new.priority = Priority::High;
+        let added = add_task(&mut list, new).unwrap();
+        assert!(matches!(added, Op::Add { index: 0, .. }));
+        let reference = list.get(0).unwrap().id.to_string();
+        assert_eq!(list.get(0).unwrap().description, "Write report");
+        edit_task(&mut list, &reference, "Write final report").unwrap();
+        assert!(edit_task(&mut list, &reference, " ").is_err());
+        assert!(matches!(
+            mark_task(&mut list, &reference, CompletionRules::default()).unwrap(),
+            Op::Edit { after, .. } if after.is_done()
+        ));
+        match remove_task(&mut list, &reference).unwrap() {
+            Op::Remove { task, .. } => assert_eq!(task.description, "Write final report"),
+            other => panic!("expected a removal, got {:?}", other),
+        }
+        assert!(matches!(
+            mark_task(&mut list, &reference, CompletionRules::default()),
+            Err(TaskError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_tags_and_project_from_description() {
+        let mut list = TaskList::new();
+        let new = NewTask::new("Rotate keys +Infra project:Work.Backend +1 now\n+ops");
+        add_task(&mut list, new).unwrap();
+        let task = list.get(0).unwrap();
+        assert_eq!(task.description, "Rotate keys +1 now");
+        assert_eq!(task.tags, ["infra", "ops"]);
+        assert_eq!(task.project.as_deref(), Some("work.backend"));
+        add_task(&mut list, NewTask::new("Fix -v flag project:work")).unwrap();
+        assert_eq!(list.get(1).unwrap().description, "Fix -v flag");
+
+        edit_task(&mut list, "1", "-ops +urgent project:").unwrap();
+        let task = list.get(0).unwrap();
+        assert_eq!(task.description, "Rotate keys +1 now");
+        assert_eq!(task.tags, ["infra", "urgent"]);
+        assert_eq!(task.project, None);
+        assert!(edit_task(&mut list, "1", "  ").is_err());
+
+        assert_eq!(
+            search_tasks(&list, "+infra", &PriorityScale::default())
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            search_tasks(&list, "project:work", &PriorityScale::default())
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            search_tasks(&list, "project:wor", &PriorityScale::default())
+                .unwrap()
+                .len(),
+            0
+        );
+        assert_eq!(
+            search_tasks(&list, "fix project:work", &PriorityScale::default())
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            search_tasks(&list, "keys +infra +ops", &PriorityScale::default())
+                .unwrap()
+                .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_subtask_completion_rules() {
+        let mut list = TaskList::new();
+        add_task(&mut list, NewTask::new("Release")).unwrap();
+        for name in ["Build", "Test"] {
+            let mut new = NewTask::new(name);
+            new.parent = Some("1".to_string());
+            add_task(&mut list, new).unwrap();
+        }
+        let mut new = NewTask::new("Unit tests");
+        new.parent = Some("3".to_string());
+        add_task(&mut list, new).unwrap();
+        assert_eq!(list.progress(list.get(0).unwrap().uuid), Some((0, 2)));
+        let parents = CompletionRules {
+            complete_parents: true,
+            ..CompletionRules::default()
+        };
+
+        set_completed(&mut list, "2", true, parents).unwrap();
+        // the last open subtask of 3 completes it, and then 1
+        let op = set_completed(&mut list, "4", true, parents).unwrap();
+        assert!(matches!(&op, Op::Batch { ops } if ops.len() == 3));
+        assert!(list.iter().all(Task::is_done));
+        // reopening a subtask reopens everything above it
+        set_completed(&mut list, "4", false, parents).unwrap();
+        assert_eq!(list.pending().count(), 3);
+        // completing a parent completes what is below it
+        set_completed(&mut list, "1", true, CompletionRules::default()).unwrap();
+        assert!(list.iter().all(Task::is_done));
+
+        assert!(move_task(&mut list, "1", Some("4")).is_err());
+        move_task(&mut list, "4", None).unwrap();
+        let lines = tree_lines(&list, false, None);
+        assert!(lines[0].contains("Release (2/2)"));
+        assert!(lines[1].starts_with("    ") && lines[1].contains("Build"));
+        assert!(!lines[3].starts_with(' ') && lines[3].contains("Unit tests"));
+
+        let removed = remove_task(&mut list, "1").unwrap();
+        assert_eq!(list.len(), 1);
+        removed.inverse().apply(&mut list).unwrap();
+        assert_eq!(list.len(), 4);
+        assert_eq!(list.get(0).unwrap().description, "Release");
+
+        // with scores, siblings go most urgent first and closed ones last
+        let mut list = TaskList::new();
+        add_task(&mut list, NewTask::new("Done")).unwrap();
+        set_completed(&mut list, "1", true, CompletionRules::default()).unwrap();
+        for (name, priority, parent) in [
+            ("Later", Priority::Low, None),
+            ("Minor", Priority::None, Some("2")),
+            ("Major", Priority::High, Some("2")),
+            ("Soon", Priority::Critical, None),
+        ] {
+            let mut new = NewTask::new(name);
+            new.priority = priority;
+            new.parent = parent.map(str::to_string);
+            add_task(&mut list, new).unwrap();
+        }
+        let lines = tree_lines(&list, false, Some(&Urgency::default()));
+        let order = ["Soon", "Later", "Major", "Minor", "Done"];
+        for (line, name) in lines.iter().zip(order) {
+            assert!(line.contains(name), "{:?}", lines);
+        }
+        assert!(tree_lines(&list, false, None)[0].contains("Done"));
+    }
+
+    #[test]
+    fn test_dependencies_block_completion() {
+        let mut list = TaskList::new();
+        let mut new = NewTask::new("Deploy");
+        new.priority = Priority::Critical;
+        add_task(&mut list, new).unwrap();
+        add_task(&mut list, NewTask::new("Write docs")).unwrap();
+        let mut new = NewTask::new("Build");
+        new.priority = Priority::High;
+        add_task(&mut list, new).unwrap();
+        add_dependency(&mut list, "1", "3").unwrap();
+        assert!(add_dependency(&mut list, "1", "3").is_err());
+        assert!(add_dependency(&mut list, "3", "1").is_err());
+        assert!(add_dependency(&mut list, "2", "2").is_err());
+        assert!(
+            list.get(0)
+                .unwrap()
+                .render_in(&list)
+                .contains("Blocked by: 3")
+        );
+
+        let ids = |list: &TaskList| -> Vec<u32> {
+            next_actionable(list, &Urgency::default())
+                .iter()
+                .map(|t| t.id)
+                .collect()
+        };
+        assert_eq!(ids(&list), vec![3, 2]);
+        let rules = CompletionRules::default();
+        assert!(matches!(
+            set_completed(&mut list, "1", true, rules),
+            Err(TaskError::Conflict(_))
+        ));
+        let force = CompletionRules {
+            ignore_blockers: true,
+            ..rules
+        };
+        set_completed(&mut list, "1", true, force).unwrap();
+        set_completed(&mut list, "1", false, rules).unwrap();
+        set_completed(&mut list, "3", true, rules).unwrap();
+        assert_eq!(ids(&list), vec![1, 2]);
+
+        remove_dependency(&mut list, "1", "3").unwrap();
+        assert!(remove_dependency(&mut list, "1", "3").is_err());
+    }
+
+    #[test]
+    fn test_dependency_edge_cases() {
+        let mut list = TaskList::new();
+        assert!(next_actionable(&list, &Urgency::default()).is_empty());
+        for description in ["Design", "Build", "Ship"] {
+            add_task(&mut list, NewTask::new(description)).unwrap();
+        }
+        add_dependency(&mut list, "3", "2").unwrap();
+        add_dependency(&mut list, "2", "1").unwrap();
+        // a cycle through a task in between is caught too
+        let error = add_dependency(&mut list, "1", "3").unwrap_err();
+        assert!(error.to_string().contains("cycle"), "{}", error);
+        assert!(matches!(
+            add_dependency(&mut list, "3", "9"),
+            Err(TaskError::NotFound(_))
+        ));
+        assert!(matches!(
+            remove_dependency(&mut list, "3", "1"),
+            Err(TaskError::NotFound(_))
+        ));
+
+        // cancelled or removed prerequisites no longer block
+        let rules = CompletionRules::default();
+        set_status(&mut list, "1", Status::Cancelled, rules).unwrap();
+        assert!(list.blockers(1).is_empty());
+        remove_task(&mut list, "2").unwrap();
+        assert!(list.blockers(1).is_empty());
+        set_completed(&mut list, "3", true, rules).unwrap();
+    }
+
+    #[test]
+    fn test_completing_recurring_task_spawns_next() {
+        let mut list = TaskList::new();
+        let mut new = NewTask::new("Water plants +home");
+        new.due_date = NaiveDate::from_ymd_opt(2099, 1, 31);
+        new.recur = parse_recurrence("monthly").unwrap();
+        add_task(&mut list, new).unwrap();
+        assert_eq!(
+            list.get(0).unwrap().recur.as_ref().unwrap().to_string(),
+            "monthly on day 31"
+        );
+
+        let rules = CompletionRules::default();
+        let op = set_completed(&mut list, "1", true, rules).unwrap();
+        let next = spawned_occurrence(&op).unwrap().clone();
+        assert_eq!(next.due_date, NaiveDate::from_ymd_opt(2099, 2, 28));
+        assert_eq!(next.series, Some(list.get(0).unwrap().uuid));
+        assert!(next.has_tag("home"));
+        // reopening and completing again does not add a second one
+        set_completed(&mut list, "1", false, rules).unwrap();
+        set_completed(&mut list, "1", true, rules).unwrap();
+        assert_eq!(list.len(), 2);
+
+        let op = set_completed(&mut list, "2", true, rules).unwrap();
+        let third = spawned_occurrence(&op).unwrap();
+        assert_eq!(third.due_date, NaiveDate::from_ymd_opt(2099, 3, 31));
+        assert_eq!(third.series, next.series);
+        op.inverse().apply(&mut list).unwrap();
+        assert_eq!(list.len(), 2);
+
+        set_recurrence(&mut list, "2", None).unwrap();
+        let op = set_completed(&mut list, "2", true, rules).unwrap();
+        assert!(spawned_occurrence(&op).is_none());
+        assert!(parse_recurrence("now and then").is_err());
+    }
+
+    #[test]
+    fn test_status_lifecycle() {
+        let mut list = TaskList::new();
+        add_task(&mut list, NewTask::new("Migrate")).unwrap();
+        for name in ["Export", "Import"] {
+            let mut new = NewTask::new(name);
+            new.parent = Some("1".to_string());
+            add_task(&mut list, new).unwrap();
+        }
+        add_task(&mut list, NewTask::new("Announce")).unwrap();
+        add_dependency(&mut list, "4", "1").unwrap();
+        let rules = CompletionRules::default();
+
+        assert!(stop_task(&mut list, "2").is_err());
+        set_status(&mut list, "2", Status::InProgress, rules).unwrap();
+        assert!(set_status(&mut list, "2", Status::InProgress, rules).is_err());
+        let task = list.get(1).unwrap();
+        assert!(task.since(Status::InProgress).is_some());
+        assert!(task.render().contains("In progress since"));
+        set_status(&mut list, "2", Status::Done, rules).unwrap();
+        set_status(&mut list, "3", Status::Waiting, rules).unwrap();
+        assert_eq!(list.pending().count(), 3);
+
+        // cancelling closes what is left below and unblocks dependents
+        let op = set_status(&mut list, "1", Status::Cancelled, rules).unwrap();
+        assert!(matches!(&op, Op::Batch { ops } if ops.len() == 2));
+        assert_eq!(list.get(2).unwrap().status, Status::Cancelled);
+        assert_eq!(list.progress(list.get(0).unwrap().uuid), Some((1, 1)));
+        assert_eq!(next_actionable(&list, &Urgency::default())[0].id, 4);
+
+        op.inverse().apply(&mut list).unwrap();
+        assert_eq!(list.get(2).unwrap().status, Status::Waiting);
+        assert_eq!(list.get(2).unwrap().transitions.len(), 1);
+        // reopening a subtask leaves its open parent alone
+        let op = set_status(&mut list, "2", Status::Pending, rules).unwrap();
+        assert_eq!(op.to_string(), "mark task 2 pending");
+        assert_eq!(status_message(Status::Cancelled), "Task cancelled.");
+    }
+
+    #[test]
+    fn test_timestamps_follow_every_change() {
+        let mut list = TaskList::new();
+        add_task(&mut list, NewTask::new("Old")).unwrap();
+        add_task(&mut list, NewTask::new("New")).unwrap();
+        let task = list.get(0).unwrap();
+        assert!(task.created_at.is_some() && task.created_at == task.modified_at);
+        let last_week = Local::now() - chrono::Duration::days(7);
+        let task = list.get_mut(0).unwrap();
+        task.created_at = Some(last_week);
+        task.modified_at = Some(last_week);
+
+        edit_task(&mut list, "1", "+chores").unwrap();
+        let task = list.get(0).unwrap();
+        assert!(task.modified_at > task.created_at);
+        set_completed(&mut list, "2", true, CompletionRules::default()).unwrap();
+        assert!(list.get(1).unwrap().completed_at.is_some());
+
+        let ids = |input: &str| -> Vec<u32> {
+            let query = Query::parse(input).unwrap();
+            find_tasks(&list, &query).iter().map(|t| t.id).collect()
+        };
+        assert_eq!(ids("created.before:yesterday"), [1]);
+        assert_eq!(ids("completed.after:yesterday"), [2]);
+        assert_eq!(ids("modified:today"), [1, 2]);
+
+        sort_tasks(&mut list, &SortKey::Completed.into(), &Urgency::default());
+        assert_eq!(list.get(0).unwrap().id, 2);
+        sort_tasks(&mut list, &SortKey::Created.into(), &Urgency::default());
+        assert_eq!(list.get(0).unwrap().id, 1);
+        set_completed(&mut list, "2", false, CompletionRules::default()).unwrap();
+        assert!(list.get(1).unwrap().completed_at.is_none());
+
+        let mut imported = Task::new("From elsewhere");
+        imported.status = Status::Done;
+        import_tasks(&mut list, vec![imported.clone()], "import").unwrap();
+        let task = list.get(0).unwrap();
+        assert!(task.created_at.is_some() && task.completed_at.is_some());
+        let op = append_tasks(&mut list, vec![imported], "import").unwrap();
+        assert_eq!(list.len(), 2);
+        assert_eq!(list.get(1).unwrap().id, 3);
+        assert!(matches!(op, Op::Replace { before, .. } if before.tasks.len() == 1));
+    }
+
+    #[test]
+    fn test_tasks_without_timestamps() {
+        let mut list = TaskList::new();
+        for name in ["Legacy", "Dated", "Also legacy", "Same time"] {
+            add_task(&mut list, NewTask::new(name)).unwrap();
+        }
+        let created = list.get(1).unwrap().created_at;
+        list.get_mut(3).unwrap().created_at = created;
+        for index in [0, 2] {
+            let task = list.get_mut(index).unwrap();
+            task.created_at = None;
+            task.modified_at = None;
+        }
+        let ids = |list: &TaskList, input: &str| -> Vec<u32> {
+            let query = Query::parse(input).unwrap();
+            find_tasks(list, &query).iter().map(|t| t.id).collect()
+        };
+        assert_eq!(ids(&list, "created:none"), [1, 3]);
+        assert_eq!(ids(&list, "created.before:tomorrow"), [2, 4]);
+        assert_eq!(ids(&list, "completed:any"), Vec::<u32>::new());
+
+        // either way round, tasks without the time go last and ties keep
+        // their order
+        let order = |list: &TaskList| -> Vec<u32> { list.iter().map(|t| t.id).collect() };
+        let newest_first = SortSpec::parse("created-").unwrap();
+        sort_tasks(&mut list, &newest_first, &Urgency::default());
+        assert_eq!(order(&list), [2, 4, 1, 3]);
+        sort_tasks(&mut list, &SortKey::Created.into(), &Urgency::default());
+        assert_eq!(order(&list), [2, 4, 1, 3]);
+    }
+
+    #[test]
+    fn test_waiting_scheduled_and_expired_tasks() {
+        let mut list = TaskList::new();
+        for name in ["Hand over", "Check pager", "Old promo", "Plan Q1"] {
+            add_task(&mut list, NewTask::new(name)).unwrap();
+        }
+        let mut new = NewTask::new("Pager subtask");
+        new.parent = Some("2".to_string());
+        add_task(&mut list, new).unwrap();
+        let date = |input: &str| parse_due_date(input).unwrap();
+        set_date(&mut list, "1", DateField::Due, date("tomorrow 17:00 utc")).unwrap();
+        set_date(&mut list, "2", DateField::Wait, date("in 3 days")).unwrap();
+        set_date(&mut list, "3", DateField::Until, date("yesterday")).unwrap();
+        set_date(&mut list, "4", DateField::Scheduled, date("2w")).unwrap();
+        assert!(set_date(&mut list, "4", DateField::Wait, date("fri 9am")).is_err());
+
+        let due = list.get(0).unwrap().due_at.unwrap();
+        assert_eq!(due.offset().local_minus_utc(), 0);
+        assert!(list.get(0).unwrap().render().contains("in "));
+        let lines = tree_lines(&list, false, None);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(hidden_count(&list), 2);
+        assert_eq!(tree_lines(&list, true, None).len(), 5);
+        let next: Vec<u32> = next_actionable(&list, &Urgency::default())
+            .iter()
+            .map(|t| t.id)
+            .collect();
+        assert_eq!(next, vec![1]);
+        // the next report leaves out the same tasks, the subtask of the
+        // waiting task among them; a status of waiting is left out too
+        let shown = report::next().select(&list, &Query::All, &Urgency::default());
+        assert_eq!(shown, next_actionable(&list, &Urgency::default()));
+        list.get_mut(0).unwrap().status = Status::Waiting;
+        assert!(next_actionable(&list, &Urgency::default()).is_empty());
+        list.get_mut(0).unwrap().status = Status::Pending;
+        assert_eq!(
+            search_tasks(&list, "pager", &PriorityScale::default())
+                .unwrap()
+                .len(),
+            2
+        );
+
+        set_date(&mut list, "2", DateField::Wait, None).unwrap();
+        assert_eq!(tree_lines(&list, false, None).len(), 4);
+    }
+
+    #[test]
+    fn test_parse_priority_and_due_date() {
+        let scale = PriorityScale::default();
+        assert_eq!(parse_priority("", &scale).unwrap(), Priority::None);
+        assert_eq!(parse_priority("3", &scale).unwrap(), Priority::Medium);
+        assert_eq!(parse_priority(" High ", &scale).unwrap(), Priority::High);
+        assert!(parse_priority("6", &scale).is_err());
+        let ten = PriorityScale::preset("0-10").unwrap();
+        assert_eq!(parse_priority("6", &ten).unwrap(), Priority::Medium);
+        assert_eq!(
+            parse_due_date("2026-11-01").unwrap().map(|due| due.date),
+            NaiveDate::from_ymd_opt(2026, 11, 1)
+        );
+        let due = parse_due_date("tomorrow 9am").unwrap().unwrap();
+        assert_eq!(due.date, Local::now().date_naive().succ_opt().unwrap());
+        assert_eq!(due.time, chrono::NaiveTime::from_hms_opt(9, 0, 0));
+        assert!(parse_due_date("next week").is_err());
+    }
+}
@@ -0,0 +1,281 @@
+//! Named priority levels and the numeric scales that map onto them.
+//!
+//! Levels order from `critical` to `none`, so sorting `priority+` puts the
+//! most important tasks first, as sorting the old 1-5 numbers did.
+
+use colored::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Priority {
+    Critical,
+    High,
+    Medium,
+    Low,
+    #[default]
+    None,
+}
+
+impl Priority {
+    pub const ALL: [Priority; 5] = [
+        Priority::Critical,
+        Priority::High,
+        Priority::Medium,
+        Priority::Low,
+        Priority::None,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Priority::Critical => "critical",
+            Priority::High => "high",
+            Priority::Medium => "medium",
+            Priority::Low => "low",
+            Priority::None => "none",
+        }
+    }
+
+    /// Whether no level is set.
+    pub fn is_none(self) -> bool {
+        self == Priority::None
+    }
+
+    /// Accepts a level's name or its first letter, in any case.
+    pub fn from_name(name: &str) -> Option<Priority> {
+        let name = name.trim().to_lowercase();
+        Priority::ALL
+            .into_iter()
+            .find(|level| level.name() == name || level.name()[..1] == name)
+    }
+
+    /// `text` in the level's color: red for critical (in bold) and high,
+    /// yellow for medium, plain otherwise.
+    pub fn paint(self, text: &str) -> ColoredString {
+        match self {
+            Priority::Critical => text.red().bold(),
+            Priority::High => text.red(),
+            Priority::Medium => text.yellow(),
+            Priority::Low | Priority::None => text.normal(),
+        }
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A name, or a number on the standard 1-5 scale.
+impl FromStr for Priority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PriorityScale::default().parse(s)
+    }
+}
+
+/// Written by name, with `null` for none.
+impl Serialize for Priority {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Priority::None => serializer.serialize_none(),
+            level => serializer.serialize_str(level.name()),
+        }
+    }
+}
+
+/// Also reads the 1-5 numbers tasks had before named levels.
+impl<'de> Deserialize<'de> for Priority {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Stored {
+            Number(u32),
+            Name(String),
+        }
+        match Option::<Stored>::deserialize(deserializer)? {
+            None => Ok(Priority::None),
+            Some(Stored::Number(number)) => PriorityScale::default()
+                .level(number)
+                .ok_or_else(|| serde::de::Error::custom(format!("invalid priority {}", number))),
+            Some(Stored::Name(name)) => name.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+/// Which level each number stands for when a priority is typed or imported
+/// as a number.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriorityScale {
+    levels: BTreeMap<u32, Priority>,
+}
+
+/// The 1-5 scale, 1 being the most important; 4 and 5 are both low.
+impl Default for PriorityScale {
+    fn default() -> Self {
+        PriorityScale::of([
+            (1, Priority::Critical),
+            (2, Priority::High),
+            (3, Priority::Medium),
+            (4, Priority::Low),
+            (5, Priority::Low),
+        ])
+    }
+}
+
+impl PriorityScale {
+    /// The built-in scales by name: `1-5`, and `0-10` where higher numbers
+    /// are more important and 0 means none.
+    pub const PRESETS: [&str; 2] = ["1-5", "0-10"];
+
+    pub fn of(levels: impl IntoIterator<Item = (u32, Priority)>) -> Self {
+        Self {
+            levels: levels.into_iter().collect(),
+        }
+    }
+
+    pub fn preset(name: &str) -> Option<PriorityScale> {
+        match name.trim() {
+            "1-5" => Some(PriorityScale::default()),
+            "0-10" => Some(PriorityScale::of((0..=10).map(|number| {
+                let level = match number {
+                    0 => Priority::None,
+                    1..=3 => Priority::Low,
+                    4..=6 => Priority::Medium,
+                    7 | 8 => Priority::High,
+                    _ => Priority::Critical,
+                };
+                (number, level)
+            }))),
+            _ => None,
+        }
+    }
+
+    pub fn level(&self, number: u32) -> Option<Priority> {
+        self.levels.get(&number).copied()
+    }
+
+    /// Makes `number` stand for `level`, adding it to the scale if needed.
+    pub fn set(&mut self, number: u32, level: Priority) {
+        self.levels.insert(number, level);
+    }
+
+    /// The numbers that share their level with a smaller number, each with
+    /// that smaller number: `[(5, 4)]` on the 1-5 scale. Such numbers cannot
+    /// be told apart once read.
+    pub fn shared(&self) -> Vec<(u32, u32)> {
+        let mut first: BTreeMap<Priority, u32> = BTreeMap::new();
+        let mut shared = Vec::new();
+        for (&number, &level) in &self.levels {
+            match first.get(&level) {
+                Some(&earlier) => shared.push((number, earlier)),
+                None => {
+                    first.insert(level, number);
+                }
+            }
+        }
+        shared
+    }
+
+    /// The scale without the numbers `shared` lists.
+    pub fn distinct(&self) -> PriorityScale {
+        let shared: Vec<u32> = self.shared().into_iter().map(|(n, _)| n).collect();
+        PriorityScale::of(
+            self.levels
+                .iter()
+                .filter(|(number, _)| !shared.contains(number))
+                .map(|(&number, &level)| (number, level)),
+        )
+    }
+
+    /// The numbers the scale knows, e.g. `1-5`.
+    pub fn range(&self) -> String {
+        let first = self.levels.keys().next();
+        let last = self.levels.keys().next_back();
+        match (first, last) {
+            (Some(first), Some(last)) if first != last => format!("{}-{}", first, last),
+            (Some(first), _) => first.to_string(),
+            _ => "no numbers".to_string(),
+        }
+    }
+
+    /// Reads a level's name or a number on this scale.
+    pub fn parse(&self, input: &str) -> Result<Priority, String> {
+        let input = input.trim();
+        if let Some(level) = Priority::from_name(input) {
+            return Ok(level);
+        }
+        input
+            .parse()
+            .ok()
+            .and_then(|number| self.level(number))
+            .ok_or_else(|| {
+                format!(
+                    "invalid priority '{}' (expected critical, high, medium, low, none or {})",
+                    input,
+                    self.range()
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels_and_scales() {
+        assert!(Priority::Critical < Priority::Low && Priority::Low < Priority::None);
+        assert_eq!("High".parse(), Ok(Priority::High));
+        assert_eq!("m".parse(), Ok(Priority::Medium));
+        assert_eq!("1".parse(), Ok(Priority::Critical));
+        assert_eq!("4".parse(), Ok(Priority::Low));
+        assert_eq!("5".parse(), Ok(Priority::Low));
+        assert_eq!(PriorityScale::default().shared(), [(5, 4)]);
+        assert_eq!(PriorityScale::default().distinct().range(), "1-4");
+        assert_eq!(
+            "6".parse::<Priority>().unwrap_err(),
+            "invalid priority '6' (expected critical, high, medium, low, none or 1-5)"
+        );
+
+        let ten = PriorityScale::preset("0-10").unwrap();
+        let levels: Vec<Priority> = [10, 8, 5, 1, 0]
+            .map(|n| ten.parse(&n.to_string()).unwrap())
+            .to_vec();
+        assert_eq!(
+            levels,
+            [
+                Priority::Critical,
+                Priority::High,
+                Priority::Medium,
+                Priority::Low,
+                Priority::None
+            ]
+        );
+        assert_eq!(ten.range(), "0-10");
+        assert!(PriorityScale::preset("1-3").is_none());
+        let mut custom = PriorityScale::default();
+        custom.set(5, Priority::None);
+        assert_eq!(custom.parse("5"), Ok(Priority::None));
+        assert!(custom.shared().is_empty());
+        assert_eq!(custom.parse("low"), Ok(Priority::Low));
+
+        let json = serde_json::to_string(&[Priority::High, Priority::None]).unwrap();
+        assert_eq!(json, r#"["high",null]"#);
+        let read: Vec<Priority> = serde_json::from_str(r#"["high",null,2,"Low"]"#).unwrap();
+        assert_eq!(
+            read,
+            [
+                Priority::High,
+                Priority::None,
+                Priority::High,
+                Priority::Low
+            ]
+        );
+        assert!(serde_json::from_str::<Priority>("9").is_err());
+    }
+}
@@ -0,0 +1,666 @@
+//! The filter language shared by `list`, `search`, `export` and the bulk
+//! commands, e.g. `status:pending priority<=high due.before:eow (+ops or
+//! project:web) "login bug"`.
+//!
+//! Terms next to each other must all match; `or`, `not` and parentheses
+//! combine them further (`not` binds tightest, then the implicit `and`,
+//! then `or`). A word that is not a term is searched for in the title and
+//! description, and so is anything in double quotes.
+
+use crate::TaskList;
+use crate::due;
+use crate::priority::{Priority, PriorityScale};
+use crate::task::{Status, Task, is_valid_name, is_valid_project};
+use chrono::{Local, NaiveDate};
+use std::cmp::Ordering;
+
+/// A parsed query; see the module documentation for the syntax.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Query {
+    /// Matches every task; what an empty query parses to.
+    #[default]
+    All,
+    Term(Term),
+    Not(Box<Query>),
+    And(Vec<Query>),
+    Or(Vec<Query>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    /// `status:pending`; `status:open` and `status:closed` list several.
+    Status(Vec<Status>),
+    /// Compared by rank, critical being the smallest, so `priority<=high`
+    /// means high or critical; tasks without one never match.
+    Priority(Cmp, Priority),
+    /// `priority:none`.
+    NoPriority,
+    /// Compared to the day of the date; tasks without it never match.
+    Date(DateKey, Cmp, NaiveDate),
+    /// `due:any` (true) or `due:none` (false).
+    HasDate(DateKey, bool),
+    /// `+tag`.
+    Tag(String),
+    /// `project:web` also matches its subprojects; a bare `project:` matches
+    /// tasks without one.
+    Project(Option<String>),
+    /// A case-insensitive substring of the title or description.
+    Text(String),
+    /// `blocked:yes`: whether the task depends on an open one.
+    Blocked(bool),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Cmp {
+    Lt,
+    Le,
+    Eq,
+    Ne,
+    Ge,
+    Gt,
+}
+
+impl Cmp {
+    fn holds(self, ordering: Ordering) -> bool {
+        match self {
+            Cmp::Lt => ordering.is_lt(),
+            Cmp::Le => ordering.is_le(),
+            Cmp::Eq => ordering.is_eq(),
+            Cmp::Ne => ordering.is_ne(),
+            Cmp::Ge => ordering.is_ge(),
+            Cmp::Gt => ordering.is_gt(),
+        }
+    }
+}
+
+/// The dates a query can compare.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DateKey {
+    Due,
+    Scheduled,
+    Wait,
+    Until,
+    Created,
+    Modified,
+    Completed,
+}
+
+impl DateKey {
+    pub const ALL: [DateKey; 7] = [
+        DateKey::Due,
+        DateKey::Scheduled,
+        DateKey::Wait,
+        DateKey::Until,
+        DateKey::Created,
+        DateKey::Modified,
+        DateKey::Completed,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DateKey::Due => "due",
+            DateKey::Scheduled => "scheduled",
+            DateKey::Wait => "wait",
+            DateKey::Until => "until",
+            DateKey::Created => "created",
+            DateKey::Modified => "modified",
+            DateKey::Completed => "completed",
+        }
+    }
+
+    /// The day of this date on `task`, in local time for the timestamps.
+    pub fn of(self, task: &Task) -> Option<NaiveDate> {
+        match self {
+            DateKey::Due => task.due_date,
+            DateKey::Scheduled => task.scheduled,
+            DateKey::Wait => task.wait,
+            DateKey::Until => task.until,
+            DateKey::Created => task.created_at.map(|at| at.date_naive()),
+            DateKey::Modified => task.modified_at.map(|at| at.date_naive()),
+            DateKey::Completed => task.completed_at.map(|at| at.date_naive()),
+        }
+    }
+}
+
+impl Query {
+    /// Parses a query; dates such as `eow` are resolved against today,
+    /// and priority numbers are read on the 1-5 scale.
+    pub fn parse(input: &str) -> Result<Query, String> {
+        Query::parse_on(input, &PriorityScale::default())
+    }
+
+    /// Like `parse`, reading priority numbers on `scale`.
+    pub fn parse_on(input: &str, scale: &PriorityScale) -> Result<Query, String> {
+        let tokens = tokenize(input)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+            scale,
+        };
+        let query = parser.or()?;
+        match parser.tokens.get(parser.position) {
+            None => Ok(query),
+            Some(Token::Close) => Err("unmatched ')'".to_string()),
+            Some(_) => unreachable!("the parser stops only at ')' or the end"),
+        }
+    }
+
+    /// Parses command-line words as one query. A word the shell kept
+    /// together (`"login bug"`, `due.before:next monday`) stays together.
+    pub fn from_args(args: &[String], scale: &PriorityScale) -> Result<Query, String> {
+        let words: Vec<String> = args.iter().map(|arg| quote_arg(arg)).collect();
+        Query::parse_on(&words.join(" "), scale)
+    }
+
+    /// Whether `args` hold more than bare words to search for: a field
+    /// term, `+tag` or `-tag`, `and`, `or`, `not`, a parenthesis or a
+    /// quoted phrase. Malformed input counts, so that parsing reports it.
+    pub fn has_syntax(args: &[String]) -> bool {
+        let words: Vec<String> = args.iter().map(|arg| quote_arg(arg)).collect();
+        let Ok(tokens) = tokenize(&words.join(" ")) else {
+            return true;
+        };
+        tokens.iter().any(|token| match token {
+            Token::Word(word) => {
+                ["and", "or", "not"]
+                    .iter()
+                    .any(|keyword| word.eq_ignore_ascii_case(keyword))
+                    || !matches!(
+                        parse_term(word, &PriorityScale::default()),
+                        Ok(Query::Term(Term::Text(_)))
+                    )
+            }
+            _ => true,
+        })
+    }
+
+    /// Whether `task`, one of `task_list`, matches; the list answers what
+    /// depends on other tasks.
+    pub fn matches(&self, task: &Task, task_list: &TaskList) -> bool {
+        match self {
+            Query::All => true,
+            Query::Term(term) => term.matches(task, task_list),
+            Query::Not(query) => !query.matches(task, task_list),
+            Query::And(queries) => queries.iter().all(|q| q.matches(task, task_list)),
+            Query::Or(queries) => queries.iter().any(|q| q.matches(task, task_list)),
+        }
+    }
+}
+
+impl Term {
+    pub fn matches(&self, task: &Task, task_list: &TaskList) -> bool {
+        match self {
+            Term::Status(statuses) => statuses.contains(&task.status),
+            Term::Priority(cmp, priority) => {
+                !task.priority.is_none() && cmp.holds(task.priority.cmp(priority))
+            }
+            Term::NoPriority => task.priority.is_none(),
+            Term::Date(key, cmp, day) => key.of(task).is_some_and(|d| cmp.holds(d.cmp(day))),
+            Term::HasDate(key, has) => key.of(task).is_some() == *has,
+            Term::Tag(tag) => task.has_tag(tag),
+            Term::Project(Some(project)) => task.in_project(project),
+            Term::Project(None) => task.project.is_none(),
+            Term::Text(text) => {
+                let text = text.to_lowercase();
+                task.description.to_lowercase().contains(&text)
+                    || task
+                        .title
+                        .as_ref()
+                        .is_some_and(|title| title.to_lowercase().contains(&text))
+            }
+            Term::Blocked(blocked) => {
+                let open = |uuid: &_| {
+                    task_list
+                        .position_by_uuid(*uuid)
+                        .is_some_and(|i| !task_list.get(i).unwrap().is_closed())
+                };
+                task.depends.iter().any(open) == *blocked
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Open,
+    Close,
+    Word(String),
+    /// Text in double quotes, never a term or keyword.
+    Quoted(String),
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut word = String::new();
+    let mut chars = input.chars();
+    let end_word = |word: &mut String, tokens: &mut Vec<Token>| {
+        if !word.is_empty() {
+            tokens.push(Token::Word(std::mem::take(word)));
+        }
+    };
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                let mut quoted = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => quoted.push(c),
+                        None => return Err("unterminated '\"'".to_string()),
+                    }
+                }
+                // quotes inside a word only keep its value together
+                if word.is_empty() {
+                    tokens.push(Token::Quoted(quoted));
+                } else {
+                    word.push_str(&quoted);
+                }
+            }
+            '(' | ')' => {
+                end_word(&mut word, &mut tokens);
+                tokens.push(if c == '(' { Token::Open } else { Token::Close });
+            }
+            c if c.is_whitespace() => end_word(&mut word, &mut tokens),
+            c => word.push(c),
+        }
+    }
+    end_word(&mut word, &mut tokens);
+    Ok(tokens)
+}
+
+/// Puts quotes back around the parts of `arg` that hold whitespace.
+fn quote_arg(arg: &str) -> String {
+    if !arg.contains(char::is_whitespace) || arg.contains('"') {
+        return arg.to_string();
+    }
+    let space = arg.find(char::is_whitespace).unwrap();
+    match arg[..space].find([':', '<', '>', '=']) {
+        Some(operator) => {
+            let value = arg[operator..].trim_start_matches([':', '<', '>', '=', '!']);
+            let split = arg.len() - value.len();
+            format!("{}\"{}\"", &arg[..split], value)
+        }
+        None => format!("\"{}\"", arg),
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [Token],
+    position: usize,
+    scale: &'a PriorityScale,
+}
+
+impl Parser<'_> {
+    fn peek_keyword(&self, keyword: &str) -> bool {
+        matches!(self.tokens.get(self.position),
+            Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword))
+    }
+
+    fn or(&mut self) -> Result<Query, String> {
+        let mut queries = vec![self.and()?];
+        while self.peek_keyword("or") {
+            self.position += 1;
+            queries.push(self.and()?);
+        }
+        Ok(combine(queries, Query::Or))
+    }
+
+    fn and(&mut self) -> Result<Query, String> {
+        let mut queries = Vec::new();
+        loop {
+            match self.tokens.get(self.position) {
+                None | Some(Token::Close) => break,
+                _ if self.peek_keyword("or") => break,
+                _ if self.peek_keyword("and") => {
+                    if queries.is_empty() {
+                        return Err("'and' needs something before it".to_string());
+                    }
+                    self.position += 1;
+                    queries.push(self.unary("and")?);
+                }
+                _ => queries.push(self.unary("")?),
+            }
+        }
+        if queries.is_empty() && !self.tokens.is_empty() {
+            let before = self.position.checked_sub(1).map(|i| &self.tokens[i]);
+            return Err(match (self.tokens.get(self.position), before) {
+                (Some(Token::Close), Some(Token::Open)) => "empty '()'",
+                (Some(Token::Close), _) => "unmatched ')'",
+                (Some(_), _) => "'or' needs something on both sides",
+                (None, Some(Token::Open)) => "missing ')'",
+                (None, _) => "nothing follows 'or'",
+            }
+            .to_string());
+        }
+        Ok(combine(queries, Query::And))
+    }
+
+    /// One term, `not` followed by one, or a group; `after` names the
+    /// keyword that asked for it, for errors.
+    fn unary(&mut self, after: &str) -> Result<Query, String> {
+        let missing = || {
+            if after.is_empty() {
+                "the query ends too early".to_string()
+            } else {
+                format!("nothing follows '{}'", after)
+            }
+        };
+        let token = self.tokens.get(self.position).ok_or_else(missing)?;
+        self.position += 1;
+        match token {
+            Token::Word(word) if word.eq_ignore_ascii_case("not") => {
+                Ok(Query::Not(Box::new(self.unary("not")?)))
+            }
+            Token::Word(word) if word.eq_ignore_ascii_case("or") => Err(missing()),
+            Token::Word(word) if word.eq_ignore_ascii_case("and") => Err(missing()),
+            Token::Word(word) => parse_term(word, self.scale),
+            Token::Quoted(text) => Ok(Query::Term(Term::Text(text.clone()))),
+            Token::Open => {
+                let query = self.or()?;
+                match self.tokens.get(self.position) {
+                    Some(Token::Close) => {
+                        self.position += 1;
+                        Ok(query)
+                    }
+                    _ => Err("missing ')'".to_string()),
+                }
+            }
+            Token::Close => Err(missing()),
+        }
+    }
+}
+
+/// One query for all of `queries`; `All` if there are none.
+fn combine(mut queries: Vec<Query>, join: fn(Vec<Query>) -> Query) -> Query {
+    match queries.len() {
+        0 => Query::All,
+        1 => queries.pop().unwrap(),
+        _ => join(queries),
+    }
+}
+
+fn parse_term(word: &str, scale: &PriorityScale) -> Result<Query, String> {
+    if let Some(tag) = word.strip_prefix('+')
+        && is_valid_name(tag)
+    {
+        return Ok(Query::Term(Term::Tag(tag.to_lowercase())));
+    }
+    if let Some(tag) = word.strip_prefix('-')
+        && is_valid_name(tag)
+    {
+        let term = Query::Term(Term::Tag(tag.to_lowercase()));
+        return Ok(Query::Not(Box::new(term)));
+    }
+    let Some(split) = word.find([':', '<', '>', '=', '!']) else {
+        return Ok(Query::Term(Term::Text(word.to_string())));
+    };
+    let (key, rest) = word.split_at(split);
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphabetic() || c == '.') {
+        return Ok(Query::Term(Term::Text(word.to_string())));
+    }
+    let (cmp, value) = if let Some(value) = rest.strip_prefix(':') {
+        (Cmp::Eq, value)
+    } else if let Some(value) = rest.strip_prefix("<=") {
+        (Cmp::Le, value)
+    } else if let Some(value) = rest.strip_prefix(">=") {
+        (Cmp::Ge, value)
+    } else if let Some(value) = rest.strip_prefix("!=") {
+        (Cmp::Ne, value)
+    } else if let Some(value) = rest.strip_prefix('<') {
+        (Cmp::Lt, value)
+    } else if let Some(value) = rest.strip_prefix('>') {
+        (Cmp::Gt, value)
+    } else if let Some(value) = rest.strip_prefix('=') {
+        (Cmp::Eq, value)
+    } else {
+        return Err(format!("cannot read '{}'", word));
+    };
+    let (key, cmp) = match key.to_lowercase().split_once('.') {
+        Some((key, modifier)) if cmp == Cmp::Eq => {
+            let cmp = match modifier {
+                "before" | "under" => Cmp::Lt,
+                "after" | "over" => Cmp::Gt,
+                "is" | "on" => Cmp::Eq,
+                "not" => Cmp::Ne,
+                other => return Err(format!("unknown modifier '.{}' in '{}'", other, word)),
+            };
+            (key.to_string(), cmp)
+        }
+        Some(_) => return Err(format!("cannot read '{}'", word)),
+        None => (key.to_lowercase(), cmp),
+    };
+    let term =
+        field_term(&key, cmp, value.trim(), scale).map_err(|e| format!("{} in '{}'", e, word))?;
+    Ok(match (term, cmp) {
+        // only ordered fields compare; the rest negate
+        (term @ (Term::Priority(..) | Term::Date(..)), _) => Query::Term(term),
+        (term, Cmp::Ne) => Query::Not(Box::new(Query::Term(term))),
+        (term, _) => Query::Term(term),
+    })
+}
+
+fn field_term(key: &str, cmp: Cmp, value: &str, scale: &PriorityScale) -> Result<Term, String> {
+    let equality = || {
+        if matches!(cmp, Cmp::Eq | Cmp::Ne) {
+            Ok(())
+        } else {
+            Err(format!("{} can only be matched, not compared", key))
+        }
+    };
+    match key {
+        "status" => {
+            equality()?;
+            let statuses = match value.to_lowercase().as_str() {
+                "open" => Status::ALL.into_iter().filter(|s| !s.is_closed()).collect(),
+                "closed" => Status::ALL.into_iter().filter(|s| s.is_closed()).collect(),
+                _ => vec![value.parse()?],
+            };
+            Ok(Term::Status(statuses))
+        }
+        "priority" | "pri" => {
+            let priority = match value {
+                "" => Priority::None,
+                _ => scale.parse(value)?,
+            };
+            if priority.is_none() {
+                equality()?;
+                return Ok(Term::NoPriority);
+            }
+            Ok(Term::Priority(cmp, priority))
+        }
+        "project" | "proj" => {
+            equality()?;
+            if value.is_empty() {
+                Ok(Term::Project(None))
+            } else if is_valid_project(value) {
+                Ok(Term::Project(Some(value.to_lowercase())))
+            } else {
+                Err(format!("invalid project '{}'", value))
+            }
+        }
+        "blocked" => {
+            equality()?;
+            match value.to_lowercase().as_str() {
+                "yes" => Ok(Term::Blocked(true)),
+                "no" => Ok(Term::Blocked(false)),
+                _ => Err(format!("blocked is yes or no, not '{}'", value)),
+            }
+        }
+        "tag" | "tags" => {
+            equality()?;
+            if is_valid_name(value) {
+                Ok(Term::Tag(value.to_lowercase()))
+            } else {
+                Err(format!("invalid tag '{}'", value))
+            }
+        }
+        key => {
+            let key = DateKey::ALL
+                .into_iter()
+                .find(|k| k.name() == key)
+                .ok_or_else(|| format!("unknown field '{}' (quote it to search for it)", key))?;
+            match value.to_lowercase().as_str() {
+                "none" | "any" => {
+                    equality()?;
+                    Ok(Term::HasDate(key, value.eq_ignore_ascii_case("any")))
+                }
+                _ => {
+                    let due = due::parse(value, Local::now().naive_local())?;
+                    Ok(Term::Date(key, cmp, due.date))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(description: &str) -> Task {
+        let mut task = Task::new(description);
+        task.created_at = Some(Local::now());
+        task
+    }
+
+    #[test]
+    fn test_priority_numbers_on_scale() {
+        let ten = PriorityScale::preset("0-10").unwrap();
+        let on_ten = |input: &str| Query::parse_on(input, &ten);
+        assert_eq!(on_ten("priority:7"), Query::parse("priority:high"));
+        assert_eq!(on_ten("priority<=9"), Query::parse("priority<=critical"));
+        assert_eq!(on_ten("priority:0"), Query::parse("priority:none"));
+        assert_eq!(on_ten("pri>=m"), Query::parse("priority>=medium"));
+        assert_eq!(
+            on_ten("priority:11").unwrap_err(),
+            "invalid priority '11' (expected critical, high, medium, low, none or 0-10) \
+             in 'priority:11'"
+        );
+        // the default scale reads the same numbers differently
+        assert_eq!(Query::parse("priority:2"), Query::parse("priority:high"));
+    }
+
+    /// A critical, pending +ops task in web.auth due 2026-10-30, and a low
+    /// priority one in progress.
+    fn login_and_docs() -> (Task, Task) {
+        let mut login = task("Fix the login bug +ops");
+        login.add_tag("ops");
+        login.project = Some("web.auth".to_string());
+        login.priority = Priority::Critical;
+        login.due_date = NaiveDate::from_ymd_opt(2026, 10, 30);
+        let mut docs = task("Write docs");
+        docs.priority = Priority::Low;
+        docs.status = Status::InProgress;
+        (login, docs)
+    }
+
+    fn text(word: &str) -> Query {
+        Query::Term(Term::Text(word.to_string()))
+    }
+
+    #[test]
+    fn test_terms_match() {
+        let (login, docs) = login_and_docs();
+        let list = TaskList::new();
+        let matching = |input: &str| -> Vec<bool> {
+            let query = Query::parse(input).unwrap();
+            vec![query.matches(&login, &list), query.matches(&docs, &list)]
+        };
+        assert_eq!(
+            matching(
+                "status:pending priority<=2 due.before:2026-11-01 +ops project:web \"login bug\""
+            ),
+            [true, false]
+        );
+        assert_eq!(matching(""), [true, true]);
+        assert_eq!(matching("status:open"), [true, true]);
+        assert_eq!(matching("+ops or docs"), [true, true]);
+        assert_eq!(matching("not (+ops or docs)"), [false, false]);
+        assert_eq!(matching("-ops and priority>3"), [false, true]);
+        assert_eq!(matching("priority<=high"), [true, false]);
+        assert_eq!(matching("pri:Low or priority:c"), [true, true]);
+        assert_eq!(matching("status!=in-progress due:none"), [false, false]);
+        assert_eq!(matching("due:any or project:"), [true, true]);
+        assert_eq!(matching("created.after:yesterday LOGIN"), [true, false]);
+        assert_eq!(matching("\"or\""), [false, false]);
+        assert!(Query::parse("10:30 \"re:call\"").is_ok());
+    }
+
+    #[test]
+    fn test_or_binds_looser_than_and() {
+        let (a, b, c) = (text("a"), text("b"), text("c"));
+        let and = |queries: &[&Query]| Query::And(queries.iter().map(|q| (*q).clone()).collect());
+        let or = |queries: &[&Query]| Query::Or(queries.iter().map(|q| (*q).clone()).collect());
+        assert_eq!(Query::parse("a or b c"), Ok(or(&[&a, &and(&[&b, &c])])));
+        assert_eq!(Query::parse("a b or c"), Ok(or(&[&and(&[&a, &b]), &c])));
+        assert_eq!(Query::parse("a and b or c"), Ok(or(&[&and(&[&a, &b]), &c])));
+        assert_eq!(Query::parse("(a or b) c"), Ok(and(&[&or(&[&a, &b]), &c])));
+        // not takes only the next term or group
+        let not_a = Query::Not(Box::new(a.clone()));
+        assert_eq!(Query::parse("not a b"), Ok(and(&[&not_a, &b])));
+        assert_eq!(Query::parse("NOT a OR b"), Ok(or(&[&not_a, &b])));
+    }
+
+    #[test]
+    fn test_errors_name_where_the_query_breaks() {
+        for (bad, message) in [
+            // at the start
+            ("or +ops", "'or' needs something on both sides"),
+            ("and +ops", "'and' needs something before it"),
+            (")", "unmatched ')'"),
+            // in the middle
+            ("+ops or or docs", "'or' needs something on both sides"),
+            ("+ops () docs", "empty '()'"),
+            ("+ops) docs", "unmatched ')'"),
+            // at the end
+            ("+ops or", "nothing follows 'or'"),
+            ("+ops and", "nothing follows 'and'"),
+            ("not", "nothing follows 'not'"),
+            ("(+ops", "missing ')'"),
+            ("\"open", "unterminated '\"'"),
+        ] {
+            assert_eq!(Query::parse(bad), Err(message.to_string()), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_bad_terms() {
+        for (bad, message) in [
+            (
+                "colour:red",
+                "unknown field 'colour' (quote it to search for it)",
+            ),
+            ("project>web", "project can only be matched, not compared"),
+            (
+                "due.soon:today",
+                "unknown modifier '.soon' in 'due.soon:today'",
+            ),
+        ] {
+            let error = Query::parse(bad).unwrap_err();
+            assert!(error.starts_with(message), "{}: {}", bad, error);
+        }
+        for bad in ["status:blocked", "priority<=9", "due.before:someday"] {
+            assert!(Query::parse(bad).is_err(), "{} should not parse", bad);
+        }
+    }
+
+    #[test]
+    fn test_args_keep_shell_words_together() {
+        let (login, _) = login_and_docs();
+        let list = TaskList::new();
+        let args = ["due.before:next monday", "login bug"].map(String::from);
+        assert_eq!(
+            Query::from_args(&args, &PriorityScale::default())
+                .unwrap()
+                .matches(&login, &list),
+            Query::parse("due.before:\"next monday\" \"login bug\"")
+                .unwrap()
+                .matches(&login, &list)
+        );
+        assert!(Query::has_syntax(&args));
+        assert!(!Query::has_syntax(&[
+            "login".to_string(),
+            "bug".to_string()
+        ]));
+    }
+}
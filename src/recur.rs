@@ -0,0 +1,452 @@
+//! Recurrence rules for repeating tasks, and the calendar arithmetic that
+//! rolls a due date forward to the next occurrence.
+
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+const WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+const NAMES: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+/// How often a task repeats, e.g. every 2 weeks on Monday and Thursday.
+///
+/// Written as `daily`, `every 3 days`, `weekly on mon,thu`, `every 2 weeks
+/// on fri`, `monthly on day 15`, `monthly on the last fri`, `yearly on
+/// 03-15`, or as an RRULE such as `FREQ=MONTHLY;BYDAY=-1FR`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Recurrence {
+    /// Interval in `unit`s, from 1 to 1000.
+    pub every: u32,
+    pub unit: Unit,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Unit {
+    Days,
+    /// On the given weekdays (sorted, Monday first); none means the weekday
+    /// of the due date.
+    Weeks(Vec<Weekday>),
+    /// On the given day; none means the day of the due date.
+    Months(Option<MonthDay>),
+    /// On the given month and day; none means those of the due date.
+    Years(Option<(u32, u32)>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MonthDay {
+    /// Day of the month, moved to the last day in shorter months.
+    Day(u32),
+    /// The last such weekday of the month.
+    Last(Weekday),
+}
+
+impl Recurrence {
+    pub fn new(every: u32, unit: Unit) -> Self {
+        Self { every, unit }
+    }
+
+    /// Fills in what the rule leaves to the due date, so that the series
+    /// keeps to it even after landing on a shorter month.
+    pub fn anchored(mut self, due: NaiveDate) -> Self {
+        match &mut self.unit {
+            Unit::Weeks(days) if days.is_empty() => days.push(due.weekday()),
+            Unit::Months(on @ None) => *on = Some(MonthDay::Day(due.day())),
+            Unit::Years(on @ None) => *on = Some((due.month(), due.day())),
+            _ => {}
+        }
+        self
+    }
+
+    /// The first occurrence strictly after `date`.
+    pub fn next_after(&self, date: NaiveDate) -> NaiveDate {
+        let every = self.every.max(1);
+        match &self.unit {
+            Unit::Days => date + Days::new(every.into()),
+            Unit::Weeks(days) if days.is_empty() => date + Days::new(7 * u64::from(every)),
+            Unit::Weeks(days) => {
+                let today = date.weekday().num_days_from_monday();
+                let offsets = days.iter().map(|d| d.num_days_from_monday());
+                if let Some(later) = offsets.clone().filter(|&d| d > today).min() {
+                    return date + Days::new((later - today).into());
+                }
+                let monday = date - Days::new(today.into());
+                let first = offsets.min().unwrap();
+                monday + Days::new(7 * u64::from(every) + u64::from(first))
+            }
+            Unit::Months(on) => {
+                let on = on.unwrap_or(MonthDay::Day(date.day()));
+                let this_month = on.in_month(date.year(), date.month());
+                if this_month > date {
+                    return this_month;
+                }
+                let (year, month) = add_months(date.year(), date.month(), every);
+                on.in_month(year, month)
+            }
+            Unit::Years(on) => {
+                let (month, day) = on.unwrap_or((date.month(), date.day()));
+                let this_year = clamped(date.year(), month, day);
+                if this_year > date {
+                    return this_year;
+                }
+                clamped(date.year() + every as i32, month, day)
+            }
+        }
+    }
+
+    fn from_rrule(rule: &str) -> Result<Self, String> {
+        let mut freq = None;
+        let mut every = 1;
+        let mut by_day = None;
+        let mut by_month_day = None;
+        let mut by_month = None;
+        for part in rule.split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| format!("invalid RRULE part '{}'", part))?;
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => freq = Some(value.to_ascii_uppercase()),
+                "INTERVAL" => every = parse_interval(value)?,
+                "BYDAY" => by_day = Some(value.to_ascii_uppercase()),
+                "BYMONTHDAY" => by_month_day = Some(value.to_string()),
+                "BYMONTH" => by_month = Some(value.to_string()),
+                other => return Err(format!("unsupported RRULE part '{}'", other)),
+            }
+        }
+        let unit = match (freq.as_deref(), by_day, by_month_day, by_month) {
+            (Some("DAILY"), None, None, None) => Unit::Days,
+            (Some("WEEKLY"), days, None, None) => {
+                let days = days.as_deref().unwrap_or("");
+                let days = days.split(',').filter(|d| !d.is_empty());
+                Unit::Weeks(sorted(days.map(rrule_weekday).collect::<Result<_, _>>()?))
+            }
+            (Some("MONTHLY"), None, day, None) => {
+                Unit::Months(day.as_deref().map(rrule_month_day).transpose()?)
+            }
+            (Some("MONTHLY"), Some(day), None, None) => {
+                let weekday = day.strip_prefix("-1").ok_or_else(|| {
+                    format!("only BYDAY=-1XX is supported monthly, not '{}'", day)
+                })?;
+                Unit::Months(Some(MonthDay::Last(rrule_weekday(weekday)?)))
+            }
+            (Some("YEARLY"), None, None, None) => Unit::Years(None),
+            (Some("YEARLY"), None, Some(day), Some(month)) => {
+                Unit::Years(Some(month_and_day(&month, &day)?))
+            }
+            (None, ..) => return Err("RRULE needs a FREQ".to_string()),
+            (Some(freq), ..) => {
+                return Err(format!("unsupported RRULE combination for FREQ={}", freq));
+            }
+        };
+        Ok(Self::new(every, unit))
+    }
+}
+
+impl MonthDay {
+    fn in_month(self, year: i32, month: u32) -> NaiveDate {
+        match self {
+            MonthDay::Day(day) => clamped(year, month, day),
+            MonthDay::Last(weekday) => {
+                let last = clamped(year, month, 31);
+                let back = (7 + last.weekday().num_days_from_monday()
+                    - weekday.num_days_from_monday())
+                    % 7;
+                last - Days::new(back.into())
+            }
+        }
+    }
+}
+
+/// The date, moved back to the month's last day if it has fewer days.
+fn clamped(year: i32, month: u32, day: u32) -> NaiveDate {
+    (1..=day)
+        .rev()
+        .find_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+        .expect("every month has a first day")
+}
+
+fn add_months(year: i32, month: u32, months: u32) -> (i32, u32) {
+    let zero_based = month - 1 + months;
+    (year + (zero_based / 12) as i32, zero_based % 12 + 1)
+}
+
+fn sorted(mut days: Vec<Weekday>) -> Vec<Weekday> {
+    days.sort_by_key(|d| d.num_days_from_monday());
+    days.dedup();
+    days
+}
+
+fn short_name(weekday: Weekday) -> &'static str {
+    &NAMES[weekday.num_days_from_monday() as usize][..3]
+}
+
+/// A weekday from its name or an abbreviation of at least three letters.
+pub(crate) fn weekday(word: &str) -> Option<Weekday> {
+    let index = NAMES
+        .iter()
+        .position(|name| word.len() >= 3 && name.starts_with(word))?;
+    Some(WEEKDAYS[index])
+}
+
+fn rrule_weekday(code: &str) -> Result<Weekday, String> {
+    WEEKDAYS
+        .iter()
+        .copied()
+        .find(|&d| short_name(d)[..2].eq_ignore_ascii_case(code))
+        .ok_or_else(|| format!("invalid weekday '{}'", code))
+}
+
+fn rrule_month_day(value: &str) -> Result<MonthDay, String> {
+    match value.parse::<i32>() {
+        Ok(-1) => Ok(MonthDay::Day(31)),
+        Ok(day) if (1..=31).contains(&day) => Ok(MonthDay::Day(day as u32)),
+        _ => Err(format!("invalid day of the month '{}'", value)),
+    }
+}
+
+fn month_and_day(month: &str, day: &str) -> Result<(u32, u32), String> {
+    let month: u32 = month
+        .parse()
+        .ok()
+        .filter(|m| (1..=12).contains(m))
+        .ok_or_else(|| format!("invalid month '{}'", month))?;
+    let day: u32 = day
+        .parse()
+        .ok()
+        .filter(|&d| NaiveDate::from_ymd_opt(2000, month, d).is_some())
+        .ok_or_else(|| format!("invalid day '{}' for month {}", day, month))?;
+    Ok((month, day))
+}
+
+/// The longest interval a rule may have, which keeps every occurrence of a
+/// four-digit due date well inside the calendar.
+const MAX_INTERVAL: u32 = 1000;
+
+fn parse_interval(value: &str) -> Result<u32, String> {
+    value
+        .parse()
+        .ok()
+        .filter(|n| (1..=MAX_INTERVAL).contains(n))
+        .ok_or_else(|| {
+            format!(
+                "invalid interval '{}' (expected 1 to {})",
+                value, MAX_INTERVAL
+            )
+        })
+}
+
+impl FromStr for Recurrence {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, String> {
+        let trimmed = input.trim();
+        if let Some(rule) = trimmed.strip_prefix("RRULE:") {
+            return Self::from_rrule(rule);
+        }
+        if trimmed.to_ascii_uppercase().starts_with("FREQ=") {
+            return Self::from_rrule(trimmed);
+        }
+        let lower = trimmed.to_lowercase();
+        let mut words = lower
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|w| !w.is_empty())
+            .peekable();
+        let unknown = || format!("unknown recurrence '{}'", trimmed);
+        let (every, unit) = match words.next() {
+            Some("daily") => (1, "day"),
+            Some("weekly") => (1, "week"),
+            Some("monthly") => (1, "month"),
+            Some("yearly" | "annually") => (1, "year"),
+            Some("every") => {
+                let every = match words.peek().and_then(|w| w.parse::<u32>().ok()) {
+                    Some(_) => parse_interval(words.next().unwrap())?,
+                    None => 1,
+                };
+                match words.peek().copied() {
+                    Some(word) if word.starts_with("weekday") => (every, "week"),
+                    Some(word) if weekday(word).is_some() => (every, "week"),
+                    Some(word) => {
+                        words.next();
+                        (every, word.trim_end_matches('s'))
+                    }
+                    None => return Err(unknown()),
+                }
+            }
+            _ => return Err(unknown()),
+        };
+        let rest: Vec<&str> = words.filter(|w| !matches!(*w, "on" | "the")).collect();
+        let unit = match (unit, rest.as_slice()) {
+            ("day", []) => Unit::Days,
+            ("week", days) => {
+                let mut weekdays = Vec::new();
+                for &word in days {
+                    match word {
+                        "weekday" | "weekdays" => weekdays.extend(&WEEKDAYS[..5]),
+                        word => weekdays.push(weekday(word).ok_or_else(unknown)?),
+                    }
+                }
+                Unit::Weeks(sorted(weekdays))
+            }
+            ("month", []) => Unit::Months(None),
+            ("month", ["day", day]) => {
+                let day = day.parse().ok().filter(|d| (1..=31).contains(d));
+                Unit::Months(Some(MonthDay::Day(day.ok_or_else(unknown)?)))
+            }
+            ("month", ["last", "day"]) => Unit::Months(Some(MonthDay::Day(31))),
+            ("month", ["last", word]) => {
+                Unit::Months(Some(MonthDay::Last(weekday(word).ok_or_else(unknown)?)))
+            }
+            ("year", []) => Unit::Years(None),
+            ("year", [date]) => {
+                let (month, day) = date.split_once('-').ok_or_else(unknown)?;
+                Unit::Years(Some(month_and_day(month, day)?))
+            }
+            _ => return Err(unknown()),
+        };
+        Ok(Self::new(every, unit))
+    }
+}
+
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (single, plural) = match self.unit {
+            Unit::Days => ("daily", "days"),
+            Unit::Weeks(_) => ("weekly", "weeks"),
+            Unit::Months(_) => ("monthly", "months"),
+            Unit::Years(_) => ("yearly", "years"),
+        };
+        if self.every == 1 {
+            write!(f, "{}", single)?;
+        } else {
+            write!(f, "every {} {}", self.every, plural)?;
+        }
+        match &self.unit {
+            Unit::Weeks(days) if !days.is_empty() => {
+                let names: Vec<&str> = days.iter().map(|&d| short_name(d)).collect();
+                write!(f, " on {}", names.join(","))
+            }
+            Unit::Months(Some(MonthDay::Day(day))) => write!(f, " on day {}", day),
+            Unit::Months(Some(MonthDay::Last(weekday))) => {
+                write!(f, " on the last {}", short_name(*weekday))
+            }
+            Unit::Years(Some((month, day))) => write!(f, " on {:02}-{:02}", month, day),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl TryFrom<String> for Recurrence {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, String> {
+        value.parse()
+    }
+}
+
+impl From<Recurrence> for String {
+    fn from(recurrence: Recurrence) -> Self {
+        recurrence.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").unwrap()
+    }
+
+    fn next(rule: &str, from: &str) -> String {
+        let rule: Recurrence = rule.parse().unwrap();
+        rule.anchored(date(from)).next_after(date(from)).to_string()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        for (input, shown) in [
+            ("daily", "daily"),
+            ("every 3 days", "every 3 days"),
+            ("Every Monday, Thursday", "weekly on mon,thu"),
+            ("every 2 weeks on fri", "every 2 weeks on fri"),
+            ("every weekday", "weekly on mon,tue,wed,thu,fri"),
+            ("monthly on the last friday", "monthly on the last fri"),
+            ("monthly on the last day", "monthly on day 31"),
+            ("yearly on 02-29", "yearly on 02-29"),
+            (
+                "FREQ=WEEKLY;INTERVAL=2;BYDAY=TU,MO",
+                "every 2 weeks on mon,tue",
+            ),
+            ("RRULE:FREQ=MONTHLY;BYDAY=-1SU", "monthly on the last sun"),
+            ("FREQ=YEARLY;BYMONTH=3;BYMONTHDAY=15", "yearly on 03-15"),
+        ] {
+            let rule: Recurrence = input.parse().unwrap();
+            assert_eq!(rule.to_string(), shown);
+            assert_eq!(shown.parse::<Recurrence>().unwrap(), rule);
+        }
+        for bad in [
+            "sometimes",
+            "every 0 days",
+            "weekly on funday",
+            "monthly on day 32",
+            "FREQ=HOURLY",
+            "FREQ=DAILY;COUNT=3",
+        ] {
+            assert!(bad.parse::<Recurrence>().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_oversized_interval() {
+        for bad in [
+            "every 999999999 days",
+            "every 1001 weeks",
+            "RRULE:FREQ=MONTHLY;INTERVAL=4294967295",
+        ] {
+            let error = bad.parse::<Recurrence>().unwrap_err();
+            assert!(error.contains("expected 1 to 1000"), "{}", bad);
+        }
+        // the largest intervals still roll the last four-digit dates on
+        assert_eq!(next("every 1000 days", "9999-12-31"), "+10002-09-26");
+        assert_eq!(next("every 1000 months", "9999-12-31"), "+10083-04-30");
+        assert_eq!(next("every 1000 years", "9999-12-31"), "+10999-12-31");
+    }
+
+    #[test]
+    fn test_next_occurrence() {
+        assert_eq!(next("every 3 days", "2026-12-30"), "2027-01-02");
+        // Wednesday 2026-10-21
+        assert_eq!(next("weekly on mon,thu", "2026-10-21"), "2026-10-22");
+        assert_eq!(next("every 2 weeks on mon,thu", "2026-10-22"), "2026-11-02");
+        assert_eq!(next("weekly", "2026-10-21"), "2026-10-28");
+        // month ends are kept rather than drifting
+        let monthly = "monthly".parse::<Recurrence>().unwrap();
+        let monthly = monthly.anchored(date("2026-01-31"));
+        let february = monthly.next_after(date("2026-01-31"));
+        assert_eq!(february, date("2026-02-28"));
+        assert_eq!(monthly.next_after(february), date("2026-03-31"));
+        assert_eq!(next("monthly on the last fri", "2026-10-30"), "2026-11-27");
+        assert_eq!(next("monthly on the last fri", "2026-10-02"), "2026-10-30");
+        assert_eq!(next("every 3 months on day 15", "2026-11-15"), "2027-02-15");
+        assert_eq!(next("yearly on 02-29", "2028-02-29"), "2029-02-28");
+        assert_eq!(next("yearly", "2026-10-18"), "2027-10-18");
+    }
+}
@@ -0,0 +1,391 @@
+//! Named reports: a saved filter, sort order, column list and limit,
+//! e.g. `task report overdue`.
+
+use crate::due;
+use crate::ops::TaskFilter;
+use crate::query::Query;
+use crate::sort::{SortKey, SortSpec};
+use crate::urgency::{Scores, Urgency};
+use crate::{Priority, Task, TaskList};
+use chrono::Local;
+
+/// A column a report can show.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Column {
+    Id,
+    Uuid,
+    Status,
+    Priority,
+    Due,
+    Scheduled,
+    Wait,
+    Until,
+    Created,
+    Modified,
+    Completed,
+    Project,
+    Tags,
+    Recur,
+    Urgency,
+    Description,
+}
+
+impl Column {
+    pub const ALL: [Column; 16] = [
+        Column::Id,
+        Column::Uuid,
+        Column::Status,
+        Column::Priority,
+        Column::Due,
+        Column::Scheduled,
+        Column::Wait,
+        Column::Until,
+        Column::Created,
+        Column::Modified,
+        Column::Completed,
+        Column::Project,
+        Column::Tags,
+        Column::Recur,
+        Column::Urgency,
+        Column::Description,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Column::Id => "id",
+            Column::Uuid => "uuid",
+            Column::Status => "status",
+            Column::Priority => "priority",
+            Column::Due => "due",
+            Column::Scheduled => "scheduled",
+            Column::Wait => "wait",
+            Column::Until => "until",
+            Column::Created => "created",
+            Column::Modified => "modified",
+            Column::Completed => "completed",
+            Column::Project => "project",
+            Column::Tags => "tags",
+            Column::Recur => "recur",
+            Column::Urgency => "urgency",
+            Column::Description => "description",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Column> {
+        let name = name.trim().to_lowercase();
+        Column::ALL.into_iter().find(|column| column.name() == name)
+    }
+
+    /// What the column shows for `task`; empty when the task has no value.
+    /// Urgency is looked up in `scores`.
+    pub fn cell(self, task: &Task, scores: &Scores) -> String {
+        let day = |date: Option<chrono::NaiveDate>| date.map(|d| d.to_string()).unwrap_or_default();
+        let stamp = |at: Option<chrono::DateTime<Local>>| {
+            at.map(|at| at.format("%Y-%m-%d").to_string())
+                .unwrap_or_default()
+        };
+        match self {
+            Column::Id => task.id.to_string(),
+            Column::Uuid => task.uuid.to_string()[..8].to_string(),
+            Column::Status => task.status.name().to_string(),
+            Column::Priority => match task.priority {
+                Priority::None => String::new(),
+                level => level.to_string(),
+            },
+            Column::Due => match task.due_date {
+                Some(date) => {
+                    let when = task.due_at.map_or(date.to_string(), due::format_at);
+                    if task.is_closed() {
+                        when
+                    } else {
+                        let relative = due::relative(date, task.due_at, Local::now());
+                        format!("{} ({})", when, relative)
+                    }
+                }
+                None => String::new(),
+            },
+            Column::Scheduled => day(task.scheduled),
+            Column::Wait => day(task.wait),
+            Column::Until => day(task.until),
+            Column::Created => stamp(task.created_at),
+            Column::Modified => stamp(task.modified_at),
+            Column::Completed => stamp(task.completed_at),
+            Column::Project => task.project.clone().unwrap_or_default(),
+            Column::Tags => {
+                let tags: Vec<String> = task.tags.iter().map(|tag| format!("+{}", tag)).collect();
+                tags.join(" ")
+            }
+            Column::Recur => task
+                .recur
+                .as_ref()
+                .map(|r| r.to_string())
+                .unwrap_or_default(),
+            Column::Urgency => match scores.get(&task.uuid) {
+                Some(score) if !task.is_closed() => format!("{:.1}", score),
+                _ => String::new(),
+            },
+            Column::Description => match &task.title {
+                Some(title) => format!("{}: {}", title, task.description),
+                None => task.description.clone(),
+            },
+        }
+    }
+}
+
+/// Columns for a report that does not list its own.
+pub const DEFAULT_COLUMNS: [Column; 6] = [
+    Column::Id,
+    Column::Status,
+    Column::Priority,
+    Column::Due,
+    Column::Urgency,
+    Column::Description,
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub name: String,
+    pub description: String,
+    pub filter: TaskFilter,
+    /// Most urgent first unless set; ties keep the list's order.
+    pub sort: SortSpec,
+    pub columns: Vec<Column>,
+    pub limit: Option<usize>,
+}
+
+impl Report {
+    /// A report showing every task the usual listing would, most urgent
+    /// first.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: String::new(),
+            filter: TaskFilter::default(),
+            sort: SortKey::Urgency.into(),
+            columns: DEFAULT_COLUMNS.to_vec(),
+            limit: None,
+        }
+    }
+
+    /// The tasks the report shows, also narrowed by `extra`, in its order.
+    pub fn select<'a>(
+        &self,
+        task_list: &'a TaskList,
+        extra: &Query,
+        urgency: &Urgency,
+    ) -> Vec<&'a Task> {
+        let mut tasks: Vec<&Task> = task_list
+            .iter()
+            .filter(|task| self.filter.matches(task, task_list) && extra.matches(task, task_list))
+            .collect();
+        self.sort.sort(&mut tasks, task_list, urgency);
+        if let Some(limit) = self.limit {
+            tasks.truncate(limit);
+        }
+        tasks
+    }
+
+    /// A header line and one aligned line per task; nothing if `tasks` is
+    /// empty.
+    pub fn render(&self, tasks: &[&Task], task_list: &TaskList, urgency: &Urgency) -> Vec<String> {
+        if tasks.is_empty() {
+            return Vec::new();
+        }
+        let scores = if self.columns.contains(&Column::Urgency) {
+            urgency.scores(task_list)
+        } else {
+            Scores::new()
+        };
+        let mut rows: Vec<Vec<String>> = vec![
+            self.columns
+                .iter()
+                .map(|column| column.name().to_uppercase())
+                .collect(),
+        ];
+        rows.extend(tasks.iter().map(|task| {
+            self.columns
+                .iter()
+                .map(|column| column.cell(task, &scores))
+                .collect()
+        }));
+        let widths: Vec<usize> = (0..self.columns.len())
+            .map(|i| rows.iter().map(|row| row[i].chars().count()).max().unwrap())
+            .collect();
+        rows.iter()
+            .map(|row| {
+                let cells: Vec<String> = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, &width)| format!("{:width$}", cell, width = width))
+                    .collect();
+                cells.join("  ").trim_end().to_string()
+            })
+            .collect()
+    }
+}
+
+fn report(
+    name: &str,
+    description: &str,
+    filter: &str,
+    sort: &[SortKey],
+    columns: Vec<Column>,
+) -> Report {
+    let mut report = Report::new(name);
+    report.description = description.to_string();
+    report.filter.query = Query::parse(filter).expect("built-in report filters parse");
+    report.sort = SortSpec::of(sort);
+    report.columns = columns;
+    report
+}
+
+/// The built-in `next` report; `ops::next_actionable` is this report
+/// without its limit.
+pub fn next() -> Report {
+    let mut next = report(
+        "next",
+        "the most important tasks that can be started now",
+        "(status:pending or status:in-progress) (scheduled:none or scheduled.before:tomorrow) \
+         blocked:no",
+        &[SortKey::Urgency, SortKey::Priority, SortKey::DueDate],
+        vec![
+            Column::Id,
+            Column::Status,
+            Column::Priority,
+            Column::Due,
+            Column::Urgency,
+            Column::Project,
+            Column::Tags,
+            Column::Description,
+        ],
+    );
+    next.limit = Some(10);
+    next
+}
+
+/// The reports available without any configuration.
+pub fn builtin() -> Vec<Report> {
+    let mut waiting = report(
+        "waiting",
+        "tasks waiting on someone else or until a later day",
+        "status:waiting or (status:open wait.after:today)",
+        &[SortKey::DueDate],
+        vec![
+            Column::Id,
+            Column::Status,
+            Column::Wait,
+            Column::Due,
+            Column::Description,
+        ],
+    );
+    waiting.filter.hidden = true;
+    vec![
+        report(
+            "today",
+            "open tasks due or scheduled today or earlier",
+            "status:open (due.before:tomorrow or scheduled.before:tomorrow)",
+            &[SortKey::DueDate, SortKey::Priority],
+            vec![
+                Column::Id,
+                Column::Status,
+                Column::Priority,
+                Column::Due,
+                Column::Project,
+                Column::Description,
+            ],
+        ),
+        report(
+            "overdue",
+            "open tasks whose due date has passed",
+            "status:open due.before:today",
+            &[SortKey::DueDate, SortKey::Priority],
+            vec![
+                Column::Id,
+                Column::Priority,
+                Column::Due,
+                Column::Description,
+            ],
+        ),
+        next(),
+        waiting,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::Status;
+    use chrono::Duration;
+
+    #[test]
+    fn test_next_leaves_out_blocked_tasks() {
+        let mut task_list = TaskList::new();
+        for description in ["Design", "Build", "Ship"] {
+            task_list.add(Task::new(description)).unwrap();
+        }
+        let design = task_list.get(0).unwrap().uuid;
+        let build = task_list.get(1).unwrap().uuid;
+        task_list.get_mut(1).unwrap().depends.push(design);
+        task_list.get_mut(2).unwrap().depends.push(build);
+        task_list.get_mut(0).unwrap().status = Status::Done;
+        let reports = builtin();
+        let next = reports.iter().find(|r| r.name == "next").unwrap();
+        let shown = next.select(&task_list, &Query::All, &Urgency::default());
+        let names: Vec<&str> = shown.iter().map(|t| t.description.as_str()).collect();
+        // Design is done, so Build is free; Ship still waits on Build
+        assert_eq!(names, ["Build"]);
+        let blocked = Query::parse("blocked:yes").unwrap();
+        assert!(blocked.matches(task_list.get(2).unwrap(), &task_list));
+        assert!(Query::parse("blocked:maybe").is_err());
+        assert!(Query::parse("blocked>no").is_err());
+    }
+
+    #[test]
+    fn test_builtin_reports() {
+        let today = Local::now().date_naive();
+        let mut task_list = TaskList::new();
+        for (description, priority, due) in [
+            ("Late", Priority::High, Some(today - Duration::days(2))),
+            ("Later", Priority::Critical, Some(today - Duration::days(1))),
+            ("Due now", Priority::None, Some(today)),
+            ("Someday", Priority::Medium, None),
+        ] {
+            let mut task = Task::new(description);
+            task.priority = priority;
+            task.due_date = due;
+            task_list.add(task).unwrap();
+        }
+        task_list.get_mut(3).unwrap().status = Status::Waiting;
+        let reports = builtin();
+        let urgency = Urgency::default();
+        let find = |name: &str| reports.iter().find(|r| r.name == name).unwrap();
+        let names = |tasks: Vec<&Task>| -> Vec<String> {
+            tasks.iter().map(|t| t.description.clone()).collect()
+        };
+
+        let overdue = find("overdue").select(&task_list, &Query::All, &urgency);
+        assert_eq!(names(overdue), ["Late", "Later"]);
+        let today_tasks = find("today").select(&task_list, &Query::All, &urgency);
+        assert_eq!(names(today_tasks), ["Late", "Later", "Due now"]);
+        let next = find("next").select(&task_list, &Query::parse("priority<=2").unwrap(), &urgency);
+        assert_eq!(names(next), ["Later", "Late"]);
+        assert_eq!(
+            names(find("waiting").select(&task_list, &Query::All, &urgency)),
+            ["Someday"]
+        );
+
+        let report = Report {
+            columns: vec![Column::Id, Column::Priority, Column::Description],
+            limit: Some(1),
+            ..find("next").clone()
+        };
+        let shown = report.select(&task_list, &Query::All, &urgency);
+        let lines = report.render(&shown, &task_list, &urgency);
+        assert_eq!(lines, ["ID  PRIORITY  DESCRIPTION", "2   critical  Later"]);
+        assert!(report.render(&[], &task_list, &urgency).is_empty());
+        let next = find("next");
+        let lines = next.render(&shown, &task_list, &urgency);
+        assert!(lines[0].contains("DUE  ") && lines[0].contains("URGENCY"));
+        assert!(lines[1].contains(" 15.3 "));
+    }
+}
@@ -0,0 +1,289 @@
+//! Sort specs such as `due+,priority-,created+`: keys tried in turn, each
+//! ascending (`+`) or descending (`-`), with tasks that lack the value
+//! placed last unless the key says `:none-first`.
+
+use crate::urgency::{Scores, Urgency};
+use crate::{Task, TaskList};
+use std::cmp::Ordering;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortKey {
+    Priority,
+    Status,
+    DueDate,
+    Project,
+    Tags,
+    Created,
+    Modified,
+    Completed,
+    Urgency,
+}
+
+impl SortKey {
+    pub const ALL: [SortKey; 9] = [
+        SortKey::Priority,
+        SortKey::Status,
+        SortKey::DueDate,
+        SortKey::Project,
+        SortKey::Tags,
+        SortKey::Created,
+        SortKey::Modified,
+        SortKey::Completed,
+        SortKey::Urgency,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SortKey::Priority => "priority",
+            SortKey::Status => "status",
+            SortKey::DueDate => "due date",
+            SortKey::Project => "project",
+            SortKey::Tags => "tags",
+            SortKey::Created => "created",
+            SortKey::Modified => "modified",
+            SortKey::Completed => "completed",
+            SortKey::Urgency => "urgency",
+        }
+    }
+
+    /// Accepts the key's name, with `-` or `_` for the space, or `due`.
+    pub fn parse(name: &str) -> Option<SortKey> {
+        let name = name.trim().to_lowercase().replace(['-', '_'], " ");
+        if name == "due" {
+            return Some(SortKey::DueDate);
+        }
+        SortKey::ALL.into_iter().find(|key| key.name() == name)
+    }
+
+    /// The latest change or completion and the most urgent task come first
+    /// unless asked otherwise; every other key starts from the smallest
+    /// value (critical priority first).
+    pub fn descending_by_default(self) -> bool {
+        matches!(
+            self,
+            SortKey::Modified | SortKey::Completed | SortKey::Urgency
+        )
+    }
+
+    fn is_missing(self, task: &Task) -> bool {
+        match self {
+            SortKey::Priority => task.priority.is_none(),
+            SortKey::Status | SortKey::Urgency => false,
+            SortKey::DueDate => task.due_date.is_none(),
+            SortKey::Project => task.project.is_none(),
+            SortKey::Tags => task.tags.is_empty(),
+            SortKey::Created => task.created_at.is_none(),
+            SortKey::Modified => task.modified_at.is_none(),
+            SortKey::Completed => task.completed_at.is_none(),
+        }
+    }
+
+    /// Ascending order of two tasks that both have a value.
+    fn compare_values(self, a: &Task, b: &Task, scores: &Scores) -> Ordering {
+        match self {
+            SortKey::Priority => a.priority.cmp(&b.priority),
+            SortKey::Status => a.status.cmp(&b.status),
+            SortKey::DueDate => (a.due_date, a.due_at).cmp(&(b.due_date, b.due_at)),
+            SortKey::Project => a.project.cmp(&b.project),
+            SortKey::Tags => a.tags.cmp(&b.tags),
+            SortKey::Created => a.created_at.cmp(&b.created_at),
+            SortKey::Modified => a.modified_at.cmp(&b.modified_at),
+            SortKey::Completed => a.completed_at.cmp(&b.completed_at),
+            SortKey::Urgency => {
+                let score = |task: &Task| scores.get(&task.uuid).copied().unwrap_or(0.0);
+                score(a).total_cmp(&score(b))
+            }
+        }
+    }
+}
+
+/// One key of a spec, with its direction and where missing values go.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SortTerm {
+    pub key: SortKey,
+    pub descending: bool,
+    pub missing_first: bool,
+}
+
+impl SortTerm {
+    pub fn new(key: SortKey) -> Self {
+        Self {
+            key,
+            descending: key.descending_by_default(),
+            missing_first: false,
+        }
+    }
+
+    pub fn compare(&self, a: &Task, b: &Task, scores: &Scores) -> Ordering {
+        match (self.key.is_missing(a), self.key.is_missing(b)) {
+            (true, true) => Ordering::Equal,
+            (true, false) if self.missing_first => Ordering::Less,
+            (true, false) => Ordering::Greater,
+            (false, true) if self.missing_first => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) if self.descending => self.key.compare_values(b, a, scores),
+            (false, false) => self.key.compare_values(a, b, scores),
+        }
+    }
+
+    fn parse(text: &str) -> Result<SortTerm, String> {
+        let (rest, missing_first) = if let Some(rest) = text.strip_suffix(":none-first") {
+            (rest, Some(true))
+        } else if let Some(rest) = text.strip_suffix(":none-last") {
+            (rest, Some(false))
+        } else {
+            (text, None)
+        };
+        let (name, descending) = if let Some(name) = rest.strip_suffix('+') {
+            (name, Some(false))
+        } else if let Some(name) = rest.strip_suffix('-') {
+            (name, Some(true))
+        } else {
+            (rest, None)
+        };
+        if name.contains(':') {
+            return Err(format!(
+                "unknown placement in '{}' (expected :none-first or :none-last)",
+                text
+            ));
+        }
+        let key = SortKey::parse(name).ok_or_else(|| format!("unknown sort key '{}'", name))?;
+        let mut term = SortTerm::new(key);
+        term.descending = descending.unwrap_or(term.descending);
+        term.missing_first = missing_first.unwrap_or(false);
+        Ok(term)
+    }
+}
+
+impl fmt::Display for SortTerm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self.key {
+            SortKey::DueDate => "due",
+            key => key.name(),
+        };
+        write!(f, "{}{}", name, if self.descending { '-' } else { '+' })?;
+        if self.missing_first {
+            write!(f, ":none-first")?;
+        }
+        Ok(())
+    }
+}
+
+/// An empty spec leaves tasks in the order they are in.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SortSpec {
+    pub terms: Vec<SortTerm>,
+}
+
+impl SortSpec {
+    /// Sorts on `keys` in turn, each in its default direction.
+    pub fn of(keys: &[SortKey]) -> Self {
+        Self {
+            terms: keys.iter().copied().map(SortTerm::new).collect(),
+        }
+    }
+
+    /// Parses comma-separated terms such as `due+`, `priority-` or
+    /// `project:none-first`.
+    pub fn parse(input: &str) -> Result<SortSpec, String> {
+        let terms = input
+            .split(',')
+            .map(|term| term.trim().to_lowercase())
+            .filter(|term| !term.is_empty())
+            .map(|term| SortTerm::parse(&term))
+            .collect::<Result<Vec<_>, _>>()?;
+        if terms.is_empty() {
+            return Err("a sort needs at least one key".to_string());
+        }
+        Ok(SortSpec { terms })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Ties on every key compare equal, so a stable sort keeps their order.
+    /// `scores` is only consulted for urgency.
+    pub fn compare(&self, a: &Task, b: &Task, scores: &Scores) -> Ordering {
+        self.terms
+            .iter()
+            .map(|term| term.compare(a, b, scores))
+            .find(|order| order.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+
+    /// The scores `compare` needs for `task_list`; none unless the spec
+    /// sorts on urgency.
+    pub fn scores(&self, task_list: &TaskList, urgency: &Urgency) -> Scores {
+        if self.terms.iter().any(|term| term.key == SortKey::Urgency) {
+            urgency.scores(task_list)
+        } else {
+            Scores::new()
+        }
+    }
+
+    /// Orders `tasks`, all from `task_list`, by the spec.
+    pub fn sort(&self, tasks: &mut [&Task], task_list: &TaskList, urgency: &Urgency) {
+        let scores = self.scores(task_list, urgency);
+        tasks.sort_by(|a, b| self.compare(a, b, &scores));
+    }
+}
+
+impl From<SortKey> for SortSpec {
+    fn from(key: SortKey) -> Self {
+        SortSpec::of(&[key])
+    }
+}
+
+impl fmt::Display for SortSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let terms: Vec<String> = self.terms.iter().map(SortTerm::to_string).collect();
+        write!(f, "{}", terms.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Priority;
+
+    #[test]
+    fn test_parse_and_compare_specs() {
+        let spec = SortSpec::parse("due+, Priority-, project:none-first, modified").unwrap();
+        assert_eq!(
+            spec.to_string(),
+            "due+,priority-,project+:none-first,modified-"
+        );
+        assert_eq!(SortSpec::parse(&spec.to_string()).unwrap(), spec);
+        assert_eq!(SortSpec::parse("due-date-").unwrap().to_string(), "due-");
+        assert!(SortSpec::parse(" , ").is_err());
+        assert_eq!(
+            SortSpec::parse("due+,size").unwrap_err(),
+            "unknown sort key 'size'"
+        );
+        assert!(SortSpec::parse("due:none-middle").is_err());
+
+        let task = |description: &str, priority: Priority| {
+            let mut task = Task::new(description);
+            task.priority = priority;
+            task
+        };
+        let mut tasks = vec![
+            task("a", Priority::Medium),
+            task("b", Priority::None),
+            task("c", Priority::Critical),
+            task("d", Priority::Medium),
+        ];
+        let order =
+            |tasks: &[Task]| -> String { tasks.iter().map(|t| &t.description[..]).collect() };
+        let spec = SortSpec::parse("priority-").unwrap();
+        tasks.sort_by(|a, b| spec.compare(a, b, &Scores::new()));
+        assert_eq!(order(&tasks), "adcb");
+        let spec = SortSpec::parse("priority+:none-first").unwrap();
+        tasks.sort_by(|a, b| spec.compare(a, b, &Scores::new()));
+        assert_eq!(order(&tasks), "bcad");
+        tasks.sort_by(|a, b| SortSpec::default().compare(a, b, &Scores::new()));
+        assert_eq!(order(&tasks), "bcad");
+    }
+}
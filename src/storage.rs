@@ -0,0 +1,1297 @@
+//! Persistence: the versioned, pipe-delimited `tasks.txt` format and JSON export.
+
+use crate::due::Due;
+use crate::error::{Result, TaskError};
+use crate::history::{Op, Snapshot};
+use crate::journal::{self, Change, Event};
+use crate::list::TaskList;
+use crate::lock::{FileLock, FileStamp};
+use crate::priority::{Priority, PriorityScale};
+use crate::task::{Status, Task, Transition, is_valid_name, is_valid_project};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime};
+use std::collections::HashSet;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::Path;
+use uuid::Uuid;
+
+pub const DEFAULT_FILE: &str = "tasks.txt";
+pub const DEFAULT_JSON_FILE: &str = "tasks.json";
+
+/// Version written by `save_tasks`. Files without a version header are
+/// version 1: `description|completed|priority|due[|id|uuid]`, unescaped.
+/// Version 3 replaced the `1`/`0` completed flag with a status name, and
+/// version 4 the 1-5 priority number with a level name.
+pub const FORMAT_VERSION: u32 = 4;
+
+/// First line of every file written since version 2.
+const VERSION_PREFIX: &str = "# task-format: ";
+/// Comment line that carries the id counter; older loaders skip it.
+const NEXT_ID_PREFIX: &str = "# next-id: ";
+
+/// Formats one task as an `id|uuid|status|priority|due|description` line,
+/// followed by `key=value` fields for optional data. `|`, `\` and line
+/// breaks inside text are backslash-escaped.
+pub fn format_line(task: &Task) -> String {
+    let priority = match task.priority {
+        Priority::None => String::new(),
+        level => level.to_string(),
+    };
+    let due = task.due_date.map(|d| d.to_string()).unwrap_or_default();
+    let mut line = format!(
+        "{}|{}|{}|{}|{}|{}",
+        task.id,
+        task.uuid,
+        task.status,
+        priority,
+        due,
+        escape(&task.description)
+    );
+    if let Some(at) = task.due_at {
+        line.push_str(&format!("|due_at={}", at.to_rfc3339()));
+    }
+    for (key, date) in [
+        ("scheduled", task.scheduled),
+        ("wait", task.wait),
+        ("until", task.until),
+    ] {
+        if let Some(date) = date {
+            line.push_str(&format!("|{}={}", key, date));
+        }
+    }
+    if let Some(title) = &task.title {
+        line.push_str("|title=");
+        line.push_str(&escape(title));
+    }
+    if !task.tags.is_empty() {
+        line.push_str("|tags=");
+        line.push_str(&task.tags.join(","));
+    }
+    if let Some(project) = &task.project {
+        line.push_str("|project=");
+        line.push_str(project);
+    }
+    if let Some(parent) = task.parent {
+        line.push_str(&format!("|parent={}", parent));
+    }
+    if !task.depends.is_empty() {
+        let depends: Vec<String> = task.depends.iter().map(Uuid::to_string).collect();
+        line.push_str(&format!("|depends={}", depends.join(",")));
+    }
+    if let Some(recur) = &task.recur {
+        line.push_str(&format!("|recur={}", recur));
+    }
+    if let Some(series) = task.series {
+        line.push_str(&format!("|series={}", series));
+    }
+    for (key, at) in [
+        ("created_at", task.created_at),
+        ("modified_at", task.modified_at),
+        ("completed_at", task.completed_at),
+    ] {
+        if let Some(at) = at {
+            line.push_str(&format!("|{}={}", key, at.to_rfc3339()));
+        }
+    }
+    if !task.transitions.is_empty() {
+        let transitions: Vec<String> = task
+            .transitions
+            .iter()
+            .map(|t| format!("{}@{}", t.status, t.at.to_rfc3339()))
+            .collect();
+        line.push_str(&format!("|transitions={}", transitions.join(",")));
+    }
+    line
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '|' => escaped.push_str("\\|"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Splits a line on unescaped `|` and unescapes each field.
+fn split_fields(line: &str) -> std::result::Result<Vec<String>, String> {
+    let mut fields = vec![String::new()];
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        let field = fields.last_mut().unwrap();
+        match c {
+            '|' => fields.push(String::new()),
+            '\\' => match chars.next() {
+                Some('\\') => field.push('\\'),
+                Some('|') => field.push('|'),
+                Some('n') => field.push('\n'),
+                Some('r') => field.push('\r'),
+                Some(other) => return Err(format!("invalid escape '\\{}'", other)),
+                None => return Err("line ends with a lone '\\'".to_string()),
+            },
+            c => field.push(c),
+        }
+    }
+    Ok(fields)
+}
+
+/// Backups `save_tasks` keeps when no other count is configured.
+pub const DEFAULT_BACKUPS: usize = 3;
+
+/// Writes every task to `filename` in the current format (older files are
+/// upgraded this way), keeping `DEFAULT_BACKUPS` backups.
+pub fn save_tasks(filename: &str, task_list: &TaskList) -> Result<()> {
+    save_tasks_with(filename, task_list, DEFAULT_BACKUPS)
+}
+
+/// Writes every task to `filename` without ever leaving it half-written:
+/// the data goes to a temporary file that is synced and then renamed over
+/// the old one. The previous contents become `filename.1`, older backups
+/// shift up, and at most `backups` of them are kept; any numbered higher,
+/// e.g. from before the count was lowered, are removed.
+pub fn save_tasks_with(filename: &str, task_list: &TaskList, backups: usize) -> Result<()> {
+    save_tasks_checked(filename, task_list, backups, None, None).map(|_| ())
+}
+
+/// Like `save_tasks_with`, holding the file's exclusive lock throughout.
+/// With `loaded` set, fails with `TaskError::ChangedOnDisk` instead of
+/// overwriting if the file no longer matches that stamp. `changes` (the
+/// operations since the file was loaded) are appended to the journal; see
+/// `journal::record`. Returns the stamp of the newly written file.
+pub fn save_tasks_checked(
+    filename: &str,
+    task_list: &TaskList,
+    backups: usize,
+    loaded: Option<&FileStamp>,
+    changes: Option<&[Op]>,
+) -> Result<FileStamp> {
+    save_tasks_then(
+        filename,
+        task_list,
+        backups,
+        loaded,
+        changes,
+        &mut || Ok(()),
+    )
+}
+
+/// Like `save_tasks_checked`, running `then` once the tasks are written and
+/// before the lock is released, for files kept next to the task file (the
+/// undo history) that must change together with it.
+pub fn save_tasks_then(
+    filename: &str,
+    task_list: &TaskList,
+    backups: usize,
+    loaded: Option<&FileStamp>,
+    changes: Option<&[Op]>,
+    then: &mut dyn FnMut() -> Result<()>,
+) -> Result<FileStamp> {
+    let _lock = FileLock::exclusive(filename)?;
+    if let Some(loaded) = loaded
+        && !loaded.matches(filename)?
+    {
+        return Err(TaskError::ChangedOnDisk(filename.to_string()));
+    }
+    let base = match changes {
+        Some(_) if !Path::new(&journal::journal_path(filename)).exists() => journal_base(filename)?,
+        _ => None,
+    };
+    write_tasks(filename, task_list, backups)?;
+    journal::record(filename, base, task_list, changes)?;
+    let stamp = FileStamp::read(filename)?;
+    then()?;
+    Ok(stamp)
+}
+
+/// The current contents of a file that is about to get its first journal
+/// entry, as of when it was last written.
+fn journal_base(filename: &str) -> Result<Option<Event>> {
+    let modified = match fs::metadata(filename).and_then(|m| m.modified()) {
+        Ok(modified) => modified,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(TaskError::io(filename, e)),
+    };
+    let scan = scan(filename, &PriorityScale::default())?;
+    let mut event = Event::new(Change::Snapshot(Snapshot {
+        tasks: scan.tasks,
+        next_id: scan.next_id,
+    }));
+    event.at = modified.into();
+    Ok(Some(event))
+}
+
+fn write_tasks(filename: &str, task_list: &TaskList, backups: usize) -> Result<()> {
+    let mut contents = format!("{}{}\n", VERSION_PREFIX, FORMAT_VERSION);
+    contents.push_str(&format!("{}{}\n", NEXT_ID_PREFIX, task_list.next_id()));
+    for task in task_list {
+        contents.push_str(&format_line(task));
+        contents.push('\n');
+    }
+    rotate_backups(filename, backups)?;
+    write_atomic(filename, &contents)
+}
+
+/// Replaces `filename` with `contents` via a synced temporary file.
+pub(crate) fn write_atomic(filename: &str, contents: &str) -> Result<()> {
+    let temp = format!("{}.tmp", filename);
+    let write = || -> io::Result<()> {
+        let mut file = File::create(&temp)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(&temp, filename)?;
+        // make the rename itself durable; not every platform can open a directory
+        let dir = Path::new(filename)
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty());
+        if let Ok(dir) = File::open(dir.unwrap_or(Path::new("."))) {
+            let _ = dir.sync_all();
+        }
+        Ok(())
+    };
+    write().map_err(|e| {
+        let _ = fs::remove_file(&temp);
+        TaskError::io(filename, e)
+    })
+}
+
+/// Path of the `number`th most recent backup of `filename`.
+pub fn backup_path(filename: &str, number: usize) -> String {
+    format!("{}.{}", filename, number)
+}
+
+fn rotate_backups(filename: &str, backups: usize) -> Result<()> {
+    for backup in list_backups(filename)? {
+        if backup.number > backups {
+            fs::remove_file(&backup.path).map_err(|e| TaskError::io(&backup.path, e))?;
+        }
+    }
+    if backups == 0 || !Path::new(filename).exists() {
+        return Ok(());
+    }
+    for number in (1..backups).rev() {
+        let from = backup_path(filename, number);
+        if Path::new(&from).exists() {
+            let to = backup_path(filename, number + 1);
+            fs::rename(&from, &to).map_err(|e| TaskError::io(&from, e))?;
+        }
+    }
+    let first = backup_path(filename, 1);
+    let copy = || -> io::Result<()> {
+        let saved_at = fs::metadata(filename)?.modified()?;
+        fs::copy(filename, &first)?;
+        // keep the time the backed-up contents were saved, not copied
+        File::options()
+            .write(true)
+            .open(&first)?
+            .set_modified(saved_at)
+    };
+    copy().map_err(|e| TaskError::io(&first, e))
+}
+
+/// A backup file found next to the task file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backup {
+    /// 1 is the most recent.
+    pub number: usize,
+    pub path: String,
+    /// When the backed-up contents were saved.
+    pub saved_at: DateTime<Local>,
+}
+
+/// Lists the backups of `filename`, most recent first.
+pub fn list_backups(filename: &str) -> Result<Vec<Backup>> {
+    let path = Path::new(filename);
+    let dir = path.parent().filter(|p| !p.as_os_str().is_empty());
+    let dir = dir.unwrap_or(Path::new("."));
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return Ok(Vec::new());
+    };
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(TaskError::io(&dir.display().to_string(), e)),
+    };
+    let mut backups = Vec::new();
+    for entry in entries {
+        let entry = entry.map_err(|e| TaskError::io(filename, e))?;
+        let entry_name = entry.file_name();
+        let number = entry_name
+            .to_str()
+            .and_then(|n| n.strip_prefix(name))
+            .and_then(|n| n.strip_prefix('.'))
+            .and_then(|n| n.parse::<usize>().ok());
+        let Some(number) = number.filter(|&n| n > 0) else {
+            continue;
+        };
+        let path = backup_path(filename, number);
+        let saved_at = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .map_err(|e| TaskError::io(&path, e))?;
+        backups.push(Backup {
+            number,
+            path,
+            saved_at: saved_at.into(),
+        });
+    }
+    backups.sort_by_key(|b| b.number);
+    Ok(backups)
+}
+
+/// Makes backup `number` the current task list and returns it. The contents
+/// being replaced are backed up in turn, so a restore can be undone.
+pub fn restore_backup(filename: &str, number: usize, backups: usize) -> Result<TaskList> {
+    let path = backup_path(filename, number);
+    if !Path::new(&path).exists() {
+        return Err(TaskError::NotFound(format!(
+            "No backup {} ({}).",
+            number, path
+        )));
+    }
+    let _lock = FileLock::exclusive(filename)?;
+    let restored = scan(&path, &PriorityScale::default())?;
+    if !restored.rejected.is_empty() {
+        return Err(rejection_error(&restored.rejected));
+    }
+    // ids handed out since the backup was taken must stay retired
+    let current_next_id =
+        scan(filename, &PriorityScale::default()).map_or(1, |current| current.next_id);
+    let next_id = restored.next_id.max(current_next_id);
+    let mut task_list = TaskList::with_next_id(restored.tasks, next_id)?;
+    write_tasks(filename, &task_list, backups)?;
+    journal::record(filename, None, &task_list, None)?;
+    task_list.mark_saved();
+    Ok(task_list)
+}
+
+/// How `load_tasks_with` treats lines it cannot parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LoadMode {
+    /// Refuse to load the file at all.
+    #[default]
+    Strict,
+    /// Load the good lines and move the bad ones to `<file>.rejected`.
+    Lenient,
+}
+
+/// A line `load_tasks_with` could not parse.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RejectedLine {
+    /// 1-based line number in the task file.
+    pub line: usize,
+    pub text: String,
+    pub reason: String,
+}
+
+impl fmt::Display for RejectedLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+#[derive(Debug)]
+pub struct LoadReport {
+    pub tasks: TaskList,
+    /// Lines that were quarantined (always empty in strict mode).
+    pub rejected: Vec<RejectedLine>,
+    /// The file as it was read, for `save_tasks_checked`.
+    pub stamp: FileStamp,
+}
+
+/// Where lenient loading quarantines the lines it drops from `filename`.
+pub fn rejected_path(filename: &str) -> String {
+    format!("{}.rejected", filename)
+}
+
+/// Loads `filename` strictly; a file that does not exist yet is an empty list.
+pub fn load_tasks(filename: &str) -> Result<TaskList> {
+    load_tasks_with(filename, LoadMode::Strict).map(|report| report.tasks)
+}
+
+/// Loads `filename`, validating every line.
+///
+/// In strict mode any malformed line fails the whole load with a
+/// `TaskError::Parse` naming every bad line. In lenient mode those lines
+/// are appended to `rejected_path(filename)` first, so the next save
+/// cannot lose them.
+///
+/// The file is read under a shared lock, so it never sees a save that is
+/// still being checked against its stamp.
+pub fn load_tasks_with(filename: &str, mode: LoadMode) -> Result<LoadReport> {
+    let _lock = lock_existing(filename)?;
+    let scanned = scan(filename, &PriorityScale::default())?;
+    if !scanned.rejected.is_empty() {
+        match mode {
+            LoadMode::Strict => return Err(rejection_error(&scanned.rejected)),
+            LoadMode::Lenient => quarantine(&rejected_path(filename), &scanned.rejected)?,
+        }
+    }
+    let mut tasks = TaskList::with_next_id(scanned.tasks, scanned.next_id)?;
+    // quarantined lines are still in the file until the next save
+    if scanned.rejected.is_empty() {
+        tasks.mark_saved();
+    }
+    Ok(LoadReport {
+        tasks,
+        rejected: scanned.rejected,
+        stamp: scanned.stamp,
+    })
+}
+
+/// Lists every malformed line in `filename` without changing anything.
+pub fn check_tasks(filename: &str) -> Result<Vec<RejectedLine>> {
+    let _lock = lock_existing(filename)?;
+    scan(filename, &PriorityScale::default()).map(|scanned| scanned.rejected)
+}
+
+/// A shared lock, skipped for a file that does not exist yet so that merely
+/// listing an empty directory leaves no lock file behind.
+fn lock_existing(filename: &str) -> Result<Option<FileLock>> {
+    if Path::new(filename).exists() {
+        FileLock::shared(filename).map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+struct Scan {
+    tasks: Vec<Task>,
+    next_id: u32,
+    rejected: Vec<RejectedLine>,
+    stamp: FileStamp,
+    /// Lines whose priority number shares its level with another number
+    /// on the scale; see `PriorityScale::shared`.
+    merged: usize,
+}
+
+/// Reads every line of `filename`, taking numeric priorities on `scale`.
+fn scan(filename: &str, scale: &PriorityScale) -> Result<Scan> {
+    let read = || -> io::Result<(String, FileStamp)> {
+        let mut file = File::open(filename)?;
+        let modified = file.metadata()?.modified()?;
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents)?;
+        let stamp = FileStamp::new(modified, &contents);
+        let text = String::from_utf8(contents)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok((text, stamp))
+    };
+    let (text, stamp) = match read() {
+        Ok(read) => read,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return Ok(Scan {
+                tasks: Vec::new(),
+                next_id: 1,
+                rejected: Vec::new(),
+                stamp: FileStamp::missing(),
+                merged: 0,
+            });
+        }
+        Err(e) => return Err(TaskError::io(filename, e)),
+    };
+    let mut tasks = Vec::new();
+    let mut next_id = 1;
+    let mut version = 1;
+    let mut rejected = Vec::new();
+    let mut merged = 0;
+    let distinct = scale.distinct();
+    for (number, line) in text.lines().enumerate() {
+        let reject = |reason: String| RejectedLine {
+            line: number + 1,
+            text: line.to_string(),
+            reason,
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(value) = line.strip_prefix(VERSION_PREFIX) {
+            version = match value.trim().parse() {
+                Ok(v) if (1..=FORMAT_VERSION).contains(&v) => v,
+                _ => {
+                    return Err(TaskError::Parse {
+                        line: number + 1,
+                        message: format!(
+                            "unsupported task file format '{}' (this build reads 1-{})",
+                            value.trim(),
+                            FORMAT_VERSION
+                        ),
+                    });
+                }
+            };
+            continue;
+        }
+        if let Some(value) = line.strip_prefix(NEXT_ID_PREFIX) {
+            match value.trim().parse() {
+                Ok(id) => next_id = id,
+                Err(_) => rejected.push(reject(format!("invalid next id '{}'", value.trim()))),
+            }
+            continue;
+        }
+        if line.starts_with('#') {
+            continue;
+        }
+        let parse = |scale| match version {
+            1 => parse_legacy_line_on(line, scale),
+            _ => parse_line_on(line, scale),
+        };
+        match parse(scale) {
+            // no id would be left to follow it
+            Ok(task) if task.id == u32::MAX => {
+                rejected.push(reject(format!("task id {} is out of range", task.id)))
+            }
+            Ok(task) => {
+                // only a shared number makes the line fail without them
+                if parse(&distinct).is_err() {
+                    merged += 1;
+                }
+                tasks.push(task);
+            }
+            Err(reason) => rejected.push(reject(reason)),
+        }
+    }
+    Ok(Scan {
+        tasks,
+        next_id,
+        rejected,
+        stamp,
+        merged,
+    })
+}
+
+fn rejection_error(rejected: &[RejectedLine]) -> TaskError {
+    let details: Vec<String> = rejected.iter().map(|r| r.to_string()).collect();
+    TaskError::Parse {
+        line: rejected[0].line,
+        message: format!(
+            "{} malformed line(s): {}",
+            rejected.len(),
+            details.join("; ")
+        ),
+    }
+}
+
+/// Appends rejected lines (each after a `# line N: reason` comment) unless
+/// an identical line is already quarantined.
+fn quarantine(path: &str, rejected: &[RejectedLine]) -> Result<()> {
+    let existing = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(TaskError::io(path, e)),
+    };
+    let known: HashSet<&str> = existing.lines().collect();
+    let fresh: Vec<&RejectedLine> = rejected
+        .iter()
+        .filter(|r| !known.contains(r.text.as_str()))
+        .collect();
+    if fresh.is_empty() {
+        return Ok(());
+    }
+    let append = || -> io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        for r in fresh {
+            writeln!(file, "# {}", r)?;
+            writeln!(file, "{}", r.text)?;
+        }
+        file.flush()
+    };
+    append().map_err(|e| TaskError::io(path, e))
+}
+
+/// Parses a line in the current format (see `format_line`).
+pub fn parse_line(line: &str) -> std::result::Result<Task, String> {
+    parse_line_on(line, &PriorityScale::default())
+}
+
+fn parse_line_on(line: &str, scale: &PriorityScale) -> std::result::Result<Task, String> {
+    let fields = split_fields(line)?;
+    if fields.len() < 6 {
+        return Err(format!(
+            "expected at least 6 '|'-separated fields, found {}",
+            fields.len()
+        ));
+    }
+    let mut task = core_fields(
+        &fields[5],
+        &fields[2],
+        &fields[3],
+        &fields[4],
+        Some(&fields[0]),
+        Some(&fields[1]),
+        scale,
+    )?;
+    for extra in &fields[6..] {
+        let (key, value) = extra
+            .split_once('=')
+            .ok_or_else(|| format!("expected key=value, found '{}'", extra))?;
+        match key {
+            "due_at" => {
+                let at = DateTime::parse_from_rfc3339(value)
+                    .map_err(|_| format!("invalid due time '{}'", value))?;
+                task.due_at = Some(at);
+            }
+            // written before due times had a time zone: local time
+            "due_time" => {
+                let time = NaiveTime::parse_from_str(value, "%H:%M")
+                    .map_err(|_| format!("invalid due time '{}'", value))?;
+                let date = task.due_date.ok_or("due_time without a due date")?;
+                let due = Due {
+                    date,
+                    time: Some(time),
+                    offset: None,
+                };
+                task.due_at = due.at();
+            }
+            "scheduled" | "wait" | "until" => {
+                let date = NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                    .map_err(|_| format!("invalid {} date '{}'", key, value))?;
+                match key {
+                    "scheduled" => task.scheduled = Some(date),
+                    "wait" => task.wait = Some(date),
+                    _ => task.until = Some(date),
+                }
+            }
+            "title" => task.title = Some(value.to_string()),
+            "tags" => {
+                for tag in value.split(',') {
+                    if !is_valid_name(tag) {
+                        return Err(format!("invalid tag '{}'", tag));
+                    }
+                    task.add_tag(tag);
+                }
+            }
+            "project" if is_valid_project(value) => task.project = Some(value.to_lowercase()),
+            "project" => return Err(format!("invalid project '{}'", value)),
+            "parent" => {
+                let parent = Uuid::parse_str(value)
+                    .map_err(|_| format!("invalid parent uuid '{}'", value))?;
+                task.parent = Some(parent);
+            }
+            "depends" => {
+                for uuid in value.split(',') {
+                    let uuid = Uuid::parse_str(uuid)
+                        .map_err(|_| format!("invalid dependency uuid '{}'", uuid))?;
+                    task.depends.push(uuid);
+                }
+            }
+            "recur" => {
+                let recur = value
+                    .parse()
+                    .map_err(|e| format!("invalid recurrence: {}", e))?;
+                task.recur = Some(recur);
+            }
+            "series" => {
+                let series = Uuid::parse_str(value)
+                    .map_err(|_| format!("invalid series uuid '{}'", value))?;
+                task.series = Some(series);
+            }
+            "created_at" | "modified_at" | "completed_at" => {
+                let at = DateTime::parse_from_rfc3339(value)
+                    .map_err(|_| format!("invalid {} time '{}'", key, value))?
+                    .with_timezone(&Local);
+                match key {
+                    "created_at" => task.created_at = Some(at),
+                    "modified_at" => task.modified_at = Some(at),
+                    _ => task.completed_at = Some(at),
+                }
+            }
+            "transitions" => {
+                for entry in value.split(',') {
+                    let (status, at) = entry
+                        .split_once('@')
+                        .ok_or_else(|| format!("invalid transition '{}'", entry))?;
+                    let at = DateTime::parse_from_rfc3339(at)
+                        .map_err(|_| format!("invalid transition time '{}'", at))?;
+                    task.transitions.push(Transition {
+                        status: status.parse()?,
+                        at: at.with_timezone(&Local),
+                    });
+                }
+            }
+            other => return Err(format!("unknown field '{}'", other)),
+        }
+    }
+    Ok(task)
+}
+
+/// Parses a version 1 line. Descriptions there were written unescaped
+/// (newlines as `\n` or flattened to spaces), so a `|` inside one shifts
+/// the other columns; in that case the leading columns are rejoined.
+pub fn parse_legacy_line(line: &str) -> std::result::Result<Task, String> {
+    parse_legacy_line_on(line, &PriorityScale::default())
+}
+
+fn parse_legacy_line_on(line: &str, scale: &PriorityScale) -> std::result::Result<Task, String> {
+    let parts: Vec<&str> = line.split('|').collect();
+    if parts.len() < 2 {
+        return Err("expected at least 'description|completed'".to_string());
+    }
+    let parse = |split: usize| {
+        let (head, tail) = parts.split_at(split);
+        let field = |i: usize| tail.get(i).copied();
+        core_fields(
+            &head.join("|").replace("\\n", "\n"),
+            tail[0],
+            field(1).unwrap_or_default(),
+            field(2).unwrap_or_default(),
+            field(3),
+            field(4),
+            scale,
+        )
+    };
+    let direct = if parts.len() > 6 {
+        Err(format!(
+            "expected at most 6 '|'-separated fields, found {}",
+            parts.len()
+        ))
+    } else {
+        parse(1)
+    };
+    direct.or_else(|error| {
+        // widest trailing column set first: completed|priority|due|id|uuid
+        [5, 3, 2, 1]
+            .into_iter()
+            .filter(|&width| parts.len() > width + 1)
+            .find_map(|width| parse(parts.len() - width).ok())
+            .ok_or(error)
+    })
+}
+
+/// Validates the columns shared by every format version. The status column
+/// may still hold the `1`/`0` completed flag of versions 1 and 2, and the
+/// priority column a number on `scale`. A missing id or uuid is left as
+/// 0 / nil for `TaskList` to assign.
+fn core_fields(
+    description: &str,
+    status: &str,
+    priority: &str,
+    due: &str,
+    id: Option<&str>,
+    uuid: Option<&str>,
+    scale: &PriorityScale,
+) -> std::result::Result<Task, String> {
+    if description.trim().is_empty() {
+        return Err("empty description".to_string());
+    }
+    let mut task = Task::new(description);
+    task.status = match status {
+        "1" => Status::Done,
+        "0" => Status::Pending,
+        other => other.parse().map_err(|_| {
+            format!(
+                "status must be 0, 1 or a status name such as in-progress, found '{}'",
+                other
+            )
+        })?,
+    };
+    if !priority.is_empty() {
+        task.priority = scale.parse(priority)?;
+    }
+    if !due.is_empty() {
+        task.due_date = Some(
+            NaiveDate::parse_from_str(due, "%Y-%m-%d")
+                .map_err(|_| format!("invalid due date '{}' (expected YYYY-MM-DD)", due))?,
+        );
+    }
+    task.id = match id.filter(|s| !s.is_empty()) {
+        Some(id) => id
+            .parse()
+            .map_err(|_| format!("invalid task id '{}'", id))?,
+        None => 0,
+    };
+    task.uuid = match uuid.filter(|s| !s.is_empty()) {
+        Some(u) => u.parse().map_err(|_| format!("invalid uuid '{}'", u))?,
+        None => Uuid::nil(),
+    };
+    Ok(task)
+}
+
+pub fn to_json(task_list: &[Task]) -> Result<String> {
+    serde_json::to_string_pretty(task_list).map_err(|e| TaskError::Validation(e.to_string()))
+}
+
+pub fn export_json(filename: &str, task_list: &[Task]) -> Result<()> {
+    let json = to_json(task_list)?;
+    write_atomic(filename, &json)
+}
+
+/// Reads tasks exported by `export_json`. Nothing is returned unless the
+/// whole file parses, so callers can keep their current list on error.
+pub fn import_json(filename: &str) -> Result<Vec<Task>> {
+    let data = std::fs::read_to_string(filename).map_err(|e| TaskError::io(filename, e))?;
+    serde_json::from_str(&data).map_err(|e| TaskError::Parse {
+        line: e.line(),
+        message: e.to_string(),
+    })
+}
+
+/// The tasks `import_file` read.
+#[derive(Debug)]
+pub struct Imported {
+    pub tasks: Vec<Task>,
+    /// How many had a priority number that reads as the same level as
+    /// another one (5 and 4 on the 1-5 scale), so the difference is lost.
+    pub merged: usize,
+}
+
+/// Reads the tasks of another file for adding to a list: a JSON export, or
+/// a text file of any format version, such as the `description|completed|
+/// priority|due` files the older binaries wrote. Numeric priorities are
+/// taken on `scale`. Nothing is returned unless every task parses.
+pub fn import_file(filename: &str, scale: &PriorityScale) -> Result<Imported> {
+    let data = std::fs::read_to_string(filename).map_err(|e| TaskError::io(filename, e))?;
+    if !data.trim_start().starts_with('[') {
+        let scanned = scan(filename, scale)?;
+        if !scanned.rejected.is_empty() {
+            return Err(rejection_error(&scanned.rejected));
+        }
+        return Ok(Imported {
+            tasks: scanned.tasks,
+            merged: scanned.merged,
+        });
+    }
+    let parse_error = |e: serde_json::Error| TaskError::Parse {
+        line: e.line(),
+        message: e.to_string(),
+    };
+    let mut tasks: serde_json::Value = serde_json::from_str(&data).map_err(parse_error)?;
+    let shared: Vec<u64> = scale.shared().into_iter().map(|(n, _)| n.into()).collect();
+    let mut merged = 0;
+    for task in tasks.as_array_mut().into_iter().flatten() {
+        let Some(priority) = task.get_mut("priority") else {
+            continue;
+        };
+        if let Some(number) = priority.as_u64() {
+            merged += usize::from(shared.contains(&number));
+            let level = u32::try_from(number)
+                .ok()
+                .and_then(|number| scale.level(number))
+                .ok_or_else(|| {
+                    TaskError::Validation(format!(
+                        "invalid priority {} (expected {})",
+                        number,
+                        scale.range()
+                    ))
+                })?;
+            *priority = serde_json::to_value(level).map_err(parse_error)?;
+        }
+    }
+    Ok(Imported {
+        tasks: serde_json::from_value(tasks).map_err(parse_error)?,
+        merged,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn remove_task_files(filename: &str) {
+        std::fs::remove_file(filename).unwrap();
+        let _ = std::fs::remove_file(crate::lock::lock_path(filename));
+        let _ = std::fs::remove_file(crate::history::history_path(filename));
+        let _ = std::fs::remove_file(journal::journal_path(filename));
+        for backup in list_backups(filename).unwrap() {
+            std::fs::remove_file(&backup.path).unwrap();
+            let _ = std::fs::remove_file(crate::lock::lock_path(&backup.path));
+        }
+    }
+
+    #[test]
+    fn test_add_and_mark_complete() {
+        let mut tasks = Vec::new();
+        let mut task = Task::new("Test");
+        task.priority = Priority::High;
+        tasks.push(task);
+        assert_eq!(tasks.len(), 1);
+        tasks[0].status = Status::Done;
+        assert!(tasks[0].is_done());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let filename = "test_tasks.txt";
+        let mut task = Task::new("Test Save");
+        task.priority = Priority::Critical;
+        let tasks = TaskList::try_from(vec![task]).unwrap();
+        save_tasks(filename, &tasks).unwrap();
+        let loaded = load_tasks(filename).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded.get(0).unwrap().description, "Test Save");
+        remove_task_files(filename);
+    }
+
+    #[test]
+    fn test_ids_persist_across_save_and_load() {
+        let filename = "test_tasks_ids.txt";
+        let mut tasks = TaskList::new();
+        tasks.add(Task::new("one")).unwrap();
+        let two = tasks.add(Task::new("two")).unwrap();
+        tasks.remove(tasks.resolve(&two.to_string()).unwrap());
+        save_tasks(filename, &tasks).unwrap();
+        let mut loaded = load_tasks(filename).unwrap();
+        assert_eq!(loaded.get(0), tasks.get(0));
+        assert_eq!(loaded.add(Task::new("three")).unwrap(), 3);
+
+        // the largest id would leave none to follow, so the line is malformed
+        let mut last = Task::new("last");
+        last.id = u32::MAX;
+        let text = format!("# task-format: 4\n{}\n", format_line(&last));
+        fs::write(filename, text).unwrap();
+        match load_tasks(filename) {
+            Err(TaskError::Parse { line, message }) => {
+                assert_eq!(line, 2);
+                assert!(
+                    message.contains("4294967295 is out of range"),
+                    "{}",
+                    message
+                );
+            }
+            other => panic!("expected parse error, got {:?}", other),
+        }
+        remove_task_files(filename);
+    }
+
+    #[test]
+    fn test_import_errors_report_line() {
+        let filename = "test_tasks_bad.json";
+        std::fs::write(filename, "[\n  {\"description\": \"x\",\n  oops\n]").unwrap();
+        match import_json(filename) {
+            Err(TaskError::Parse { line, .. }) => assert_eq!(line, 3),
+            other => panic!("expected parse error, got {:?}", other),
+        }
+        std::fs::remove_file(filename).unwrap();
+        assert!(matches!(import_json(filename), Err(TaskError::Io { .. })));
+    }
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        assert!(load_tasks("test_tasks_missing.txt").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_line_reasons() {
+        assert!(
+            parse_legacy_line("no separator")
+                .unwrap_err()
+                .contains("at least")
+        );
+        assert!(
+            parse_legacy_line("x|2")
+                .unwrap_err()
+                .contains("status must be")
+        );
+        assert!(
+            parse_legacy_line("x|0|urgent")
+                .unwrap_err()
+                .contains("priority")
+        );
+        assert!(
+            parse_legacy_line("x|0||31-12-2026")
+                .unwrap_err()
+                .contains("due date")
+        );
+        assert!(
+            parse_legacy_line("a|b|0|||1|u|extra")
+                .unwrap_err()
+                .contains("at most")
+        );
+    }
+
+    #[test]
+    fn test_strict_and_lenient_loading() {
+        let filename = "test_tasks_corrupt.txt";
+        let rejected = rejected_path(filename);
+        let _ = std::fs::remove_file(&rejected);
+        std::fs::write(filename, "good|0||\nbroken line\n\nalso good|1|9|\n").unwrap();
+
+        match load_tasks(filename) {
+            Err(TaskError::Parse { line, message }) => {
+                assert_eq!(line, 2);
+                assert!(message.contains("line 2"));
+                assert!(message.contains("line 4"));
+            }
+            other => panic!("expected parse error, got {:?}", other),
+        }
+
+        assert_eq!(check_tasks(filename).unwrap().len(), 2);
+        assert!(!std::path::Path::new(&rejected).exists());
+
+        let report = load_tasks_with(filename, LoadMode::Lenient).unwrap();
+        assert_eq!(report.tasks.len(), 1);
+        assert_eq!(report.rejected.len(), 2);
+        // loading again does not duplicate the quarantined lines
+        load_tasks_with(filename, LoadMode::Lenient).unwrap();
+        let quarantined = std::fs::read_to_string(&rejected).unwrap();
+        assert_eq!(quarantined.matches("broken line").count(), 1);
+        assert!(quarantined.contains("# line 2: expected at least"));
+
+        remove_task_files(filename);
+        std::fs::remove_file(rejected).unwrap();
+    }
+
+    #[test]
+    fn test_parse_legacy_line_without_ids() {
+        let task = parse_legacy_line("Old task|1|2|2024-01-05").unwrap();
+        assert_eq!(task.status, Status::Done);
+        assert_eq!(task.id, 0);
+        assert!(task.uuid.is_nil());
+        let list = TaskList::try_from(vec![task]).unwrap();
+        assert_eq!(list.get(0).unwrap().id, 1);
+        assert!(!list.get(0).unwrap().uuid.is_nil());
+    }
+
+    #[test]
+    fn test_format_escapes_round_trip() {
+        let mut task = Task::new("a|b \\ c\nsecond line");
+        task.title = Some("x=y|z".to_string());
+        task.id = 4;
+        task.add_tag("infra");
+        task.add_tag("ops");
+        task.project = Some("work.backend".to_string());
+        task.parent = Some(Uuid::new_v4());
+        task.depends = vec![Uuid::new_v4(), Uuid::new_v4()];
+        task.recur = "FREQ=MONTHLY;BYDAY=-1FR".parse().ok();
+        task.series = Some(Uuid::new_v4());
+        task.due_date = NaiveDate::from_ymd_opt(2026, 10, 23);
+        task.due_at = DateTime::parse_from_rfc3339("2026-10-23T17:30:00+02:00").ok();
+        task.wait = NaiveDate::from_ymd_opt(2026, 10, 20);
+        task.until = NaiveDate::from_ymd_opt(2026, 11, 1);
+        let started = DateTime::parse_from_rfc3339("2026-10-18T09:00:00+00:00").unwrap();
+        task.set_status(Status::InProgress, started.with_timezone(&Local));
+        task.set_status(Status::Waiting, Local::now());
+        task.created_at = Some(started.with_timezone(&Local));
+        let line = format_line(&task);
+        assert!(!line.contains('\n'));
+        assert!(line.contains("|tags=infra,ops|project=work.backend|parent="));
+        assert_eq!(parse_line(&line).unwrap(), task);
+        assert!(
+            parse_line("1||0|||x|tags=a,+b")
+                .unwrap_err()
+                .contains("tag")
+        );
+        assert!(parse_line("1||0|||x|project=a..b").is_err());
+        assert!(parse_line("1||0|||x|recur=fortnightly").is_err());
+        assert!(parse_line("1||0|||bad \\q").unwrap_err().contains("escape"));
+        assert!(
+            parse_line("1||0|||x|colour=red")
+                .unwrap_err()
+                .contains("unknown")
+        );
+    }
+
+    #[test]
+    fn test_legacy_files_upgrade_on_save() {
+        let filename = "test_tasks_legacy.txt";
+        // advTodo (flattened, no due), advTodos (\n escapes), and a 6-column line
+        std::fs::write(
+            filename,
+            "Call Bob|0|2\nLine one\\nline two|1||2024-01-05\nKeep|0|||7|\nA|B|0|3|\n",
+        )
+        .unwrap();
+        let loaded = load_tasks(filename).unwrap();
+        let descriptions: Vec<&str> = loaded.iter().map(|t| t.description.as_str()).collect();
+        assert_eq!(
+            descriptions,
+            ["Call Bob", "Line one\nline two", "Keep", "A|B"]
+        );
+        assert_eq!(loaded.get(2).unwrap().id, 7);
+        assert_eq!(loaded.get(3).unwrap().priority, Priority::Medium);
+
+        save_tasks(filename, &loaded).unwrap();
+        let text = std::fs::read_to_string(filename).unwrap();
+        assert!(text.starts_with("# task-format: 4\n"));
+        assert!(text.contains("|medium||A\\|B"));
+        assert!(text.contains("|done||2024-01-05|Line one"));
+        assert_eq!(load_tasks(filename).unwrap().tasks(), loaded.tasks());
+
+        // version 2 still had the completed flag
+        std::fs::write(filename, "# task-format: 2\n3||1|||Ship\n4||0|||Tell\n").unwrap();
+        let statuses: Vec<Status> = load_tasks(filename)
+            .unwrap()
+            .iter()
+            .map(|t| t.status)
+            .collect();
+        assert_eq!(statuses, [Status::Done, Status::Pending]);
+
+        std::fs::write(filename, "# task-format: 9\n").unwrap();
+        assert!(matches!(
+            load_tasks(filename),
+            Err(TaskError::Parse { line: 1, .. })
+        ));
+        remove_task_files(filename);
+    }
+
+    #[test]
+    fn test_legacy_priority_five_reads_as_low() {
+        let filename = "test_tasks_legacy_priorities.txt";
+        std::fs::write(filename, "A|0|1\nB|0|2\nC|0|3\nD|0|4\nE|0|5\n").unwrap();
+        let loaded = load_tasks(filename).unwrap();
+        let priorities: Vec<Priority> = loaded.iter().map(|t| t.priority).collect();
+        use Priority::{Critical, High, Low, Medium};
+        assert_eq!(priorities, [Critical, High, Medium, Low, Low]);
+        save_tasks(filename, &loaded).unwrap();
+        let reloaded = load_tasks(filename).unwrap();
+        assert_eq!(reloaded.tasks(), loaded.tasks());
+        remove_task_files(filename);
+
+        // an import counts the tasks whose 5 became the same low as a 4
+        std::fs::write(filename, "D|0|4\nE|0|5\nF|1|5\n").unwrap();
+        let imported = import_file(filename, &PriorityScale::default()).unwrap();
+        assert_eq!(imported.tasks.len(), 3);
+        assert_eq!(imported.merged, 2);
+        let mut own_five = PriorityScale::default();
+        own_five.set(5, Priority::None);
+        assert_eq!(import_file(filename, &own_five).unwrap().merged, 0);
+        std::fs::remove_file(filename).unwrap();
+
+        let filename = "test_tasks_legacy_priorities.json";
+        let old = r#"[{"description": "x", "completed": false, "priority": 5, "due_date": null}]"#;
+        std::fs::write(filename, old).unwrap();
+        let imported = import_file(filename, &PriorityScale::default()).unwrap();
+        assert_eq!((imported.tasks[0].priority, imported.merged), (Low, 1));
+        std::fs::remove_file(filename).unwrap();
+    }
+
+    #[test]
+    fn test_save_then_runs_after_the_write() {
+        let filename = "test_tasks_then.txt";
+        let _ = fs::remove_file(filename);
+        let mut list = TaskList::new();
+        list.add(Task::new("Ship")).unwrap();
+        let mut seen = Vec::new();
+        let mut then = || {
+            let written = fs::read_to_string(filename).unwrap().contains("|Ship");
+            let lock = fs::File::open(crate::lock::lock_path(filename)).unwrap();
+            let locked = matches!(lock.try_lock_shared(), Err(fs::TryLockError::WouldBlock));
+            seen.push((written, locked));
+            Ok(())
+        };
+        save_tasks_then(filename, &list, 0, None, None, &mut then).unwrap();
+        assert_eq!(seen, [(true, true)]);
+        let failing = save_tasks_then(filename, &list, 0, None, None, &mut || {
+            Err(TaskError::Validation("history".to_string()))
+        });
+        assert!(matches!(failing, Err(TaskError::Validation(_))));
+        remove_task_files(filename);
+    }
+
+    #[test]
+    fn test_saves_rotate_backups() {
+        let filename = "test_tasks_backups.txt";
+        let mut tasks = TaskList::new();
+        for name in ["one", "two", "three", "four"] {
+            tasks.add(Task::new(name)).unwrap();
+            save_tasks_with(filename, &tasks, 2).unwrap();
+        }
+        assert!(!Path::new(&format!("{}.tmp", filename)).exists());
+        let backups = list_backups(filename).unwrap();
+        let numbers: Vec<usize> = backups.iter().map(|b| b.number).collect();
+        assert_eq!(numbers, [1, 2]);
+        assert_eq!(load_tasks(&backups[0].path).unwrap().len(), 3);
+        assert_eq!(load_tasks(&backups[1].path).unwrap().len(), 2);
+
+        let restored = restore_backup(filename, 2, 2).unwrap();
+        assert_eq!(restored.len(), 2);
+        assert_eq!(restored.next_id(), 5);
+        assert_eq!(load_tasks(filename).unwrap().len(), 2);
+        // the list that was replaced is now the newest backup
+        assert_eq!(load_tasks(&backup_path(filename, 1)).unwrap().len(), 4);
+        assert!(matches!(
+            restore_backup(filename, 9, 2),
+            Err(TaskError::NotFound(_))
+        ));
+
+        remove_task_files(filename);
+    }
+
+    #[test]
+    fn test_backup_edge_cases() {
+        let filename = "test_tasks_backup_edges.txt";
+        let _ = fs::remove_file(filename);
+        assert!(list_backups(filename).unwrap().is_empty());
+        let mut tasks = TaskList::new();
+        tasks.add(Task::new("one")).unwrap();
+        // the first save has nothing to back up, and 0 keeps no backups
+        save_tasks_with(filename, &tasks, 2).unwrap();
+        assert!(list_backups(filename).unwrap().is_empty());
+        tasks.add(Task::new("two")).unwrap();
+        save_tasks_with(filename, &tasks, 0).unwrap();
+        assert!(list_backups(filename).unwrap().is_empty());
+
+        // lowering the count drops the backups past it
+        for _ in 0..3 {
+            save_tasks_with(filename, &tasks, 3).unwrap();
+        }
+        assert_eq!(list_backups(filename).unwrap().len(), 3);
+        save_tasks_with(filename, &tasks, 1).unwrap();
+        let numbers: Vec<usize> = list_backups(filename)
+            .unwrap()
+            .iter()
+            .map(|b| b.number)
+            .collect();
+        assert_eq!(numbers, [1]);
+        save_tasks_with(filename, &tasks, 0).unwrap();
+        assert!(list_backups(filename).unwrap().is_empty());
+
+        // a damaged backup is refused and the current list left alone
+        fs::write(backup_path(filename, 1), "# task-format: 4\nnot a task\n").unwrap();
+        assert!(matches!(
+            restore_backup(filename, 1, 2),
+            Err(TaskError::Parse { .. })
+        ));
+        assert_eq!(load_tasks(filename).unwrap().len(), 2);
+        assert!(matches!(
+            restore_backup(filename, 0, 2),
+            Err(TaskError::NotFound(_))
+        ));
+        remove_task_files(filename);
+    }
+
+    #[test]
+    fn test_json_export_import() {
+        let filename = "test_tasks.json";
+        let mut task = Task::new("Test JSON");
+        task.priority = Priority::Medium;
+        let tasks = vec![task];
+        export_json(filename, &tasks).unwrap();
+        let loaded = import_json(filename).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].description, "Test JSON");
+        // exports from before statuses had a completed flag
+        let old = r#"{"description": "x", "completed": true, "priority": null, "due_date": null}"#;
+        let task: Task = serde_json::from_str(old).unwrap();
+        assert_eq!(task.status, Status::Done);
+        remove_task_files(filename);
+    }
+
+    #[test]
+    fn test_import_files_on_a_scale() {
+        let ten = PriorityScale::preset("0-10").unwrap();
+        let filename = "test_tasks_import.txt";
+        std::fs::write(filename, "Call Bob|0|9\nShip|1|0|2024-01-05\n").unwrap();
+        let tasks = import_file(filename, &ten).unwrap().tasks;
+        assert_eq!(tasks[0].priority, Priority::Critical);
+        assert_eq!(tasks[1].priority, Priority::None);
+        assert!(import_file(filename, &PriorityScale::default()).is_err());
+        remove_task_files(filename);
+
+        let filename = "test_tasks_import.json";
+        let old = r#"[{"description": "x", "completed": false, "priority": 7, "due_date": null},
+                      {"description": "y", "completed": true, "priority": null, "due_date": null}]"#;
+        std::fs::write(filename, old).unwrap();
+        let tasks = import_file(filename, &ten).unwrap().tasks;
+        assert_eq!(tasks[0].priority, Priority::High);
+        assert_eq!(tasks[1].status, Status::Done);
+        assert!(matches!(
+            import_file(filename, &PriorityScale::default()),
+            Err(TaskError::Validation(_))
+        ));
+        std::fs::remove_file(filename).unwrap();
+    }
+}
@@ -0,0 +1,390 @@
+//! The canonical `Task` model.
+
+use crate::TaskList;
+use crate::due::{self, Due};
+use crate::priority::Priority;
+use crate::recur::Recurrence;
+use chrono::{DateTime, FixedOffset, Local, NaiveDate};
+use colored::*;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Where a task is in its lifecycle.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "kebab-case")]
+pub enum Status {
+    #[default]
+    Pending,
+    InProgress,
+    /// Blocked on someone else; unlike a wait date it does not hide the task.
+    Waiting,
+    Done,
+    Cancelled,
+}
+
+impl Status {
+    pub const ALL: [Status; 5] = [
+        Status::Pending,
+        Status::InProgress,
+        Status::Waiting,
+        Status::Done,
+        Status::Cancelled,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Status::Pending => "pending",
+            Status::InProgress => "in-progress",
+            Status::Waiting => "waiting",
+            Status::Done => "done",
+            Status::Cancelled => "cancelled",
+        }
+    }
+
+    /// Done and cancelled tasks are closed: nothing is left to do on them.
+    pub fn is_closed(self) -> bool {
+        matches!(self, Status::Done | Status::Cancelled)
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_lowercase().replace('_', "-");
+        Status::ALL
+            .into_iter()
+            .find(|status| status.name() == name)
+            .ok_or_else(|| {
+                format!(
+                    "unknown status '{}' (expected pending, in-progress, waiting, done or cancelled)",
+                    s.trim()
+                )
+            })
+    }
+}
+
+/// Reads a status, or the `completed` flag that tasks had before statuses.
+fn status_or_flag<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Status, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Stored {
+        Flag(bool),
+        Status(Status),
+    }
+    Ok(match Stored::deserialize(deserializer)? {
+        Stored::Flag(true) => Status::Done,
+        Stored::Flag(false) => Status::Pending,
+        Stored::Status(status) => status,
+    })
+}
+
+/// A change of status and when it happened.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Transition {
+    pub status: Status,
+    pub at: DateTime<Local>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Task {
+    /// Short numeric id, assigned by `TaskList` and never reused.
+    #[serde(default)]
+    pub id: u32,
+    #[serde(default)]
+    pub uuid: Uuid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub description: String,
+    #[serde(default, alias = "completed", deserialize_with = "status_or_flag")]
+    pub status: Status,
+    /// Every status change, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transitions: Vec<Transition>,
+    #[serde(default)]
+    pub priority: Priority,
+    pub due_date: Option<NaiveDate>,
+    /// The exact moment on `due_date`, in the time zone it was given in;
+    /// none means any time that day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_at: Option<DateTime<FixedOffset>>,
+    /// When work on the task should start.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scheduled: Option<NaiveDate>,
+    /// Hidden from the usual views until this day.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait: Option<NaiveDate>,
+    /// Expires, and is hidden, once this day has passed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<NaiveDate>,
+    /// Lower-case tag names without the `+`, sorted and unique.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Dot-separated project path such as `work.backend.auth`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    /// The task this one is a subtask of.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent: Option<Uuid>,
+    /// Tasks that must be completed before this one.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends: Vec<Uuid>,
+    /// Completing the task adds its next occurrence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recur: Option<Recurrence>,
+    /// The first task of the recurring series this one belongs to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub series: Option<Uuid>,
+    /// When the task was added; unknown for tasks older than timestamps.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Local>>,
+    /// When the task last changed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified_at: Option<DateTime<Local>>,
+    /// When the task was last done or cancelled; none while it is open.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed_at: Option<DateTime<Local>>,
+}
+
+/// Whether `name` can be used as a tag or a project path segment: a letter
+/// followed by letters, digits, `-` or `_`.
+pub fn is_valid_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(char::is_alphabetic)
+        && chars.all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Whether `path` is a valid project: names separated by single dots.
+pub fn is_valid_project(path: &str) -> bool {
+    path.split('.').all(is_valid_name)
+}
+
+impl Task {
+    /// Creates a pending task with no priority or due date.
+    pub fn new(description: impl Into<String>) -> Self {
+        Task {
+            id: 0,
+            uuid: Uuid::new_v4(),
+            title: None,
+            description: description.into(),
+            status: Status::Pending,
+            transitions: Vec::new(),
+            priority: Priority::None,
+            due_date: None,
+            due_at: None,
+            scheduled: None,
+            wait: None,
+            until: None,
+            tags: Vec::new(),
+            project: None,
+            parent: None,
+            depends: Vec::new(),
+            recur: None,
+            series: None,
+            created_at: None,
+            modified_at: None,
+            completed_at: None,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.status == Status::Done
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.status.is_closed()
+    }
+
+    /// Moves the task to `status`, logging the change at `at`.
+    pub fn set_status(&mut self, status: Status, at: DateTime<Local>) {
+        if self.status != status {
+            self.status = status;
+            self.transitions.push(Transition { status, at });
+            self.modified_at = Some(at);
+            self.completed_at = status.is_closed().then_some(at);
+        }
+    }
+
+    /// When the task last entered `status`, if it ever did.
+    pub fn since(&self, status: Status) -> Option<DateTime<Local>> {
+        self.transitions
+            .iter()
+            .rev()
+            .find(|t| t.status == status)
+            .map(|t| t.at)
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Whether the task belongs to `project` or one of its subprojects.
+    pub fn in_project(&self, project: &str) -> bool {
+        let project = project.to_lowercase();
+        self.project.as_deref().is_some_and(|own| {
+            own == project
+                || own
+                    .strip_prefix(project.as_str())
+                    .is_some_and(|rest| rest.starts_with('.'))
+        })
+    }
+
+    /// Adds `tag` unless it is already there, keeping the tags sorted.
+    pub fn add_tag(&mut self, tag: &str) {
+        let tag = tag.to_lowercase();
+        if let Err(index) = self.tags.binary_search(&tag) {
+            self.tags.insert(index, tag);
+        }
+    }
+
+    pub fn remove_tag(&mut self, tag: &str) {
+        self.tags.retain(|t| !t.eq_ignore_ascii_case(tag));
+    }
+
+    pub fn due(&self) -> Option<Due> {
+        Some(Due {
+            date: self.due_date?,
+            time: self.due_at.map(|at| at.time()),
+            offset: self.due_at.map(|at| *at.offset()),
+        })
+    }
+
+    /// Sets the due date, and the exact moment if `due` has a time.
+    pub fn set_due(&mut self, due: Option<Due>) {
+        self.due_date = due.map(|due| due.date);
+        self.due_at = due.and_then(|due| due.at());
+    }
+
+    /// Whether an open task is still waiting for its wait date on `today`.
+    pub fn is_waiting(&self, today: NaiveDate) -> bool {
+        !self.is_closed() && self.wait.is_some_and(|wait| wait > today)
+    }
+
+    /// Whether an open task's `until` day has passed.
+    pub fn is_expired(&self, today: NaiveDate) -> bool {
+        !self.is_closed() && self.until.is_some_and(|until| until < today)
+    }
+
+    /// Waiting and expired tasks are left out of the usual views.
+    pub fn is_hidden(&self, today: NaiveDate) -> bool {
+        self.is_waiting(today) || self.is_expired(today)
+    }
+
+    /// Formats the task as a single colored list line led by its id.
+    pub fn render(&self) -> String {
+        self.render_with(None, &[])
+    }
+
+    /// Like `render`, adding what only the list knows: how many subtasks
+    /// are done and which open tasks this one waits for.
+    pub fn render_in(&self, task_list: &TaskList) -> String {
+        let blockers: Vec<u32> = task_list
+            .position_by_uuid(self.uuid)
+            .map(|index| task_list.blockers(index).iter().map(|t| t.id).collect())
+            .unwrap_or_default();
+        self.render_with(task_list.progress(self.uuid), &blockers)
+    }
+
+    fn render_with(&self, progress: Option<(usize, usize)>, blockers: &[u32]) -> String {
+        let status = match self.status {
+            Status::Pending => "[ ]".yellow(),
+            Status::InProgress => "[>]".blue(),
+            Status::Waiting => "[~]".magenta(),
+            Status::Done => "[x]".green(),
+            Status::Cancelled => "[-]".dimmed(),
+        };
+        let priority = match self.priority {
+            Priority::None => String::new(),
+            level => level.paint(&format!("(Priority: {})", level)).to_string(),
+        };
+
+        let now = Local::now();
+        let today = now.date_naive();
+        let due = if let Some(date) = self.due_date {
+            let when = self.due_at.map_or(date.to_string(), due::format_at);
+            if self.is_closed() {
+                format!("(Due: {})", when).cyan().to_string()
+            } else {
+                let relative = due::relative(date, self.due_at, now);
+                let text = format!("(Due: {}, {})", when, relative);
+                if relative.starts_with("overdue") {
+                    text.red().to_string()
+                } else {
+                    text.cyan().to_string()
+                }
+            }
+        } else {
+            "".to_string()
+        };
+
+        let mut text = match &self.title {
+            Some(title) => format!("{}: {}", title, self.description),
+            None => self.description.clone(),
+        };
+        if let Some((done, total)) = progress {
+            text.push_str(&format!(" ({}/{})", done, total));
+        }
+
+        let mut line = format!("{} {}. {} {} {}", status, self.id, text, priority, due);
+        let label = match self.status {
+            Status::InProgress => Some(
+                self.since(Status::InProgress)
+                    .map_or("(In progress)".blue(), |at| {
+                        format!("(In progress since {})", at.format("%Y-%m-%d %H:%M")).blue()
+                    }),
+            ),
+            Status::Waiting => Some("(Waiting on someone else)".magenta()),
+            Status::Cancelled => Some("(Cancelled)".dimmed()),
+            Status::Pending | Status::Done => None,
+        };
+        if let Some(label) = label {
+            line.push_str(&format!(" {}", label));
+        }
+        if !blockers.is_empty() {
+            let ids: Vec<String> = blockers.iter().map(u32::to_string).collect();
+            line.push_str(&format!(
+                " {}",
+                format!("(Blocked by: {})", ids.join(", ")).red()
+            ));
+        }
+        if let Some(scheduled) = self.scheduled.filter(|&day| day > today) {
+            line.push_str(&format!(" (Scheduled: {})", scheduled));
+        }
+        if self.is_waiting(today) {
+            let text = format!("(Waiting until {})", self.wait.unwrap());
+            line.push_str(&format!(" {}", text.dimmed()));
+        }
+        if let Some(until) = self.until.filter(|_| !self.is_closed()) {
+            let text = if self.is_expired(today) {
+                format!("(Expired {})", until).dimmed()
+            } else {
+                format!("(Until: {})", until).normal()
+            };
+            line.push_str(&format!(" {}", text));
+        }
+        if let Some(recur) = &self.recur {
+            line.push_str(&format!(" {}", format!("({})", recur).cyan()));
+        }
+        if let Some(project) = &self.project {
+            line.push_str(&format!(" {}", format!("project:{}", project).blue()));
+        }
+        for tag in &self.tags {
+            line.push_str(&format!(" {}", format!("+{}", tag).magenta()));
+        }
+        line
+    }
+
+    pub fn display(&self) {
+        println!("{}", self.render());
+    }
+}
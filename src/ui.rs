@@ -0,0 +1,112 @@
+//! The `Ui` trait through which interactive code talks to the user.
+//!
+//! `StdinUi` is the terminal implementation; `ScriptedUi` replays canned
+//! input and records output so menu flows can run in tests.
+
+use std::collections::VecDeque;
+use std::io::{self, Write};
+
+pub trait Ui {
+    /// Shows `prompt` and reads one trimmed line; `None` once input is exhausted.
+    fn read_line(&mut self, prompt: &str) -> Option<String>;
+
+    /// Shows one line of output.
+    fn print(&mut self, text: &str);
+
+    /// Like `read_line`, but treats end of input as an empty answer.
+    fn ask(&mut self, prompt: &str) -> String {
+        self.read_line(prompt).unwrap_or_default()
+    }
+
+    /// Reads lines until a single `.` and joins them with newlines.
+    fn read_multiline(&mut self) -> String {
+        let mut text = String::new();
+        while let Some(line) = self.read_line("") {
+            if line == "." {
+                break;
+            }
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            text.push_str(&line);
+        }
+        text.trim().to_string()
+    }
+
+    /// Asks a y/n question; anything but `y` counts as no.
+    fn confirm(&mut self, prompt: &str) -> bool {
+        self.ask(prompt).eq_ignore_ascii_case("y")
+    }
+
+    /// Offers numbered `options` on one line and returns the chosen index.
+    fn choose(&mut self, prompt: &str, options: &[&str]) -> Option<usize> {
+        let listed: Vec<String> = options
+            .iter()
+            .enumerate()
+            .map(|(i, option)| format!("{}. {}", i + 1, option))
+            .collect();
+        self.print(&format!("{}: {}", prompt, listed.join("  ")));
+        match self.ask("Enter your choice: ").parse::<usize>() {
+            Ok(n) if n >= 1 && n <= options.len() => Some(n - 1),
+            _ => None,
+        }
+    }
+}
+
+/// Reads from stdin and prints to stdout.
+#[derive(Debug, Default)]
+pub struct StdinUi;
+
+impl Ui for StdinUi {
+    fn read_line(&mut self, prompt: &str) -> Option<String> {
+        print!("{}", prompt);
+        io::stdout().flush().ok()?;
+        let mut line = String::new();
+        match io::stdin().read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line.trim_end_matches(['\r', '\n']).trim().to_string()),
+        }
+    }
+
+    fn print(&mut self, text: &str) {
+        println!("{}", text);
+    }
+}
+
+/// Answers prompts from a fixed list of lines and keeps everything printed.
+#[derive(Debug, Default)]
+pub struct ScriptedUi {
+    input: VecDeque<String>,
+    pub output: Vec<String>,
+}
+
+impl ScriptedUi {
+    pub fn new<I, S>(lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            input: lines.into_iter().map(Into::into).collect(),
+            output: Vec::new(),
+        }
+    }
+
+    /// Everything printed so far, one entry per line.
+    pub fn transcript(&self) -> String {
+        self.output.join("\n")
+    }
+}
+
+impl Ui for ScriptedUi {
+    fn read_line(&mut self, prompt: &str) -> Option<String> {
+        if !prompt.is_empty() {
+            self.output.push(prompt.trim_end().to_string());
+        }
+        self.input.pop_front().map(|line| line.trim().to_string())
+    }
+
+    fn print(&mut self, text: &str) {
+        self.output.push(text.to_string());
+    }
+}
@@ -0,0 +1,317 @@
+//! The urgency score: one number that weighs a task's priority, how soon
+//! it is due, its age, tags and project, whether it is started, waiting,
+//! blocked or blocking others. Each factor is scaled by a coefficient that
+//! `urgency.<factor>` in the config file can change.
+
+use crate::task::Status;
+use crate::{Priority, Task, TaskList};
+use chrono::{Local, NaiveDate};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+/// Scores by task uuid, for sorting.
+pub type Scores = HashMap<Uuid, f64>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Urgency {
+    /// For critical priority; each lower level gets a fifth less.
+    pub priority: f64,
+    /// For a task a week or more overdue, down to a fifth of it for one
+    /// due in two weeks or later.
+    pub due: f64,
+    /// For a task a year old or older, less for younger ones.
+    pub age: f64,
+    /// For three tags or more; one tag counts 0.8 and two 0.9 of it.
+    pub tags: f64,
+    pub project: f64,
+    /// For a task whose scheduled day has come.
+    pub scheduled: f64,
+    pub in_progress: f64,
+    pub waiting: f64,
+    pub blocked: f64,
+    /// For a task other open tasks depend on.
+    pub blocking: f64,
+    /// Added for each of these tags the task carries.
+    pub tag: BTreeMap<String, f64>,
+}
+
+impl Default for Urgency {
+    fn default() -> Self {
+        Self {
+            priority: 6.0,
+            due: 12.0,
+            age: 2.0,
+            tags: 1.0,
+            project: 1.0,
+            scheduled: 5.0,
+            in_progress: 4.0,
+            waiting: -3.0,
+            blocked: -5.0,
+            blocking: 8.0,
+            tag: BTreeMap::new(),
+        }
+    }
+}
+
+/// One factor's part of a score.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Contribution {
+    pub reason: String,
+    pub points: f64,
+}
+
+impl Urgency {
+    /// The coefficient named `name` as written in the config file, e.g.
+    /// `in-progress`.
+    pub fn coefficient_mut(&mut self, name: &str) -> Option<&mut f64> {
+        if let Some(tag) = name.strip_prefix("tag.") {
+            return (!tag.is_empty()).then(|| self.tag.entry(tag.to_lowercase()).or_default());
+        }
+        Some(match name {
+            "priority" => &mut self.priority,
+            "due" => &mut self.due,
+            "age" => &mut self.age,
+            "tags" => &mut self.tags,
+            "project" => &mut self.project,
+            "scheduled" => &mut self.scheduled,
+            "in-progress" => &mut self.in_progress,
+            "waiting" => &mut self.waiting,
+            "blocked" => &mut self.blocked,
+            "blocking" => &mut self.blocking,
+            _ => return None,
+        })
+    }
+
+    /// What makes up the score of the task at `index`, leaving out factors
+    /// that add nothing. Closed tasks score nothing.
+    pub fn breakdown(
+        &self,
+        task_list: &TaskList,
+        index: usize,
+        today: NaiveDate,
+    ) -> Vec<Contribution> {
+        let task = task_list.get(index).unwrap();
+        if task.is_closed() {
+            return Vec::new();
+        }
+        let mut parts = Vec::new();
+        let mut add = |reason: String, coefficient: f64, factor: f64| {
+            parts.push(Contribution {
+                reason,
+                points: coefficient * factor,
+            });
+        };
+        let factor = match task.priority {
+            Priority::Critical => 1.0,
+            Priority::High => 0.8,
+            Priority::Medium => 0.6,
+            Priority::Low => 0.4,
+            Priority::None => 0.0,
+        };
+        if factor > 0.0 {
+            add(format!("priority {}", task.priority), self.priority, factor);
+        }
+        if let Some(due) = task.due_date {
+            let days = (due - today).num_days();
+            let reason = match days {
+                0 => "due today".to_string(),
+                1 => "due tomorrow".to_string(),
+                _ if days < 0 => format!("overdue by {} day(s)", -days),
+                _ => format!("due in {} days", days),
+            };
+            let factor = 0.2 + 0.8 * (14 - days.clamp(-7, 14)) as f64 / 21.0;
+            add(reason, self.due, factor);
+        }
+        if let Some(created) = task.created_at {
+            let days = (today - created.date_naive()).num_days().max(0);
+            let factor = (days as f64 / 365.0).min(1.0);
+            add(format!("{} day(s) old", days), self.age, factor);
+        }
+        let factor = match task.tags.len() {
+            0 => 0.0,
+            1 => 0.8,
+            2 => 0.9,
+            _ => 1.0,
+        };
+        if factor > 0.0 {
+            add(format!("{} tag(s)", task.tags.len()), self.tags, factor);
+        }
+        for tag in &task.tags {
+            if let Some(&coefficient) = self.tag.get(tag) {
+                add(format!("+{}", tag), coefficient, 1.0);
+            }
+        }
+        if let Some(project) = &task.project {
+            add(format!("project {}", project), self.project, 1.0);
+        }
+        if task.scheduled.is_some_and(|day| day <= today) {
+            add("scheduled".to_string(), self.scheduled, 1.0);
+        }
+        if task.status == Status::InProgress {
+            add("in progress".to_string(), self.in_progress, 1.0);
+        }
+        if task.status == Status::Waiting || task.is_waiting(today) {
+            add("waiting".to_string(), self.waiting, 1.0);
+        }
+        let blockers = task_list.blockers(index).len();
+        if blockers > 0 {
+            add(
+                format!("blocked by {} task(s)", blockers),
+                self.blocked,
+                1.0,
+            );
+        }
+        let blocking = task_list
+            .iter()
+            .filter(|other| !other.is_closed() && other.depends.contains(&task.uuid))
+            .count();
+        if blocking > 0 {
+            add(format!("blocking {} task(s)", blocking), self.blocking, 1.0);
+        }
+        parts.retain(|part| part.points != 0.0);
+        parts
+    }
+
+    /// The `why` listing: the score, then each factor's points.
+    pub fn explain(&self, task_list: &TaskList, index: usize, today: NaiveDate) -> Vec<String> {
+        let task = task_list.get(index).unwrap();
+        if task.is_closed() {
+            return vec![format!(
+                "Task {} is {}, so it has no urgency.",
+                task.id, task.status
+            )];
+        }
+        let parts = self.breakdown(task_list, index, today);
+        let total = total(&parts);
+        let mut lines = vec![format!("Task {} has urgency {:.1}:", task.id, total)];
+        if parts.is_empty() {
+            lines.push("  nothing adds to it".to_string());
+        }
+        for part in parts {
+            lines.push(format!("  {:+6.1}  {}", part.points, part.reason));
+        }
+        lines
+    }
+
+    pub fn score(&self, task_list: &TaskList, index: usize, today: NaiveDate) -> f64 {
+        total(&self.breakdown(task_list, index, today))
+    }
+
+    /// Every task's score as of today.
+    pub fn scores(&self, task_list: &TaskList) -> Scores {
+        let today = Local::now().date_naive();
+        (0..task_list.len())
+            .map(|index| {
+                let task: &Task = task_list.get(index).unwrap();
+                (task.uuid, self.score(task_list, index, today))
+            })
+            .collect()
+    }
+}
+
+/// The points added up from 0.0; `Iterator::sum` gives -0.0 for none,
+/// which would show as `-0.0`.
+fn total(parts: &[Contribution]) -> f64 {
+    parts.iter().fold(0.0, |total, part| total + part.points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_urgency_weighs_every_factor() {
+        let today = Local::now().date_naive();
+        let mut task_list = TaskList::new();
+        let mut high = Task::new("P1 next year");
+        high.priority = Priority::Critical;
+        high.due_date = Some(today + Duration::days(365));
+        task_list.add(high).unwrap();
+        let mut due = Task::new("P3 due today");
+        due.priority = Priority::Medium;
+        due.due_date = Some(today);
+        task_list.add(due).unwrap();
+        let urgency = Urgency::default();
+        let score = |list: &TaskList, index| urgency.score(list, index, today);
+        assert!(score(&task_list, 1) > score(&task_list, 0));
+
+        let parts = urgency.breakdown(&task_list, 1, today);
+        assert_eq!(parts[0].reason, "priority medium");
+        assert!((parts[0].points - 3.6).abs() < 1e-9);
+        assert_eq!(parts[1].reason, "due today");
+        assert!((parts[1].points - 8.8).abs() < 1e-9);
+
+        let blocker = task_list.get(1).unwrap().uuid;
+        task_list.get_mut(0).unwrap().depends.push(blocker);
+        task_list.get_mut(0).unwrap().tags = vec!["urgent".to_string()];
+        let mut custom = Urgency::default();
+        *custom.coefficient_mut("tag.urgent").unwrap() = 10.0;
+        *custom.coefficient_mut("in-progress").unwrap() = 0.0;
+        assert!(custom.coefficient_mut("colour").is_none());
+        let reasons: Vec<String> = custom
+            .breakdown(&task_list, 0, today)
+            .into_iter()
+            .map(|part| part.reason)
+            .collect();
+        assert_eq!(
+            reasons,
+            [
+                "priority critical",
+                "due in 365 days",
+                "1 tag(s)",
+                "+urgent",
+                "blocked by 1 task(s)"
+            ]
+        );
+        assert!(
+            custom
+                .breakdown(&task_list, 1, today)
+                .iter()
+                .any(|part| part.reason == "blocking 1 task(s)" && part.points == 8.0)
+        );
+
+        assert_eq!(
+            urgency.explain(&task_list, 1, today)[..2],
+            ["Task 2 has urgency 20.4:", "    +3.6  priority medium"]
+        );
+        task_list.get_mut(1).unwrap().status = Status::Done;
+        assert!(urgency.breakdown(&task_list, 1, today).is_empty());
+        assert_eq!(urgency.scores(&task_list)[&blocker], 0.0);
+    }
+
+    #[test]
+    fn test_coefficients_from_taskrc_and_ties() {
+        use crate::config::Config;
+        use crate::sort::{SortKey, SortSpec};
+        let today = Local::now().date_naive();
+        let mut task_list = TaskList::new();
+        let mut important = Task::new("Important");
+        important.priority = Priority::High;
+        task_list.add(important).unwrap();
+        let mut due = Task::new("Due today");
+        due.due_date = Some(today);
+        task_list.add(due).unwrap();
+        let order = |urgency: &Urgency| -> Vec<String> {
+            let mut tasks: Vec<&Task> = task_list.iter().collect();
+            SortSpec::of(&[SortKey::Urgency]).sort(&mut tasks, &task_list, urgency);
+            tasks.iter().map(|t| t.description.clone()).collect()
+        };
+        assert_eq!(order(&Urgency::default()), ["Due today", "Important"]);
+
+        let config = Config::parse("urgency.priority = 20\n").unwrap();
+        assert_eq!(order(&config.urgency), ["Important", "Due today"]);
+        // with nothing counting, every score ties and the list keeps its order
+        let config =
+            Config::parse("urgency.priority = 0\nurgency.due = 0\nurgency.age = 0\n").unwrap();
+        assert_eq!(config.urgency.score(&task_list, 0, today), 0.0);
+        assert_eq!(config.urgency.score(&task_list, 1, today), 0.0);
+        let shown = format!("{:.1}", config.urgency.score(&task_list, 1, today));
+        assert_eq!(shown, "0.0");
+        assert_eq!(order(&config.urgency), ["Important", "Due today"]);
+
+        for bad in ["urgency.due = NaN", "urgency.due = inf", "urgency.tag = 3"] {
+            assert!(Config::parse(bad).is_err(), "{}", bad);
+        }
+    }
+}
@@ -0,0 +1,481 @@
+use std::path::{Path, PathBuf};
+use task::history::{DEFAULT_HISTORY_LIMIT, History, history_path};
+use task::journal::journal_path;
+use task::lock::lock_path;
+use task::menu::Menu;
+use task::storage::{export_json, load_tasks, load_tasks_with, save_tasks};
+use task::task::Status;
+use task::ui::ScriptedUi;
+use task::{Priority, Task, TaskList};
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("task-menu-{}-{}", std::process::id(), name))
+}
+
+/// Removes a task file along with the lock, history and journal files that
+/// loading and saving leave behind.
+fn remove_task_file(path: &Path) {
+    let path = path.to_str().unwrap();
+    std::fs::remove_file(path).unwrap();
+    for companion in [lock_path(path), history_path(path), journal_path(path)] {
+        let _ = std::fs::remove_file(companion);
+    }
+}
+
+fn run_menu(task_list: TaskList, file: &Path, json: &Path, input: &[&str]) -> (TaskList, String) {
+    let mut ui = ScriptedUi::new(input.iter().copied());
+    let mut menu = Menu::new(
+        &mut ui,
+        task_list,
+        file.to_str().unwrap(),
+        json.to_str().unwrap(),
+    );
+    menu.run();
+    let task_list = menu.task_list().clone();
+    (task_list, ui.transcript())
+}
+
+#[test]
+fn add_mark_and_save_through_menu() {
+    let file = temp_path("add.txt");
+    let json = temp_path("add.json");
+    let input = [
+        "1",
+        "Buy milk",
+        "and bread",
+        ".",
+        "2",
+        "2026-11-01", // add
+        "5",
+        "1", // toggle complete
+        "7", // save
+        "13",
+    ];
+    let (task_list, transcript) = run_menu(TaskList::new(), &file, &json, &input);
+
+    assert_eq!(task_list.len(), 1);
+    let task = task_list.get(0).unwrap();
+    assert_eq!(task.description, "Buy milk\nand bread");
+    assert_eq!(task.priority, Priority::High);
+    assert!(task.is_done());
+    assert!(transcript.contains("Task 1 added."));
+    assert!(transcript.contains("Task marked as completed."));
+    assert!(transcript.contains("Tasks saved to file."));
+
+    let saved = load_tasks(file.to_str().unwrap()).unwrap();
+    assert_eq!(saved.get(0), Some(task));
+    remove_task_file(&file);
+}
+
+#[test]
+fn failed_import_keeps_current_tasks() {
+    let file = temp_path("import.txt");
+    let json = temp_path("import.json");
+    std::fs::write(&json, "not json").unwrap();
+    let mut task_list = TaskList::new();
+    task_list.add(Task::new("Keep me")).unwrap();
+
+    let (task_list, transcript) = run_menu(task_list, &file, &json, &["10", "13"]);
+
+    assert_eq!(task_list.len(), 1);
+    assert!(transcript.contains("Failed to import tasks"));
+    std::fs::remove_file(json).unwrap();
+}
+
+#[test]
+fn import_then_undo_and_remove() {
+    let file = temp_path("undo.txt");
+    let json = temp_path("undo.json");
+    export_json(json.to_str().unwrap(), &[Task::new("Imported")]).unwrap();
+
+    let input = [
+        "10", // import
+        "2", "1", "y", // remove task 1
+        "11", "y", // undo the removal
+        "6", "IMPORT", // search
+    ];
+    let (task_list, transcript) = run_menu(TaskList::new(), &file, &json, &input);
+
+    assert_eq!(task_list.len(), 1);
+    assert!(transcript.contains("Task removed."));
+    assert!(transcript.contains("Undo successful."));
+    assert!(transcript.contains("Imported"));
+    // input ran out without choosing Exit
+    assert!(transcript.ends_with("Exiting..."));
+    std::fs::remove_file(json).unwrap();
+}
+
+#[test]
+fn invalid_choices_are_reported() {
+    let file = temp_path("invalid.txt");
+    let json = temp_path("invalid.json");
+    let (_, transcript) = run_menu(TaskList::new(), &file, &json, &["42", "4", "12", "9", "13"]);
+    assert!(transcript.contains("Invalid choice. Please try again."));
+    assert!(transcript.contains("No tasks found."));
+    assert!(transcript.contains("Invalid choice."));
+}
+
+#[test]
+fn corrupt_file_offers_lenient_load() {
+    let file = temp_path("corrupt.txt");
+    std::fs::write(&file, "good|0||\nbad|2|x|\n").unwrap();
+    let options = task::cli::Options {
+        file: file.to_str().unwrap().to_string(),
+        ..Default::default()
+    };
+
+    let mut ui = ScriptedUi::new(["n"]);
+    assert_eq!(task::cli::run_menu(&options, &mut ui), 1);
+    assert!(ui.transcript().contains("line 2: "));
+
+    let mut ui = ScriptedUi::new(["y", "3", "13"]);
+    assert_eq!(task::cli::run_menu(&options, &mut ui), 0);
+    assert!(ui.transcript().contains("good"));
+    let rejected = task::storage::rejected_path(options.file.as_str());
+    assert!(
+        std::fs::read_to_string(&rejected)
+            .unwrap()
+            .contains("bad|2|x|")
+    );
+    remove_task_file(&file);
+    std::fs::remove_file(&rejected).unwrap();
+}
+
+#[test]
+fn save_after_outside_change_offers_merge() {
+    let file = temp_path("shared.txt");
+    let json = temp_path("shared.json");
+    let path = file.to_str().unwrap();
+    let mut original = TaskList::new();
+    original.add(Task::new("Shared")).unwrap();
+    save_tasks(path, &original).unwrap();
+    let report = load_tasks_with(path, Default::default()).unwrap();
+
+    // another copy of the program adds a task after we loaded
+    let mut theirs = report.tasks.clone();
+    theirs.add(Task::new("Theirs")).unwrap();
+    save_tasks(path, &theirs).unwrap();
+
+    let mut ui = ScriptedUi::new([
+        "1", "Mine", ".", "", "", // add
+        "7", "2", // save, then merge
+        "13",
+    ]);
+    let mut menu = Menu::new(&mut ui, report.tasks, path, json.to_str().unwrap());
+    menu.set_loaded(report.stamp);
+    menu.run();
+    let transcript = ui.transcript();
+
+    assert!(transcript.contains("was changed by another process"));
+    assert!(transcript.contains("Tasks saved to file."));
+    let saved = load_tasks(path).unwrap();
+    let descriptions: Vec<&str> = saved.iter().map(|t| t.description.as_str()).collect();
+    assert_eq!(descriptions, ["Shared", "Theirs", "Mine"]);
+    assert_eq!(saved.get(2).unwrap().id, 3);
+
+    remove_task_file(&file);
+    for backup in task::storage::list_backups(path).unwrap() {
+        std::fs::remove_file(backup.path).unwrap();
+    }
+}
+
+#[test]
+fn save_keeps_history_recorded_elsewhere() {
+    let file = temp_path("history.txt");
+    let json = temp_path("history.json");
+    let path = file.to_str().unwrap();
+    save_tasks(path, &TaskList::new()).unwrap();
+    let report = load_tasks_with(path, Default::default()).unwrap();
+
+    // another copy of the program records a step after we loaded
+    let mut theirs = report.tasks.clone();
+    let op = task::ops::add_task(&mut theirs, task::ops::NewTask::new("Theirs")).unwrap();
+    let mut history = History::load(path, DEFAULT_HISTORY_LIMIT).unwrap();
+    history.record(op);
+    history.save(path).unwrap();
+
+    let mut ui = ScriptedUi::new([
+        "1", "Mine", ".", "", "", // add
+        "7", "13", // save
+    ]);
+    let mut menu = Menu::new(&mut ui, report.tasks, path, json.to_str().unwrap());
+    menu.set_loaded(report.stamp);
+    menu.run();
+
+    let history = History::load(path, DEFAULT_HISTORY_LIMIT).unwrap();
+    let steps: Vec<String> = history.done().map(|step| step.op.to_string()).collect();
+    assert_eq!(steps, ["add task 1 'Theirs'", "add task 1 'Mine'"]);
+    remove_task_file(&file);
+}
+
+#[test]
+fn exit_guard_offers_to_save_changes() {
+    let file = temp_path("guard.txt");
+    let json = temp_path("guard.json");
+    let input = [
+        "1", "Unsaved", ".", "", "", // add
+        "13", "3", // exit, then cancel
+        "3", // still in the menu
+        "13", "1", // exit, saving first
+    ];
+    let (task_list, transcript) = run_menu(TaskList::new(), &file, &json, &input);
+
+    assert!(transcript.contains("You have unsaved changes"));
+    assert_eq!(transcript.matches("Exiting...").count(), 1);
+    assert!(transcript.contains("Tasks saved to file."));
+    assert!(!task_list.is_dirty());
+    assert_eq!(load_tasks(file.to_str().unwrap()).unwrap().len(), 1);
+    remove_task_file(&file);
+
+    let (_, transcript) = run_menu(task_list, &file, &json, &["5", "1", "13", "2"]);
+    assert!(transcript.ends_with("Exiting..."));
+    assert!(!file.exists(), "discarding must not save");
+}
+
+#[test]
+fn autosave_writes_after_each_change() {
+    let file = temp_path("autosave.txt");
+    let json = temp_path("autosave.json");
+    let path = file.to_str().unwrap();
+    let mut ui = ScriptedUi::new([
+        "1",
+        "Saved at once",
+        ".",
+        "",
+        "",  // add
+        "3", // view: nothing to save
+    ]);
+    let mut menu = Menu::new(&mut ui, TaskList::new(), path, json.to_str().unwrap());
+    menu.set_autosave(true);
+    menu.run();
+    let transcript = ui.transcript();
+
+    assert_eq!(transcript.matches("Tasks saved to file.").count(), 1);
+    assert!(!transcript.contains("unsaved changes"));
+    assert_eq!(load_tasks(path).unwrap().len(), 1);
+    remove_task_file(&file);
+}
+
+#[test]
+fn redo_and_history_through_menu() {
+    let file = temp_path("redo.txt");
+    let json = temp_path("redo.json");
+    let input = [
+        "1", "Undo me", ".", "", "", // add
+        "11", "y",  // undo
+        "14", // redo
+        "14", // nothing left to redo
+        "15", // history
+        "13", "2",
+    ];
+    let (task_list, transcript) = run_menu(TaskList::new(), &file, &json, &input);
+
+    assert_eq!(task_list.len(), 1);
+    assert!(transcript.contains("Undo successful."));
+    assert!(transcript.contains("Redo successful. Reapplied: add task 1 'Undo me'."));
+    assert!(transcript.contains("Nothing to redo."));
+    assert!(transcript.contains("  1. "));
+}
+
+#[test]
+fn subtasks_through_menu() {
+    let file = temp_path("subtasks.txt");
+    let json = temp_path("subtasks.json");
+    let input = [
+        "1", "Release", ".", "", "", // add
+        "16", "1", "Build", ".", "", "", // add a subtask
+        "17", "2", "", // move it to the top level
+        "17", "2", "1", // and back
+        "5", "2", // complete the subtask
+        "3", "13", "2",
+    ];
+    let mut ui = ScriptedUi::new(input);
+    let mut menu = Menu::new(
+        &mut ui,
+        TaskList::new(),
+        file.to_str().unwrap(),
+        json.to_str().unwrap(),
+    );
+    menu.set_complete_parents(true);
+    menu.run();
+    let task_list = menu.task_list().clone();
+    let transcript = ui.transcript();
+
+    assert!(task_list.iter().all(|t| t.is_done()));
+    assert_eq!(transcript.matches("Task moved.").count(), 2);
+    assert!(transcript.contains("1 related task(s) changed too."));
+    assert!(transcript.contains("Release (1/1)"));
+    // a blank urgency column, then the subtask's indent
+    assert!(transcript.contains(&format!("\n{:6}    [x] 2. Build", "")));
+}
+
+#[test]
+fn relative_due_dates_are_confirmed() {
+    let file = temp_path("due.txt");
+    let json = temp_path("due.json");
+    let input = [
+        "1",
+        "Call the bank",
+        ".",
+        "",             // add
+        "someday",      // not understood, asked again
+        "tomorrow 9am", // echoed back
+        "n",            // and rejected
+        "in 2 days 5pm",
+        "y",
+        "22",
+        "1",
+        "2", // scheduled
+        "tomorrow",
+        "y",
+        "13",
+        "2",
+    ];
+    let (task_list, transcript) = run_menu(TaskList::new(), &file, &json, &input);
+
+    let task = task_list.get(0).unwrap();
+    let expected = chrono::Local::now().date_naive() + chrono::Days::new(2);
+    assert_eq!(task.due_date, Some(expected));
+    assert_eq!(
+        task.due_at.map(|at| at.time()),
+        chrono::NaiveTime::from_hms_opt(17, 0, 0)
+    );
+    assert!(transcript.contains("Cannot understand the due date 'someday'"));
+    assert!(transcript.contains(&format!(
+        "The due date will be {} 17:00. Is that right?",
+        expected.format("%a %Y-%m-%d")
+    )));
+    let tomorrow = chrono::Local::now().date_naive() + chrono::Days::new(1);
+    assert!(transcript.contains(&format!(
+        "The scheduled date will be {}. Is that right?",
+        tomorrow.format("%a %Y-%m-%d")
+    )));
+    assert_eq!(task.scheduled, Some(tomorrow));
+}
+
+#[test]
+fn status_changes_through_menu() {
+    let file = temp_path("status.txt");
+    let json = temp_path("status.json");
+    let input = [
+        "1",
+        "Write the report",
+        ".",
+        "",
+        "",   // add
+        "23", // change status
+        "1",
+        "2", // in-progress
+        "23",
+        "1",
+        "9", // not a status
+        "23",
+        "1",
+        "5", // cancelled
+        "13",
+        "2",
+    ];
+    let (task_list, transcript) = run_menu(TaskList::new(), &file, &json, &input);
+
+    let task = task_list.get(0).unwrap();
+    assert_eq!(task.status, Status::Cancelled);
+    assert_eq!(task.transitions.len(), 2);
+    assert!(transcript.contains("Task started."));
+    assert!(transcript.contains("[>] 1. Write the report"));
+    assert!(transcript.contains("Invalid choice."));
+    assert!(transcript.contains("Task cancelled."));
+}
+
+#[test]
+fn only_blocked_tasks_are_offered_to_complete_anyway() {
+    let file = temp_path("force.txt");
+    let json = temp_path("force.json");
+    let mut task_list = TaskList::new();
+    for (name, uuid) in [
+        ("Design", "abc00000-0000-4000-8000-000000000001"),
+        ("Build", "abc00000-0000-4000-8000-000000000002"),
+    ] {
+        let mut task = Task::new(name);
+        task.uuid = uuid.parse().unwrap();
+        task_list.add(task).unwrap();
+    }
+    let design = task_list.get(0).unwrap().uuid;
+    task_list.get_mut(1).unwrap().depends.push(design);
+    let input = [
+        "5", "abc", // ambiguous: no prompt
+        "5", "2", "n", // blocked: asked, and declined
+        "13", "2",
+    ];
+    let (task_list, transcript) = run_menu(task_list, &file, &json, &input);
+
+    assert!(transcript.contains("'abc' matches more than one task"));
+    assert_eq!(transcript.matches("Complete it anyway?").count(), 1);
+    assert!(transcript.contains("Task 2 is blocked by task(s) 1."));
+    assert!(task_list.iter().all(|t| !t.is_done()));
+}
+
+#[test]
+fn reports_through_menu() {
+    let file = temp_path("report.txt");
+    let json = temp_path("report.json");
+    let mut task_list = TaskList::new();
+    for (description, due) in [
+        ("Renew passport", "2020-01-01"),
+        ("Plan trip", "2999-01-01"),
+    ] {
+        let mut task = Task::new(description);
+        task.due_date = due.parse().ok();
+        task_list.add(task).unwrap();
+    }
+    let input = [
+        "24", "2", // overdue
+        "24", "9", // not a report
+        "26", "1", // why
+        "13",
+    ];
+    let (_, transcript) = run_menu(task_list, &file, &json, &input);
+
+    assert!(transcript.contains("Report: 1. today ("));
+    assert!(transcript.contains("ID  PRIORITY  DUE"));
+    assert!(transcript.contains("Renew passport"));
+    let reports = &transcript[..transcript.find("--- Task List ---").unwrap()];
+    assert!(!reports.contains("Plan trip"));
+    assert!(transcript.contains("Invalid choice."));
+    assert!(transcript.contains("Task 1 has urgency 12.0:"));
+    assert!(transcript.contains("+12.0  overdue by "));
+}
+
+#[test]
+fn sorted_views_leave_the_list_alone() {
+    let file = temp_path("sorted.txt");
+    let json = temp_path("sorted.json");
+    let mut task_list = TaskList::new();
+    for (description, priority) in [
+        ("Low", Priority::Low),
+        ("Unset", Priority::None),
+        ("High", Priority::Critical),
+    ] {
+        let mut task = Task::new(description);
+        task.priority = priority;
+        task_list.add(task).unwrap();
+    }
+    task_list.mark_saved();
+    let input = [
+        "25",
+        "priority-:none-first", // view only
+        "12",
+        "10",
+        "priority+,bogus", // rejected
+        "13",
+    ];
+    let (task_list, transcript) = run_menu(task_list, &file, &json, &input);
+
+    let order: Vec<&str> = task_list.iter().map(|t| t.description.as_str()).collect();
+    assert_eq!(order, ["Low", "Unset", "High"]);
+    let unset = transcript.find("2. Unset").unwrap();
+    let low = transcript.find("1. Low").unwrap();
+    let high = transcript.find("3. High").unwrap();
+    assert!(unset < low && low < high);
+    assert!(transcript.contains("Invalid sort: unknown sort key 'bogus'."));
+    assert!(!file.exists());
+}